@@ -0,0 +1,12 @@
+fn main() {
+    // Only the `grpc` feature has anything to build: the service and
+    // messages in `proto/envin.proto`, compiled with a bundled `protoc` so
+    // none has to be installed.
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/envin.proto");
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("no bundled protoc");
+        std::env::set_var("PROTOC", protoc);
+        tonic_build::compile_protos("proto/envin.proto").expect("failed to compile the protos");
+    }
+}
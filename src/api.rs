@@ -0,0 +1,55 @@
+//! The JSON API `envin serve --http` answers, one endpoint per section.
+
+use {
+    crate::{
+        serve::{Request, Response},
+        Report,
+    },
+    serde_json::{Map, Value},
+};
+
+/// The `Content-Type` of every answer.
+pub const CONTENT_TYPE: &str = "application/json";
+
+/// The fields of the system section `/mem` answers with.
+const MEMORY_FIELDS: &[&str] = &[
+    "memory_used_bytes",
+    "memory_total_bytes",
+    "memory",
+    "swap_used_bytes",
+    "swap_total_bytes",
+    "swap",
+];
+
+/// Answers `GET /` with the whole report, `/cpu` and `/mem` with those
+/// parts of the system section, and `/<section>` with any collected
+/// section, such as `/system`, `/processes` or `/disk-io`.
+pub fn respond(request: &Request, report: &Report) -> Response {
+    if request.method != "GET" {
+        return Response::method_not_allowed();
+    }
+    match route(report, &request.path).and_then(|value| serde_json::to_string_pretty(&value).ok()) {
+        Some(body) => Response::ok(CONTENT_TYPE, body),
+        None => Response::not_found(),
+    }
+}
+
+/// Looks up the part of `report` that `path` names, `None` if it was not
+/// collected.
+pub fn route(report: &Report, path: &str) -> Option<Value> {
+    let report = serde_json::to_value(report).ok()?;
+    let name = path.trim_matches('/').replace('-', "_");
+    match name.as_str() {
+        "" | "report" => Some(report),
+        "cpu" => report.get("system")?.get("cpu").cloned(),
+        "mem" | "memory" => {
+            let system = report.get("system")?;
+            let memory: Map<String, Value> = MEMORY_FIELDS
+                .iter()
+                .filter_map(|field| Some((field.to_string(), system.get(*field)?.clone())))
+                .collect();
+            Some(Value::Object(memory))
+        }
+        name => report.get(name).cloned(),
+    }
+}
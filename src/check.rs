@@ -0,0 +1,573 @@
+//! Threshold checks for `envin check`, which exits with the status of the
+//! worst one so it can gate CI jobs and health checks.
+
+use {
+    crate::{
+        format::{format_bytes, parse_byte_size, ByteUnits},
+        Report, Sections,
+    },
+    serde::Serialize,
+    std::fmt,
+};
+
+/// How a check came out, from best to worst.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Status {
+    Ok,
+    Warning,
+    /// The value could not be read, such as a mount that does not exist.
+    Unknown,
+    Critical,
+}
+
+impl Status {
+    /// The exit code monitoring systems expect: 0 for OK, 1 for warning, 2
+    /// for critical and 3 for unknown.
+    pub fn code(self) -> i32 {
+        match self {
+            Status::Ok => 0,
+            Status::Warning => 1,
+            Status::Critical => 2,
+            Status::Unknown => 3,
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Status::Ok => "OK",
+            Status::Warning => "WARNING",
+            Status::Critical => "CRITICAL",
+            Status::Unknown => "UNKNOWN",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+}
+
+/// What a healthy value looks like, such as `<90` or `>=5GiB`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Condition {
+    comparison: Comparison,
+    value: f64,
+    /// As given, to explain a failure.
+    text: String,
+}
+
+impl Condition {
+    /// The Nagios range a plugin alerts on. Plain ranges include their ends,
+    /// so `<=90` is healthy `~:90`, while `<90` alerts on `@90:`, 90 and up,
+    /// and `>5` on `@~:5`.
+    fn range(&self) -> String {
+        match self.comparison {
+            Comparison::Less => format!("@{}:", self.value),
+            Comparison::LessOrEqual => format!("~:{}", self.value),
+            Comparison::Greater => format!("@~:{}", self.value),
+            Comparison::GreaterOrEqual => format!("{}:", self.value),
+            Comparison::Equal => format!("{0}:{0}", self.value),
+        }
+    }
+
+    fn holds(&self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Less => value < self.value,
+            Comparison::LessOrEqual => value <= self.value,
+            Comparison::Greater => value > self.value,
+            Comparison::GreaterOrEqual => value >= self.value,
+            Comparison::Equal => value == self.value,
+        }
+    }
+}
+
+/// The conditions a value is checked against: with only `critical`, the
+/// check is critical once it fails; with `warning` too, failing that first
+/// is a warning.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Threshold {
+    pub warning: Option<Condition>,
+    pub critical: Condition,
+}
+
+impl Threshold {
+    /// Parses `<90`, or `<80,<90` for a warning at 80 and critical at 90.
+    /// Percentages may end in `%`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        Self::parse_with(input, |value| {
+            value
+                .trim_end_matches('%')
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid number `{}`", value))
+        })
+    }
+
+    /// Parses sizes such as `>5GiB`, or `>10GiB,>5GiB` for a warning below
+    /// 10 GiB and critical below 5.
+    pub fn parse_bytes(input: &str) -> Result<Self, String> {
+        Self::parse_with(input, |value| {
+            parse_byte_size(value).map(|bytes| bytes as f64)
+        })
+    }
+
+    fn parse_with(
+        input: &str,
+        value: impl Fn(&str) -> Result<f64, String>,
+    ) -> Result<Self, String> {
+        let condition = |text: &str| {
+            let text = text.trim();
+            let (comparison, rest) = [
+                ("<=", Comparison::LessOrEqual),
+                (">=", Comparison::GreaterOrEqual),
+                ("<", Comparison::Less),
+                (">", Comparison::Greater),
+                ("=", Comparison::Equal),
+            ]
+            .into_iter()
+            .find_map(|(operator, comparison)| {
+                text.strip_prefix(operator).map(|rest| (comparison, rest))
+            })
+            .ok_or_else(|| format!("`{}` does not start with <, <=, >, >= or =", text))?;
+            Ok::<_, String>(Condition {
+                comparison,
+                value: value(rest)?,
+                text: text.to_string(),
+            })
+        };
+
+        match input.split_once(',') {
+            Some((warning, critical)) => Ok(Self {
+                warning: Some(condition(warning)?),
+                critical: condition(critical)?,
+            }),
+            None => Ok(Self {
+                warning: None,
+                critical: condition(input)?,
+            }),
+        }
+    }
+
+    fn status(&self, value: f64) -> (Status, &Condition) {
+        if !self.critical.holds(value) {
+            return (Status::Critical, &self.critical);
+        }
+        match &self.warning {
+            Some(warning) if !warning.holds(value) => (Status::Warning, warning),
+            Some(warning) => (Status::Ok, warning),
+            None => (Status::Ok, &self.critical),
+        }
+    }
+}
+
+/// Parses a `--disk-used-pct` such as `/:<90`: a mount point, a colon and
+/// a threshold.
+pub fn parse_mount(input: &str) -> Result<(String, Threshold), String> {
+    let (mount, threshold) = split_mount(input)?;
+    Ok((mount, Threshold::parse(threshold)?))
+}
+
+/// Parses a `--disk-free` such as `/:>5GiB`.
+pub fn parse_mount_bytes(input: &str) -> Result<(String, Threshold), String> {
+    let (mount, threshold) = split_mount(input)?;
+    Ok((mount, Threshold::parse_bytes(threshold)?))
+}
+
+/// Mount points may contain colons themselves, as in `C:\`, so the split
+/// is at the one before the threshold.
+fn split_mount(input: &str) -> Result<(String, &str), String> {
+    let start = input
+        .find(['<', '>', '='])
+        .ok_or_else(|| format!("expected MOUNT:THRESHOLD, got `{}`", input))?;
+    match input[..start].strip_suffix(':') {
+        Some(mount) if !mount.is_empty() => Ok((mount.to_string(), &input[start..])),
+        _ => Err(format!("expected MOUNT:THRESHOLD, got `{}`", input)),
+    }
+}
+
+/// A figure of the report to check.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Metric {
+    /// Memory in use, as a percentage of the total.
+    MemoryUsedPercent,
+    SwapUsedPercent,
+    /// Over an `--interval` of sampling.
+    CpuUsedPercent,
+    /// The one-minute load average.
+    Load,
+    /// Bytes available on the file system mounted here.
+    DiskFree(String),
+    DiskUsedPercent(String),
+}
+
+impl Metric {
+    /// The name a check is reported under, after its flag.
+    fn name(&self) -> String {
+        match self {
+            Metric::MemoryUsedPercent => "mem_used_pct".into(),
+            Metric::SwapUsedPercent => "swap_used_pct".into(),
+            Metric::CpuUsedPercent => "cpu_used_pct".into(),
+            Metric::Load => "load_avg".into(),
+            Metric::DiskFree(mount) => format!("disk_free[{}]", mount),
+            Metric::DiskUsedPercent(mount) => format!("disk_used_pct[{}]", mount),
+        }
+    }
+
+    fn is_bytes(&self) -> bool {
+        matches!(self, Metric::DiskFree(_))
+    }
+
+    /// The unit of performance data.
+    fn unit(&self) -> &'static str {
+        match self {
+            Metric::DiskFree(_) => "B",
+            Metric::Load => "",
+            _ => "%",
+        }
+    }
+
+    fn value(&self, report: &Report) -> Result<f64, String> {
+        let percent = |used: u64, total: u64| {
+            if total == 0 {
+                0.0
+            } else {
+                used as f64 / total as f64 * 100.0
+            }
+        };
+        let system = || {
+            report
+                .system
+                .as_ref()
+                .ok_or_else(|| "the system section was not collected".to_string())
+        };
+        let disk = |mount: &str| {
+            let mount = match mount.trim_end_matches(['/', '\\']) {
+                "" => mount,
+                trimmed => trimmed,
+            };
+            report
+                .disk
+                .iter()
+                .flat_map(|disk| &disk.disks)
+                .find(|entry| {
+                    entry.mount_point == mount
+                        || entry.mount_point.trim_end_matches(['/', '\\']) == mount
+                })
+                .ok_or_else(|| format!("nothing is mounted at {}", mount))
+        };
+
+        match self {
+            Metric::MemoryUsedPercent => {
+                system().map(|system| percent(system.memory_used_bytes, system.memory_total_bytes))
+            }
+            Metric::SwapUsedPercent => {
+                system().map(|system| percent(system.swap_used_bytes, system.swap_total_bytes))
+            }
+            Metric::CpuUsedPercent => system()?
+                .cpu
+                .usage
+                .as_ref()
+                .map(|usage| f64::from(usage.global_percent))
+                .ok_or_else(|| "CPU usage was not sampled".into()),
+            Metric::Load => report
+                .load
+                .as_ref()
+                .map(|load| load.one)
+                .ok_or_else(|| "the load average is unavailable".into()),
+            Metric::DiskFree(mount) => disk(mount).map(|entry| entry.available_bytes as f64),
+            Metric::DiskUsedPercent(mount) => {
+                disk(mount).map(|entry| percent(entry.used_bytes, entry.total_bytes))
+            }
+        }
+    }
+}
+
+/// One metric and what it should look like.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Check {
+    pub metric: Metric,
+    pub threshold: Threshold,
+}
+
+/// How one check came out.
+#[derive(Clone, Debug, Serialize)]
+pub struct Outcome {
+    pub name: String,
+    pub status: Status,
+    /// Left out when unknown, as TOML has no null.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    /// The value, what it was expected to be, or why it is unknown.
+    pub message: String,
+    /// `'name'=value[unit];warning;critical`, unless the value is unknown.
+    #[serde(skip)]
+    perfdata: Option<String>,
+}
+
+impl Check {
+    pub fn evaluate(&self, report: &Report, units: ByteUnits) -> Outcome {
+        let name = self.metric.name();
+
+        match self.metric.value(report) {
+            Ok(value) => {
+                let (status, condition) = self.threshold.status(value);
+                let shown = if self.metric.is_bytes() {
+                    format_bytes(value as u64, units)
+                } else {
+                    format!("{:.1}", value)
+                };
+                let message = if status == Status::Ok {
+                    format!("{} is {}", name, shown)
+                } else {
+                    format!("{} is {}, expected {}", name, shown, condition.text)
+                };
+                let perfdata = self.perfdata(&name, value);
+                Outcome {
+                    name,
+                    status,
+                    value: Some(value),
+                    message,
+                    perfdata: Some(perfdata),
+                }
+            }
+            Err(err) => Outcome {
+                message: format!("{}: {}", name, err),
+                name,
+                status: Status::Unknown,
+                value: None,
+                perfdata: None,
+            },
+        }
+    }
+
+    fn perfdata(&self, name: &str, value: f64) -> String {
+        let warning = self
+            .threshold
+            .warning
+            .as_ref()
+            .map(Condition::range)
+            .unwrap_or_default();
+        let value = if self.metric.is_bytes() {
+            value.round()
+        } else {
+            (value * 100.0).round() / 100.0
+        };
+        let unit = self.metric.unit();
+        // Percentages also give their minimum and maximum.
+        let bounds = if unit == "%" { ";0;100" } else { "" };
+        format!(
+            "'{}'={}{};{};{}{}",
+            name,
+            value,
+            unit,
+            warning,
+            self.threshold.critical.range(),
+            bounds
+        )
+    }
+}
+
+/// The sections `checks` read.
+pub fn sections(checks: &[Check]) -> Sections {
+    let mut sections = Sections::none();
+    for check in checks {
+        match check.metric {
+            Metric::MemoryUsedPercent | Metric::SwapUsedPercent | Metric::CpuUsedPercent => {
+                sections.system = true
+            }
+            Metric::Load => sections.load = true,
+            Metric::DiskFree(_) | Metric::DiskUsedPercent(_) => sections.disk = true,
+        }
+    }
+    sections
+}
+
+/// The worst status of `outcomes`, OK when there are none.
+pub fn worst(outcomes: &[Outcome]) -> Status {
+    outcomes
+        .iter()
+        .map(|outcome| outcome.status)
+        .max()
+        .unwrap_or(Status::Ok)
+}
+
+/// The line Nagios and Icinga read from a plugin, as in
+/// `WARNING - mem_used_pct is 85.2, expected <80 | 'mem_used_pct'=85.2%;@80:;@90:;0;100`:
+/// the worst status, what failed or else every result, then performance
+/// data for each value.
+pub fn nagios(outcomes: &[Outcome]) -> String {
+    let status = worst(outcomes);
+    let messages: Vec<&str> = outcomes
+        .iter()
+        .filter(|outcome| status == Status::Ok || outcome.status != Status::Ok)
+        .map(|outcome| outcome.message.as_str())
+        .collect();
+    let perfdata: Vec<&str> = outcomes
+        .iter()
+        .filter_map(|outcome| outcome.perfdata.as_deref())
+        .collect();
+
+    let mut line = format!("{} - {}", status, messages.join("; "));
+    if !perfdata.is_empty() {
+        line.push_str(" | ");
+        line.push_str(&perfdata.join(" "));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_alerts_where_status_does() {
+        for (threshold, range, boundary, status) in [
+            ("<90", "@90:", 90.0, Status::Critical),
+            ("<=90", "~:90", 90.0, Status::Ok),
+            (">5", "@~:5", 5.0, Status::Critical),
+            (">=5", "5:", 5.0, Status::Ok),
+            ("=1", "1:1", 1.0, Status::Ok),
+        ] {
+            let threshold = Threshold::parse(threshold).unwrap();
+            assert_eq!(threshold.critical.range(), range);
+            assert_eq!(threshold.status(boundary).0, status);
+        }
+    }
+
+    #[test]
+    fn parses_thresholds() {
+        let threshold = Threshold::parse("<80, <=90%").unwrap();
+        let warning = threshold.warning.unwrap();
+        assert_eq!(warning.comparison, Comparison::Less);
+        assert_eq!(warning.value, 80.0);
+        assert_eq!(threshold.critical.comparison, Comparison::LessOrEqual);
+        assert_eq!(threshold.critical.value, 90.0);
+        assert_eq!(threshold.critical.text, "<=90%");
+
+        let threshold = Threshold::parse_bytes(">10GiB,>5GB").unwrap();
+        assert_eq!(
+            threshold.warning.unwrap().value,
+            10.0 * 1024.0 * 1024.0 * 1024.0
+        );
+        assert_eq!(threshold.critical.value, 5e9);
+
+        assert!(Threshold::parse("90").is_err());
+        assert!(Threshold::parse("<lots").is_err());
+        assert!(Threshold::parse_bytes(">5XB").is_err());
+    }
+
+    #[test]
+    fn splits_mounts_at_the_threshold() {
+        assert_eq!(split_mount("/:<90"), Ok(("/".to_string(), "<90")));
+        assert_eq!(split_mount(r"C:\:>5GiB"), Ok((r"C:\".to_string(), ">5GiB")));
+        assert!(split_mount("/<90").is_err());
+        assert!(split_mount(":<90").is_err());
+        assert!(split_mount("/").is_err());
+    }
+
+    #[test]
+    fn failing_the_warning_is_a_warning() {
+        let threshold = Threshold::parse("<80,<90").unwrap();
+        assert_eq!(threshold.status(50.0).0, Status::Ok);
+        assert_eq!(threshold.status(85.0).0, Status::Warning);
+        assert_eq!(threshold.status(95.0).0, Status::Critical);
+    }
+
+    #[test]
+    fn unknown_ranks_between_warning_and_critical() {
+        assert!(Status::Ok < Status::Warning);
+        assert!(Status::Warning < Status::Unknown);
+        assert!(Status::Unknown < Status::Critical);
+
+        let outcome = |status| Outcome {
+            name: String::new(),
+            status,
+            value: None,
+            message: String::new(),
+            perfdata: None,
+        };
+        assert_eq!(worst(&[]), Status::Ok);
+        assert_eq!(
+            worst(&[outcome(Status::Unknown), outcome(Status::Warning)]),
+            Status::Unknown
+        );
+    }
+
+    #[test]
+    fn formats_nagios_status_and_perfdata() {
+        let memory = Check {
+            metric: Metric::MemoryUsedPercent,
+            threshold: Threshold::parse("<80,<90").unwrap(),
+        };
+        let disk = Check {
+            metric: Metric::DiskFree("/".into()),
+            threshold: Threshold::parse_bytes(">1KiB").unwrap(),
+        };
+        assert_eq!(
+            memory.perfdata("mem_used_pct", 85.237),
+            "'mem_used_pct'=85.24%;@80:;@90:;0;100"
+        );
+        assert_eq!(
+            disk.perfdata("disk_free[/]", 2048.4),
+            "'disk_free[/]'=2048B;;@~:1024"
+        );
+
+        let outcomes = [
+            Outcome {
+                name: "mem_used_pct".into(),
+                status: Status::Warning,
+                value: Some(85.237),
+                message: "mem_used_pct is 85.2, expected <80".into(),
+                perfdata: Some(memory.perfdata("mem_used_pct", 85.237)),
+            },
+            Outcome {
+                name: "disk_free[/]".into(),
+                status: Status::Ok,
+                value: Some(2048.4),
+                message: "disk_free[/] is 2.0 KiB".into(),
+                perfdata: Some(disk.perfdata("disk_free[/]", 2048.4)),
+            },
+            Outcome {
+                name: "load_avg".into(),
+                status: Status::Ok,
+                value: None,
+                message: "load_avg is 0.5".into(),
+                perfdata: None,
+            },
+        ];
+        assert_eq!(
+            nagios(&outcomes),
+            "WARNING - mem_used_pct is 85.2, expected <80 | \
+             'mem_used_pct'=85.24%;@80:;@90:;0;100 'disk_free[/]'=2048B;;@~:1024"
+        );
+        assert_eq!(
+            nagios(&outcomes[1..]),
+            "OK - disk_free[/] is 2.0 KiB; load_avg is 0.5 | 'disk_free[/]'=2048B;;@~:1024"
+        );
+    }
+
+    #[test]
+    fn serializes_unknown_outcomes_to_toml() {
+        let check = Check {
+            metric: Metric::DiskFree("/nonexist".into()),
+            threshold: Threshold::parse_bytes(">1GiB").unwrap(),
+        };
+        let outcome = check.evaluate(&Report::default(), ByteUnits::Iec);
+        assert_eq!(outcome.status, Status::Unknown);
+        // As `envin check --output toml` lists it.
+        assert!(toml::to_string(&serde_json::json!({ "checks": [&outcome] })).is_ok());
+        assert_eq!(
+            toml::to_string(&outcome).unwrap(),
+            "name = \"disk_free[/nonexist]\"\n\
+             status = \"UNKNOWN\"\n\
+             message = \"disk_free[/nonexist]: nothing is mounted at /nonexist\"\n"
+        );
+    }
+}
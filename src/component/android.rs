@@ -0,0 +1,86 @@
+use {
+    crate::{component::memory::meminfo, format::DisplayOptions, util::command_output},
+    serde::{Deserialize, Serialize},
+};
+
+/// The Android release and device, from the system properties `getprop`
+/// reads, for builds running under Termux or as NDK binaries. Empty on
+/// other systems.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AndroidInfo {
+    /// The SDK level, such as `34` for Android 14.
+    pub api_level: Option<u32>,
+    /// Such as `14`.
+    pub release: Option<String>,
+    pub manufacturer: Option<String>,
+    /// Such as `Pixel 8`.
+    pub model: Option<String>,
+    /// The ABIs the device runs, preferred first, such as `arm64-v8a`.
+    pub abis: Vec<String>,
+    pub total_memory_bytes: Option<u64>,
+}
+
+impl AndroidInfo {
+    pub fn collect() -> Self {
+        let property = |name: &str| {
+            if cfg!(target_os = "android") {
+                command_output("getprop", &[name]).filter(|value| !value.is_empty())
+            } else {
+                None
+            }
+        };
+
+        Self {
+            api_level: property("ro.build.version.sdk").and_then(|level| level.parse().ok()),
+            release: property("ro.build.version.release"),
+            manufacturer: property("ro.product.manufacturer"),
+            model: property("ro.product.model"),
+            // Devices before Android 5 only name one or two ABIs.
+            abis: property("ro.product.cpu.abilist")
+                .or_else(|| property("ro.product.cpu.abi"))
+                .map(|abis| abis.split(',').map(str::to_string).collect())
+                .unwrap_or_default(),
+            total_memory_bytes: cfg!(target_os = "android")
+                .then(|| meminfo().get("MemTotal").copied())
+                .flatten(),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Android Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        let Some(api_level) = self.api_level else {
+            options.line("  Android : None");
+            return;
+        };
+        options.line(format_args!(
+            "  Android : {} (API {})",
+            self.release.as_deref().unwrap_or("Unknown"),
+            api_level
+        ));
+        let device = match (&self.manufacturer, &self.model) {
+            (Some(manufacturer), Some(model)) => format!("{} {}", manufacturer, model),
+            (Some(name), None) | (None, Some(name)) => name.clone(),
+            (None, None) => "Unknown".into(),
+        };
+        options.line(format_args!("  Device  : {}", device));
+        options.line(format_args!(
+            "  ABIs    : {}",
+            if self.abis.is_empty() {
+                "Unknown".into()
+            } else {
+                self.abis.join(", ")
+            }
+        ));
+        options.line(format_args!(
+            "  Memory  : {}",
+            self.total_memory_bytes
+                .map(|bytes| options.bytes(bytes))
+                .unwrap_or_else(|| "Unknown".into())
+        ));
+    }
+}
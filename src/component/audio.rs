@@ -0,0 +1,214 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_objects, json_objects_in, json_string},
+    },
+    serde::{Deserialize, Serialize},
+};
+
+/// The sound server and the devices it plays to and records from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AudioInfo {
+    /// `PipeWire`, `PulseAudio`, `ALSA`, `CoreAudio` or `WASAPI`.
+    pub server: Option<String>,
+    pub devices: Vec<AudioDevice>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AudioDevice {
+    pub name: String,
+    /// `playback` or `capture`.
+    pub kind: String,
+    pub default: bool,
+}
+
+impl AudioInfo {
+    pub fn collect() -> Self {
+        if cfg!(target_os = "macos") {
+            Self {
+                server: Some("CoreAudio".into()),
+                devices: core_audio_devices(),
+            }
+        } else if cfg!(target_os = "windows") {
+            Self {
+                server: Some("WASAPI".into()),
+                devices: wasapi_devices(),
+            }
+        } else {
+            pulse_audio().unwrap_or_else(alsa)
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Audio Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        options.line(format_args!(
+            "  Sound Server : {}",
+            self.server.as_deref().unwrap_or("None")
+        ));
+
+        for (kind, title) in [("playback", "Playback"), ("capture", "Capture")] {
+            let devices: Vec<&AudioDevice> = self
+                .devices
+                .iter()
+                .filter(|device| device.kind == kind)
+                .collect();
+            if devices.is_empty() {
+                options.line(format_args!("  {} : None", title));
+                continue;
+            }
+            options.line(format_args!("  {}:", title));
+            for device in devices {
+                let default = if device.default { " (default)" } else { "" };
+                options.line(format_args!("    {}{}", device.name, default));
+            }
+        }
+    }
+}
+
+/// Asks the PulseAudio server, or PipeWire standing in for one.
+fn pulse_audio() -> Option<AudioInfo> {
+    let info = command_output("pactl", &["info"])?;
+    let field = |name: &str| {
+        info.lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+            .map(str::trim)
+    };
+    // PipeWire reports `PulseAudio (on PipeWire 1.0.5)`.
+    let server = field("Server Name").map(|server| {
+        if server.contains("PipeWire") {
+            "PipeWire".to_string()
+        } else {
+            "PulseAudio".to_string()
+        }
+    });
+
+    let mut devices = Vec::new();
+    for (list, kind, default) in [
+        ("sinks", "playback", field("Default Sink")),
+        ("sources", "capture", field("Default Source")),
+    ] {
+        let Some(output) = command_output("pactl", &["list", "short", list]) else {
+            continue;
+        };
+        // Rows read `52	alsa_output.pci-0000_00_1f.3.analog-stereo	PipeWire	...`;
+        // every sink also shows up as a `.monitor` source.
+        devices.extend(
+            output
+                .lines()
+                .filter_map(|line| line.split('\t').nth(1))
+                .filter(|name| !name.ends_with(".monitor"))
+                .map(|name| AudioDevice {
+                    name: name.to_string(),
+                    kind: kind.to_string(),
+                    default: Some(name) == default,
+                }),
+        );
+    }
+
+    Some(AudioInfo { server, devices })
+}
+
+/// Lists the PCM devices of the kernel, whose rows read `00-00: ALC887-VD
+/// Analog : ALC887-VD Analog : playback 1 : capture 1`.
+fn alsa() -> AudioInfo {
+    let Ok(pcm) = std::fs::read_to_string("/proc/asound/pcm") else {
+        return AudioInfo {
+            server: None,
+            devices: Vec::new(),
+        };
+    };
+
+    let devices = pcm
+        .lines()
+        .flat_map(|line| {
+            let fields: Vec<&str> = line.split(" : ").map(str::trim).collect();
+            let name = fields
+                .first()
+                .and_then(|id| id.split_once(": "))
+                .map(|(_, name)| name.to_string());
+            ["playback", "capture"]
+                .into_iter()
+                .filter(move |kind| fields.iter().any(|field| field.starts_with(kind)))
+                .filter_map(move |kind| {
+                    Some(AudioDevice {
+                        name: name.clone()?,
+                        kind: kind.to_string(),
+                        default: false,
+                    })
+                })
+        })
+        .collect();
+
+    AudioInfo {
+        server: Some("ALSA".into()),
+        devices,
+    }
+}
+
+/// Reads `system_profiler SPAudioDataType`, which counts the channels each
+/// way and marks the default devices.
+fn core_audio_devices() -> Vec<AudioDevice> {
+    let Some(output) = command_output("system_profiler", &["SPAudioDataType", "-json"]) else {
+        return Vec::new();
+    };
+    let Ok(value) = output.parse::<serde_json::Value>() else {
+        return Vec::new();
+    };
+
+    json_objects_in(&value, "SPAudioDataType")
+        .into_iter()
+        .flat_map(|audio| json_objects_in(audio, "_items"))
+        .flat_map(|device| {
+            let name = json_string(device, "_name");
+            [("playback", "output"), ("capture", "input")]
+                .into_iter()
+                .filter(|(_, way)| device.get(format!("coreaudio_device_{}", way)).is_some())
+                .filter_map(move |(kind, way)| {
+                    let default =
+                        json_string(device, &format!("coreaudio_default_audio_{}_device", way));
+                    Some(AudioDevice {
+                        name: name.clone()?,
+                        kind: kind.to_string(),
+                        default: default.as_deref() == Some("spaudio_yes"),
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Lists the audio endpoints, whose instance IDs tell render endpoints
+/// (`{0.0.0.00000000}`) from capture ones (`{0.0.1.00000000}`).
+fn wasapi_devices() -> Vec<AudioDevice> {
+    let Some(output) = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-PnpDevice -Class AudioEndpoint -PresentOnly | \
+             Select-Object FriendlyName,InstanceId | ConvertTo-Json",
+        ],
+    ) else {
+        return Vec::new();
+    };
+
+    json_objects(&output)
+        .iter()
+        .filter_map(|endpoint| {
+            let id = json_string(endpoint, "InstanceId")?;
+            let kind = if id.contains("{0.0.1.") {
+                "capture"
+            } else {
+                "playback"
+            };
+            Some(AudioDevice {
+                name: json_string(endpoint, "FriendlyName")?,
+                kind: kind.to_string(),
+                default: false,
+            })
+        })
+        .collect()
+}
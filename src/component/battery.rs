@@ -0,0 +1,141 @@
+use {
+    crate::{format::DisplayOptions, util::format_duration},
+    serde::{Deserialize, Serialize},
+    starship_battery::units::{energy, ratio, time},
+};
+
+/// Batteries found on the machine.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BatteryInfo {
+    pub batteries: Vec<BatteryEntry>,
+}
+
+/// A single battery.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BatteryEntry {
+    pub vendor: Option<String>,
+    pub model: Option<String>,
+    pub technology: String,
+    pub state: String,
+    pub charge_percent: f32,
+    pub time_to_empty_secs: Option<u64>,
+    pub time_to_full_secs: Option<u64>,
+    pub cycle_count: Option<u32>,
+    pub energy_wh: f32,
+    pub energy_full_wh: f32,
+    pub energy_full_design_wh: f32,
+    /// How much of its design capacity the battery still holds when full.
+    pub health_percent: Option<f32>,
+}
+
+impl BatteryInfo {
+    pub fn collect() -> Self {
+        let batteries = starship_battery::Manager::new()
+            .and_then(|manager| manager.batteries())
+            .map(|batteries| {
+                batteries
+                    .filter_map(Result::ok)
+                    .map(|battery| {
+                        let energy_full = battery.energy_full().get::<energy::watt_hour>();
+                        let energy_full_design =
+                            battery.energy_full_design().get::<energy::watt_hour>();
+                        BatteryEntry {
+                            vendor: battery.vendor().map(str::to_string),
+                            model: battery.model().map(str::to_string),
+                            technology: battery.technology().to_string(),
+                            state: battery.state().to_string(),
+                            charge_percent: battery.state_of_charge().get::<ratio::percent>(),
+                            time_to_empty_secs: battery
+                                .time_to_empty()
+                                .map(|time| time.get::<time::second>() as u64),
+                            time_to_full_secs: battery
+                                .time_to_full()
+                                .map(|time| time.get::<time::second>() as u64),
+                            cycle_count: battery.cycle_count(),
+                            energy_wh: battery.energy().get::<energy::watt_hour>(),
+                            energy_full_wh: energy_full,
+                            energy_full_design_wh: energy_full_design,
+                            // Some firmware reports no design capacity at all.
+                            health_percent: (energy_full_design > 0.0)
+                                .then(|| energy_full / energy_full_design * 100.0),
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { batteries }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Battery Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.batteries.is_empty() {
+            options.line("  Batteries: None");
+        }
+
+        for (index, battery) in self.batteries.iter().enumerate() {
+            let model = [battery.vendor.as_deref(), battery.model.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            options.line(format_args!(
+                "  {}Battery {}{}",
+                "-".repeat(10),
+                index,
+                "-".repeat(10)
+            ));
+            if !model.is_empty() {
+                options.line(format_args!(
+                    "    Model         : {} ({})",
+                    model, battery.technology
+                ));
+            }
+            options.line(format_args!(
+                "    Charge        : {} ({})",
+                options.warn_if(
+                    battery.charge_percent <= 15.0
+                        && battery.state.eq_ignore_ascii_case("discharging"),
+                    format!("{:.1}%", battery.charge_percent)
+                ),
+                battery.state
+            ));
+            if let Some(secs) = battery.time_to_empty_secs {
+                options.line(format_args!(
+                    "    Time to Empty : {}",
+                    format_duration(secs)
+                ));
+            }
+            if let Some(secs) = battery.time_to_full_secs {
+                options.line(format_args!(
+                    "    Time to Full  : {}",
+                    format_duration(secs)
+                ));
+            }
+            options.line(format_args!(
+                "    Capacity      : {:.2} Wh / {:.2} Wh (design {:.2} Wh)",
+                battery.energy_wh, battery.energy_full_wh, battery.energy_full_design_wh
+            ));
+            options.line(format_args!(
+                "    Health        : {}",
+                battery
+                    .health_percent
+                    .map(|health| options.warn_if(health < 80.0, format!("{:.1}%", health)))
+                    .unwrap_or_else(|| "Unknown".into())
+            ));
+            options.line(format_args!(
+                "    Cycle Count   : {}",
+                battery
+                    .cycle_count
+                    .map(|count| count.to_string())
+                    .unwrap_or_else(|| "Unknown".into())
+            ));
+        }
+    }
+}
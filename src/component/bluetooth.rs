@@ -0,0 +1,252 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output_timeout, json_objects, json_objects_in, json_string},
+    },
+    serde::{Deserialize, Serialize},
+    std::time::Duration,
+};
+
+/// `bluetoothctl` waits for `bluetoothd` indefinitely when it is not
+/// running, yet answers at once when it is.
+const BLUEZ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// `system_profiler` and PowerShell can take a few seconds to start.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Bluetooth adapters and the devices paired with them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BluetoothInfo {
+    pub adapters: Vec<BluetoothAdapter>,
+    pub devices: Vec<BluetoothDevice>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BluetoothAdapter {
+    pub name: String,
+    pub address: Option<String>,
+    pub powered: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BluetoothDevice {
+    pub name: String,
+    pub address: Option<String>,
+    pub connected: bool,
+}
+
+impl BluetoothInfo {
+    pub fn collect() -> Self {
+        if cfg!(target_os = "macos") {
+            macos()
+        } else if cfg!(target_os = "windows") {
+            windows()
+        } else {
+            bluez()
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Bluetooth Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.adapters.is_empty() {
+            options.line("  Adapters: None");
+        }
+        for adapter in &self.adapters {
+            let powered = match adapter.powered {
+                Some(true) => "on",
+                Some(false) => "off",
+                None => "Unknown",
+            };
+            // Rows are printed as is: addresses contain `:`.
+            println!(
+                "  Adapter {} [{}] : {}",
+                adapter.name,
+                adapter.address.as_deref().unwrap_or("Unknown"),
+                powered
+            );
+        }
+
+        if self.devices.is_empty() {
+            options.line("  Paired Devices: None");
+            return;
+        }
+        println!("  Paired Devices:");
+        for device in &self.devices {
+            let connected = if device.connected { " (connected)" } else { "" };
+            println!(
+                "    {} [{}]{}",
+                device.name,
+                device.address.as_deref().unwrap_or("Unknown"),
+                connected
+            );
+        }
+    }
+}
+
+/// Asks BlueZ through `bluetoothctl`, which speaks D-Bus to `bluetoothd`.
+fn bluez() -> BluetoothInfo {
+    // Rows read `Controller 00:1A:7D:DA:71:13 laptop [default]`. Without an
+    // answer `bluetoothd` is not running, and asking for devices would only
+    // wait out the timeout again.
+    let Some(list) = command_output_timeout("bluetoothctl", &["list"], BLUEZ_TIMEOUT) else {
+        return BluetoothInfo {
+            adapters: Vec::new(),
+            devices: Vec::new(),
+        };
+    };
+    let adapters = list
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.strip_prefix("Controller ")?.split_whitespace();
+            let address = fields.next()?.to_string();
+            let name = fields
+                .filter(|field| *field != "[default]")
+                .collect::<Vec<_>>()
+                .join(" ");
+            let powered =
+                command_output_timeout("bluetoothctl", &["show", &address], BLUEZ_TIMEOUT)
+                    .and_then(|show| {
+                        show.lines().find_map(|line| {
+                            Some(line.trim().strip_prefix("Powered:")?.trim() == "yes")
+                        })
+                    });
+            Some(BluetoothAdapter {
+                name,
+                address: Some(address),
+                powered,
+            })
+        })
+        .collect();
+
+    // Rows read `Device AA:BB:CC:DD:EE:FF WH-1000XM4`.
+    let list = |filter: &str| -> Vec<(String, String)> {
+        command_output_timeout("bluetoothctl", &["devices", filter], BLUEZ_TIMEOUT)
+            .map(|output| {
+                output
+                    .lines()
+                    .filter_map(|line| {
+                        let (address, name) = line.strip_prefix("Device ")?.split_once(' ')?;
+                        Some((address.to_string(), name.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let connected = list("Connected");
+    let devices = list("Paired")
+        .into_iter()
+        .map(|(address, name)| BluetoothDevice {
+            connected: connected.iter().any(|(other, _)| *other == address),
+            name,
+            address: Some(address),
+        })
+        .collect();
+
+    BluetoothInfo { adapters, devices }
+}
+
+/// Reads `system_profiler SPBluetoothDataType`, which lists devices as
+/// objects keyed by their name.
+fn macos() -> BluetoothInfo {
+    let value = command_output_timeout(
+        "system_profiler",
+        &["SPBluetoothDataType", "-json"],
+        PROBE_TIMEOUT,
+    )
+    .and_then(|output| output.parse::<serde_json::Value>().ok())
+    .unwrap_or_default();
+
+    let mut info = BluetoothInfo {
+        adapters: Vec::new(),
+        devices: Vec::new(),
+    };
+    for bluetooth in json_objects_in(&value, "SPBluetoothDataType") {
+        let controller = &bluetooth["controller_properties"];
+        if controller.is_object() {
+            info.adapters.push(BluetoothAdapter {
+                name: json_string(controller, "controller_chipset")
+                    .unwrap_or_else(|| "Bluetooth Controller".into()),
+                address: json_string(controller, "controller_address"),
+                powered: json_string(controller, "controller_state")
+                    .map(|state| state == "attrib_on"),
+            });
+        }
+        for (key, connected) in [("device_connected", true), ("device_not_connected", false)] {
+            for entry in json_objects_in(bluetooth, key) {
+                let Some(devices) = entry.as_object() else {
+                    continue;
+                };
+                info.devices
+                    .extend(devices.iter().map(|(name, device)| BluetoothDevice {
+                        name: name.clone(),
+                        address: json_string(device, "device_address"),
+                        connected,
+                    }));
+            }
+        }
+    }
+    info
+}
+
+/// Lists the present Bluetooth devices, telling the radios, which sit on
+/// USB or PCI, from the paired devices the Bluetooth stack enumerates.
+fn windows() -> BluetoothInfo {
+    let entities = command_output_timeout(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-PnpDevice -Class Bluetooth -PresentOnly | \
+             Select-Object FriendlyName,InstanceId,Status | ConvertTo-Json",
+        ],
+        PROBE_TIMEOUT,
+    )
+    .map(|output| json_objects(&output))
+    .unwrap_or_default();
+
+    let mut info = BluetoothInfo {
+        adapters: Vec::new(),
+        devices: Vec::new(),
+    };
+    for entity in &entities {
+        let (Some(name), Some(id)) = (
+            json_string(entity, "FriendlyName"),
+            json_string(entity, "InstanceId"),
+        ) else {
+            continue;
+        };
+        let ok = json_string(entity, "Status").as_deref() == Some("OK");
+        if id.starts_with("USB\\") || id.starts_with("PCI\\") {
+            info.adapters.push(BluetoothAdapter {
+                name,
+                address: None,
+                powered: Some(ok),
+            });
+        } else if id.starts_with("BTHENUM\\DEV_") || id.starts_with("BTHLE\\DEV_") {
+            // `BTHENUM\DEV_AABBCCDDEEFF\...` carries the device address.
+            let address = id
+                .split('\\')
+                .nth(1)
+                .and_then(|part| part.strip_prefix("DEV_"))
+                .filter(|hex| hex.len() == 12)
+                .map(|hex| {
+                    hex.as_bytes()
+                        .chunks(2)
+                        .map(|pair| String::from_utf8_lossy(pair).into_owned())
+                        .collect::<Vec<_>>()
+                        .join(":")
+                });
+            info.devices.push(BluetoothDevice {
+                name,
+                address,
+                connected: ok,
+            });
+        }
+    }
+    info
+}
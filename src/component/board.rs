@@ -0,0 +1,348 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, is_bsd, json_objects, json_string, read_trimmed, sysctl},
+    },
+    serde::{Deserialize, Serialize},
+    std::path::Path,
+};
+
+/// The motherboard, firmware and chassis as SMBIOS describes them. The
+/// UUID and serial numbers identify the machine, so `envin` redacts them
+/// unless told otherwise.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BoardInfo {
+    pub system_vendor: Option<String>,
+    pub product_name: Option<String>,
+    pub board_vendor: Option<String>,
+    pub board_name: Option<String>,
+    pub board_version: Option<String>,
+    pub bios_vendor: Option<String>,
+    pub bios_version: Option<String>,
+    /// As the firmware gives it, such as `03/14/2024`.
+    pub bios_date: Option<String>,
+    /// Such as `Desktop`, `Notebook` or `Rack Mount Chassis`.
+    pub chassis_type: Option<String>,
+    /// Reading these needs root on Linux.
+    pub uuid: Option<String>,
+    pub system_serial: Option<String>,
+    pub board_serial: Option<String>,
+    pub chassis_serial: Option<String>,
+}
+
+/// The fields redacted unless `--show-serials` is given.
+pub const SERIAL_FIELDS: [&str; 4] = [
+    "board.uuid",
+    "board.system_serial",
+    "board.board_serial",
+    "board.chassis_serial",
+];
+
+/// Fillers vendors leave in fields they did not bother setting.
+const PLACEHOLDERS: &[&str] = &[
+    "To Be Filled By O.E.M.",
+    "To be filled by O.E.M.",
+    "Default string",
+    "System Product Name",
+    "System manufacturer",
+    "Not Applicable",
+    "Not Specified",
+    "None",
+    "N/A",
+    "Chassis Serial Number",
+    "",
+];
+
+/// The SMBIOS chassis types, indexed from 1.
+const CHASSIS_TYPES: &[&str] = &[
+    "Other",
+    "Unknown",
+    "Desktop",
+    "Low Profile Desktop",
+    "Pizza Box",
+    "Mini Tower",
+    "Tower",
+    "Portable",
+    "Laptop",
+    "Notebook",
+    "Hand Held",
+    "Docking Station",
+    "All in One",
+    "Sub Notebook",
+    "Space-saving",
+    "Lunch Box",
+    "Main Server Chassis",
+    "Expansion Chassis",
+    "SubChassis",
+    "Bus Expansion Chassis",
+    "Peripheral Chassis",
+    "RAID Chassis",
+    "Rack Mount Chassis",
+    "Sealed-case PC",
+    "Multi-system Chassis",
+    "Compact PCI",
+    "Advanced TCA",
+    "Blade",
+    "Blade Enclosure",
+    "Tablet",
+    "Convertible",
+    "Detachable",
+    "IoT Gateway",
+    "Embedded PC",
+    "Mini PC",
+    "Stick PC",
+];
+
+impl BoardInfo {
+    pub fn collect() -> Self {
+        let board = if cfg!(target_os = "windows") {
+            windows_board()
+        } else if cfg!(target_os = "macos") {
+            macos_board()
+        } else if is_bsd() {
+            bsd_board()
+        } else {
+            sysfs_board()
+        };
+        board.unwrap_or_else(Self::empty)
+    }
+
+    fn empty() -> Self {
+        Self {
+            system_vendor: None,
+            product_name: None,
+            board_vendor: None,
+            board_name: None,
+            board_version: None,
+            bios_vendor: None,
+            bios_version: None,
+            bios_date: None,
+            chassis_type: None,
+            uuid: None,
+            system_serial: None,
+            board_serial: None,
+            chassis_serial: None,
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Board Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        let join = |parts: &[&Option<String>]| {
+            let parts: Vec<&str> = parts.iter().filter_map(|part| part.as_deref()).collect();
+            if parts.is_empty() {
+                "Unknown".into()
+            } else {
+                parts.join(" ")
+            }
+        };
+        options.line(format_args!(
+            "  System  : {}",
+            join(&[&self.system_vendor, &self.product_name])
+        ));
+        options.line(format_args!(
+            "  Board   : {}",
+            join(&[&self.board_vendor, &self.board_name, &self.board_version])
+        ));
+        let date = self.bios_date.as_ref().map(|date| format!("({})", date));
+        options.line(format_args!(
+            "  BIOS    : {}",
+            join(&[&self.bios_vendor, &self.bios_version, &date])
+        ));
+        options.line(format_args!(
+            "  Chassis : {}",
+            self.chassis_type.as_deref().unwrap_or("Unknown")
+        ));
+        for (key, value) in [
+            ("UUID    ", &self.uuid),
+            ("Serial  ", &self.system_serial),
+            ("Board SN", &self.board_serial),
+            ("Case SN ", &self.chassis_serial),
+        ] {
+            if let Some(value) = value {
+                options.line(format_args!("  {} : {}", key, value));
+            }
+        }
+    }
+}
+
+/// Drops the placeholders of unset fields.
+fn field(value: Option<String>) -> Option<String> {
+    value.filter(|value| !PLACEHOLDERS.contains(&value.trim()))
+}
+
+fn chassis_name(code: u64) -> Option<String> {
+    let index = usize::try_from(code).ok()?.checked_sub(1)?;
+    CHASSIS_TYPES.get(index).map(|name| name.to_string())
+}
+
+/// Reads `/sys/class/dmi/id` on Linux, which exposes everything but the
+/// UUID and serial numbers without privileges.
+fn sysfs_board() -> Option<BoardInfo> {
+    let dmi = Path::new("/sys/class/dmi/id");
+    if !dmi.exists() {
+        return None;
+    }
+    let read = |name: &str| field(read_trimmed(dmi.join(name)));
+
+    Some(BoardInfo {
+        system_vendor: read("sys_vendor"),
+        product_name: read("product_name"),
+        board_vendor: read("board_vendor"),
+        board_name: read("board_name"),
+        board_version: read("board_version"),
+        bios_vendor: read("bios_vendor"),
+        bios_version: read("bios_version"),
+        bios_date: read("bios_date"),
+        chassis_type: read("chassis_type")
+            .and_then(|code| code.parse().ok())
+            .and_then(chassis_name),
+        uuid: read("product_uuid"),
+        system_serial: read("product_serial"),
+        board_serial: read("board_serial"),
+        chassis_serial: read("chassis_serial"),
+    })
+}
+
+/// FreeBSD's loader copies SMBIOS into the kernel environment as
+/// `smbios.system.maker` and the like; NetBSD has `machdep.dmi` sysctls and
+/// OpenBSD a handful of `hw` ones.
+fn bsd_board() -> Option<BoardInfo> {
+    let kenv = |name: &str| field(command_output("kenv", &["-q", &format!("smbios.{}", name)]));
+    if kenv("system.maker").is_some() {
+        return Some(BoardInfo {
+            system_vendor: kenv("system.maker"),
+            product_name: kenv("system.product"),
+            board_vendor: kenv("planar.maker"),
+            board_name: kenv("planar.product"),
+            board_version: kenv("planar.version"),
+            bios_vendor: kenv("bios.vendor"),
+            bios_version: kenv("bios.version"),
+            bios_date: kenv("bios.reldate"),
+            chassis_type: kenv("chassis.type"),
+            uuid: kenv("system.uuid"),
+            system_serial: kenv("system.serial"),
+            board_serial: kenv("planar.serial"),
+            chassis_serial: kenv("chassis.serial"),
+        });
+    }
+
+    let dmi = |name: &str| field(sysctl(&format!("machdep.dmi.{}", name)));
+    if dmi("system-vendor").is_some() {
+        return Some(BoardInfo {
+            system_vendor: dmi("system-vendor"),
+            product_name: dmi("system-product"),
+            board_vendor: dmi("board-vendor"),
+            board_name: dmi("board-product"),
+            board_version: dmi("board-version"),
+            bios_vendor: dmi("bios-vendor"),
+            bios_version: dmi("bios-version"),
+            bios_date: dmi("bios-date"),
+            chassis_type: dmi("chassis-type")
+                .and_then(|code| code.parse().ok())
+                .and_then(chassis_name),
+            uuid: dmi("system-uuid"),
+            system_serial: dmi("system-serial"),
+            board_serial: dmi("board-serial"),
+            chassis_serial: dmi("chassis-serial"),
+        });
+    }
+
+    let hw = |name: &str| field(sysctl(&format!("hw.{}", name)));
+    hw("vendor").map(|vendor| BoardInfo {
+        system_vendor: Some(vendor),
+        product_name: hw("product"),
+        board_version: hw("version"),
+        uuid: hw("uuid"),
+        system_serial: hw("serialno"),
+        ..BoardInfo::empty()
+    })
+}
+
+fn windows_board() -> Option<BoardInfo> {
+    let output = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "$system = Get-CimInstance Win32_ComputerSystem; \
+             $board = Get-CimInstance Win32_BaseBoard; \
+             $bios = Get-CimInstance Win32_BIOS; \
+             $enclosure = Get-CimInstance Win32_SystemEnclosure | Select-Object -First 1; \
+             $product = Get-CimInstance Win32_ComputerSystemProduct; \
+             [pscustomobject]@{ \
+               SystemVendor = $system.Manufacturer; ProductName = $system.Model; \
+               BoardVendor = $board.Manufacturer; BoardName = $board.Product; \
+               BoardVersion = $board.Version; BiosVendor = $bios.Manufacturer; \
+               BiosVersion = $bios.SMBIOSBIOSVersion; \
+               BiosDate = $bios.ReleaseDate.ToString('yyyy-MM-dd'); \
+               ChassisType = @($enclosure.ChassisTypes)[0]; Uuid = $product.UUID; \
+               SystemSerial = $bios.SerialNumber; BoardSerial = $board.SerialNumber; \
+               ChassisSerial = $enclosure.SerialNumber } | ConvertTo-Json",
+        ],
+    )?;
+    let board = json_objects(&output).into_iter().next()?;
+    let read = |key: &str| field(json_string(&board, key));
+
+    Some(BoardInfo {
+        system_vendor: read("SystemVendor"),
+        product_name: read("ProductName"),
+        board_vendor: read("BoardVendor"),
+        board_name: read("BoardName"),
+        board_version: read("BoardVersion"),
+        bios_vendor: read("BiosVendor"),
+        bios_version: read("BiosVersion"),
+        bios_date: read("BiosDate"),
+        chassis_type: board["ChassisType"].as_u64().and_then(chassis_name),
+        uuid: read("Uuid"),
+        system_serial: read("SystemSerial"),
+        board_serial: read("BoardSerial"),
+        chassis_serial: read("ChassisSerial"),
+    })
+}
+
+/// Macs have no SMBIOS tables to speak of; IOKit names the model and board,
+/// and `system_profiler` the firmware.
+fn macos_board() -> Option<BoardInfo> {
+    let registry = command_output("ioreg", &["-rd1", "-c", "IOPlatformExpertDevice"])?;
+    // Properties read `"model" = <"MacBookPro18,3">`.
+    let property = |name: &str| {
+        let key = format!("\"{}\" = ", name);
+        registry.lines().find_map(|line| {
+            let value = line.trim().strip_prefix(&key)?;
+            let value = value.trim_start_matches("<\"").trim_end_matches("\">");
+            field(Some(value.trim_matches('"').to_string()))
+        })
+    };
+    let hardware = command_output("system_profiler", &["SPHardwareDataType", "-json"])
+        .and_then(|output| output.parse::<serde_json::Value>().ok())
+        .and_then(|value| value["SPHardwareDataType"].get(0).cloned())
+        .unwrap_or_default();
+    let model = property("model");
+
+    Some(BoardInfo {
+        system_vendor: property("manufacturer"),
+        chassis_type: model.as_deref().map(|model| {
+            if model.contains("Book") {
+                "Notebook".into()
+            } else {
+                "Desktop".into()
+            }
+        }),
+        product_name: model,
+        board_vendor: property("manufacturer"),
+        board_name: property("board-id").or_else(|| property("target-type")),
+        board_version: None,
+        bios_vendor: Some("Apple Inc.".into()),
+        bios_version: json_string(&hardware, "boot_rom_version"),
+        bios_date: None,
+        uuid: property("IOPlatformUUID"),
+        system_serial: property("IOPlatformSerialNumber"),
+        board_serial: None,
+        chassis_serial: None,
+    })
+}
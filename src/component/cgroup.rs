@@ -0,0 +1,212 @@
+use {
+    crate::{format::DisplayOptions, util::read_trimmed},
+    serde::{Deserialize, Serialize},
+    std::path::{Path, PathBuf},
+    sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System},
+};
+
+/// The resource limits of the control group `envin` runs in, as Docker and
+/// Kubernetes set them, next to the host totals they narrow. Linux only.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CgroupInfo {
+    /// `v1` or `v2`, if control groups are mounted.
+    pub version: Option<String>,
+    /// The group's path below the hierarchy root.
+    pub path: Option<String>,
+    /// `None` when memory is not limited.
+    pub memory_limit_bytes: Option<u64>,
+    pub memory_usage_bytes: Option<u64>,
+    pub host_memory_bytes: u64,
+    /// How many CPUs' worth of time the quota allows, `None` when unlimited.
+    pub cpu_quota: Option<f64>,
+    /// The relative share of CPU time: `cpu.shares` on v1, defaulting to
+    /// 1024, or `cpu.weight` on v2, defaulting to 100.
+    pub cpu_weight: Option<u64>,
+    /// CPU time used by the group since it was created.
+    pub cpu_usage_usec: Option<u64>,
+    pub host_cpus: usize,
+}
+
+/// Where the hierarchies are mounted.
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Limits at or above this are how v1 spells "unlimited", rounded down to a
+/// page.
+const V1_UNLIMITED: u64 = 1 << 62;
+
+impl CgroupInfo {
+    pub fn collect() -> Self {
+        // The standard library's CPU count already honors the quota, so the
+        // host's comes from sysinfo.
+        let sys = System::new_with_specifics(
+            RefreshKind::new()
+                .with_memory(MemoryRefreshKind::new().with_ram())
+                .with_cpu(CpuRefreshKind::new()),
+        );
+        let mut info = Self {
+            version: None,
+            path: None,
+            memory_limit_bytes: None,
+            memory_usage_bytes: None,
+            host_memory_bytes: sys.total_memory(),
+            cpu_quota: None,
+            cpu_weight: None,
+            cpu_usage_usec: None,
+            host_cpus: sys.cpus().len(),
+        };
+
+        let Some(groups) = read_trimmed("/proc/self/cgroup") else {
+            return info;
+        };
+        if Path::new(CGROUP_ROOT).join("cgroup.controllers").exists() {
+            info.read_v2(&groups);
+        } else if Path::new(CGROUP_ROOT).join("memory").exists()
+            || Path::new(CGROUP_ROOT).join("cpu").exists()
+        {
+            info.read_v1(&groups);
+        }
+        info
+    }
+
+    /// Reads the unified hierarchy, named by the `0::/path` line.
+    fn read_v2(&mut self, groups: &str) {
+        let path = groups
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .unwrap_or("/");
+        let directory = group_directory(Path::new(CGROUP_ROOT), path);
+        let read = |file: &str| read_trimmed(directory.join(file));
+
+        self.version = Some("v2".into());
+        self.path = Some(path.to_string());
+        self.memory_limit_bytes = read("memory.max").and_then(|max| max.parse().ok());
+        self.memory_usage_bytes = read("memory.current").and_then(|usage| usage.parse().ok());
+        // `cpu.max` reads `max 100000` or `200000 100000`: quota and period.
+        self.cpu_quota = read("cpu.max").and_then(|max| {
+            let (quota, period) = max.split_once(' ')?;
+            quota_cpus(quota.parse().ok()?, period.parse().ok()?)
+        });
+        self.cpu_weight = read("cpu.weight").and_then(|weight| weight.parse().ok());
+        self.cpu_usage_usec = read("cpu.stat").and_then(|stat| {
+            stat.lines()
+                .find_map(|line| line.strip_prefix("usage_usec "))?
+                .parse()
+                .ok()
+        });
+    }
+
+    /// Reads the per-controller hierarchies, named by lines such as
+    /// `4:memory:/docker/1f2e...` or `3:cpu,cpuacct:/docker/1f2e...`.
+    fn read_v1(&mut self, groups: &str) {
+        let controller = |name: &str| {
+            groups.lines().find_map(|line| {
+                let mut fields = line.splitn(3, ':');
+                let (_, controllers, path) = (fields.next()?, fields.next()?, fields.next()?);
+                controllers
+                    .split(',')
+                    .any(|controller| controller == name)
+                    .then(|| {
+                        let root = Path::new(CGROUP_ROOT).join(name);
+                        (path.to_string(), group_directory(&root, path))
+                    })
+            })
+        };
+        let number = |directory: &Path, file: &str| {
+            read_trimmed(directory.join(file)).and_then(|value| value.parse::<i64>().ok())
+        };
+
+        self.version = Some("v1".into());
+        if let Some((path, memory)) = controller("memory") {
+            self.path = Some(path);
+            self.memory_limit_bytes = number(&memory, "memory.limit_in_bytes")
+                .map(|limit| limit as u64)
+                .filter(|limit| *limit < V1_UNLIMITED);
+            self.memory_usage_bytes =
+                number(&memory, "memory.usage_in_bytes").map(|usage| usage as u64);
+        }
+        if let Some((path, cpu)) = controller("cpu") {
+            self.path.get_or_insert(path);
+            // A quota of -1 means unlimited.
+            self.cpu_quota = number(&cpu, "cpu.cfs_quota_us")
+                .zip(number(&cpu, "cpu.cfs_period_us"))
+                .and_then(|(quota, period)| {
+                    quota_cpus(u64::try_from(quota).ok()?, u64::try_from(period).ok()?)
+                });
+            self.cpu_weight = number(&cpu, "cpu.shares").map(|shares| shares as u64);
+        }
+        if let Some((_, cpuacct)) = controller("cpuacct") {
+            self.cpu_usage_usec =
+                number(&cpuacct, "cpuacct.usage").map(|nanoseconds| nanoseconds as u64 / 1000);
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Cgroup Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        let Some(version) = &self.version else {
+            options.line("  Cgroup        : None");
+            return;
+        };
+        options.line(format_args!("  Version       : {}", version));
+        options.line(format_args!(
+            "  Path          : {}",
+            self.path.as_deref().unwrap_or("Unknown")
+        ));
+        match (self.memory_limit_bytes, self.memory_usage_bytes) {
+            (Some(limit), Some(usage)) => options.line(format_args!(
+                "  Memory        : {} (host {})",
+                options.usage(usage, limit),
+                options.bytes(self.host_memory_bytes)
+            )),
+            (None, Some(usage)) => options.line(format_args!(
+                "  Memory        : {} / unlimited (host {})",
+                options.bytes(usage),
+                options.bytes(self.host_memory_bytes)
+            )),
+            (Some(limit), None) => options.line(format_args!(
+                "  Memory Limit  : {} (host {})",
+                options.bytes(limit),
+                options.bytes(self.host_memory_bytes)
+            )),
+            (None, None) => options.line("  Memory        : Unknown"),
+        }
+        options.line(format_args!(
+            "  CPU Quota     : {} (host {} CPUs)",
+            self.cpu_quota
+                .map(|cpus| format!("{:.2} CPUs", cpus))
+                .unwrap_or_else(|| "unlimited".into()),
+            self.host_cpus
+        ));
+        options.line(format_args!(
+            "  CPU Weight    : {}",
+            self.cpu_weight
+                .map(|weight| weight.to_string())
+                .unwrap_or_else(|| "Unknown".into())
+        ));
+        options.line(format_args!(
+            "  CPU Time      : {}",
+            self.cpu_usage_usec
+                .map(|usec| format!("{:.1} s", usec as f64 / 1_000_000.0))
+                .unwrap_or_else(|| "Unknown".into())
+        ));
+    }
+}
+
+/// Finds the group's directory under `root`. With a cgroup namespace, as in
+/// most containers, the group is mounted at the root itself.
+fn group_directory(root: &Path, path: &str) -> PathBuf {
+    let directory = root.join(path.trim_start_matches('/'));
+    if directory.exists() {
+        directory
+    } else {
+        root.to_path_buf()
+    }
+}
+
+fn quota_cpus(quota: u64, period: u64) -> Option<f64> {
+    (period > 0).then(|| quota as f64 / period as f64)
+}
@@ -0,0 +1,277 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, command_output_timeout, json_string, read_trimmed},
+    },
+    serde::{Deserialize, Serialize},
+    serde_json::Value,
+    std::{path::Path, time::Duration},
+};
+
+/// The cloud the machine runs in, recognized from the SMBIOS strings the
+/// providers put in their virtual firmware. The instance type, region and
+/// zone mostly come from the provider's metadata service, which is only
+/// asked when told to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CloudInfo {
+    /// `AWS`, `GCP`, `Azure` or `DigitalOcean`.
+    pub provider: Option<String>,
+    /// Such as `m5.large`, `e2-medium` or `Standard_D2s_v3`.
+    pub instance_type: Option<String>,
+    pub region: Option<String>,
+    pub zone: Option<String>,
+    /// Whether the metadata service was asked, and answered.
+    pub metadata: bool,
+}
+
+/// The link-local address AWS, Azure and DigitalOcean serve metadata on.
+const METADATA_ADDRESS: &str = "http://169.254.169.254";
+
+/// The metadata services answer within milliseconds from inside the cloud;
+/// elsewhere the address drops packets.
+const METADATA_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The asset tag Azure sets on the chassis of its virtual machines, which
+/// Hyper-V guests outside Azure lack.
+const AZURE_ASSET_TAG: &str = "7783-7084-3265-9085-8269-3286-77";
+
+impl CloudInfo {
+    pub fn collect(query_metadata: bool) -> Self {
+        let dmi = Dmi::read();
+        let provider = dmi.provider();
+        let mut info = Self {
+            // Nitro instances name their type as the product.
+            instance_type: (provider == Some("AWS"))
+                .then(|| dmi.product_name.clone())
+                .flatten()
+                .filter(|name| name.contains('.')),
+            provider: provider.map(str::to_string),
+            region: None,
+            zone: None,
+            metadata: false,
+        };
+        if query_metadata {
+            match provider {
+                Some("AWS") => info.read_aws(),
+                Some("GCP") => info.read_gcp(),
+                Some("Azure") => info.read_azure(),
+                Some("DigitalOcean") => info.read_digitalocean(),
+                _ => {}
+            }
+        }
+        info
+    }
+
+    /// Asks IMDSv2 for a session token, then for the instance identity
+    /// document.
+    fn read_aws(&mut self) {
+        let Some(token) = fetch(
+            "PUT",
+            &format!("{}/latest/api/token", METADATA_ADDRESS),
+            "X-aws-ec2-metadata-token-ttl-seconds: 60",
+        ) else {
+            return;
+        };
+        let Some(document) = fetch_json(
+            &format!(
+                "{}/latest/dynamic/instance-identity/document",
+                METADATA_ADDRESS
+            ),
+            &format!("X-aws-ec2-metadata-token: {}", token.trim()),
+        ) else {
+            return;
+        };
+        self.metadata = true;
+        self.instance_type = json_string(&document, "instanceType").or(self.instance_type.take());
+        self.region = json_string(&document, "region");
+        self.zone = json_string(&document, "availabilityZone");
+    }
+
+    /// The machine type and zone are resource paths, as in
+    /// `projects/123/zones/us-central1-a`.
+    fn read_gcp(&mut self) {
+        let Some(instance) = fetch_json(
+            "http://metadata.google.internal/computeMetadata/v1/instance/?recursive=true",
+            "Metadata-Flavor: Google",
+        ) else {
+            return;
+        };
+        let last = |key: &str| {
+            json_string(&instance, key).and_then(|path| path.rsplit('/').next().map(str::to_string))
+        };
+        self.metadata = true;
+        self.instance_type = last("machineType");
+        self.zone = last("zone");
+        self.region = self
+            .zone
+            .as_ref()
+            .and_then(|zone| zone.rsplit_once('-').map(|(region, _)| region.to_string()));
+    }
+
+    /// Azure only names a zone for machines placed in one.
+    fn read_azure(&mut self) {
+        let Some(compute) = fetch_json(
+            &format!(
+                "{}/metadata/instance/compute?api-version=2021-02-01",
+                METADATA_ADDRESS
+            ),
+            "Metadata: true",
+        ) else {
+            return;
+        };
+        self.metadata = true;
+        self.instance_type = json_string(&compute, "vmSize");
+        self.region = json_string(&compute, "location");
+        self.zone = json_string(&compute, "zone");
+    }
+
+    /// Droplets have no zones, and their metadata does not name the size.
+    fn read_digitalocean(&mut self) {
+        let Some(droplet) = fetch_json(&format!("{}/metadata/v1.json", METADATA_ADDRESS), "")
+        else {
+            return;
+        };
+        self.metadata = true;
+        self.region = json_string(&droplet, "region");
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Cloud Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        let Some(provider) = &self.provider else {
+            options.line("  Provider      : None");
+            return;
+        };
+        options.line(format_args!("  Provider      : {}", provider));
+        for (key, value) in [
+            ("Instance Type", &self.instance_type),
+            ("Region       ", &self.region),
+            ("Zone         ", &self.zone),
+        ] {
+            options.line(format_args!(
+                "  {} : {}",
+                key,
+                value.as_deref().unwrap_or("Unknown")
+            ));
+        }
+        if !self.metadata {
+            options.line("  Metadata      : not queried or unreachable");
+        }
+    }
+}
+
+/// The SMBIOS strings the providers are told apart by.
+struct Dmi {
+    system_vendor: Option<String>,
+    product_name: Option<String>,
+    bios_vendor: Option<String>,
+    bios_version: Option<String>,
+    asset_tag: Option<String>,
+}
+
+impl Dmi {
+    fn read() -> Self {
+        if cfg!(target_os = "windows") {
+            return Self::windows().unwrap_or(Self::empty());
+        }
+        let dmi = Path::new("/sys/class/dmi/id");
+        let read = |name: &str| read_trimmed(dmi.join(name));
+        Self {
+            system_vendor: read("sys_vendor"),
+            product_name: read("product_name"),
+            bios_vendor: read("bios_vendor"),
+            bios_version: read("bios_version"),
+            asset_tag: read("chassis_asset_tag"),
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            system_vendor: None,
+            product_name: None,
+            bios_vendor: None,
+            bios_version: None,
+            asset_tag: None,
+        }
+    }
+
+    fn windows() -> Option<Self> {
+        let output = command_output(
+            "powershell",
+            &[
+                "-NoProfile",
+                "-Command",
+                "$system = Get-CimInstance Win32_ComputerSystem; \
+                 $bios = Get-CimInstance Win32_BIOS; \
+                 $enclosure = Get-CimInstance Win32_SystemEnclosure | Select-Object -First 1; \
+                 [pscustomobject]@{ \
+                   SystemVendor = $system.Manufacturer; ProductName = $system.Model; \
+                   BiosVendor = $bios.Manufacturer; BiosVersion = $bios.SMBIOSBIOSVersion; \
+                   AssetTag = $enclosure.SMBIOSAssetTag } | ConvertTo-Json",
+            ],
+        )?;
+        let dmi: Value = output.parse().ok()?;
+        Some(Self {
+            system_vendor: json_string(&dmi, "SystemVendor"),
+            product_name: json_string(&dmi, "ProductName"),
+            bios_vendor: json_string(&dmi, "BiosVendor"),
+            bios_version: json_string(&dmi, "BiosVersion"),
+            asset_tag: json_string(&dmi, "AssetTag"),
+        })
+    }
+
+    /// Older Xen instances on AWS only mention Amazon in the BIOS version,
+    /// as in `4.11.amazon`.
+    fn provider(&self) -> Option<&'static str> {
+        let has = |field: &Option<String>, needle: &str| {
+            field
+                .as_deref()
+                .is_some_and(|value| value.to_ascii_lowercase().contains(needle))
+        };
+        if has(&self.system_vendor, "amazon")
+            || has(&self.bios_vendor, "amazon")
+            || has(&self.bios_version, "amazon")
+        {
+            Some("AWS")
+        } else if has(&self.system_vendor, "google") || has(&self.product_name, "google compute") {
+            Some("GCP")
+        } else if has(&self.system_vendor, "microsoft")
+            && self.asset_tag.as_deref() == Some(AZURE_ASSET_TAG)
+        {
+            Some("Azure")
+        } else if has(&self.system_vendor, "digitalocean") {
+            Some("DigitalOcean")
+        } else {
+            None
+        }
+    }
+}
+
+/// Requests `url` with `curl`, bypassing any proxy, as the metadata
+/// services only answer from the instance itself.
+fn fetch(method: &str, url: &str, header: &str) -> Option<String> {
+    let timeout = METADATA_TIMEOUT.as_secs().to_string();
+    let mut args = vec![
+        "--fail",
+        "--silent",
+        "--noproxy",
+        "*",
+        "--max-time",
+        &timeout,
+        "--request",
+        method,
+    ];
+    if !header.is_empty() {
+        args.extend(["--header", header]);
+    }
+    args.push(url);
+    command_output_timeout("curl", &args, METADATA_TIMEOUT + Duration::from_secs(1))
+}
+
+fn fetch_json(url: &str, header: &str) -> Option<Value> {
+    fetch("GET", url, header)?.parse().ok()
+}
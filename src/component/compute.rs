@@ -0,0 +1,283 @@
+use {
+    crate::{
+        component::gpu::nvidia_cuda_version,
+        format::DisplayOptions,
+        util::{command_output, command_output_timeout, parse_version, read_trimmed},
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        env, fs,
+        path::{Path, PathBuf},
+        time::Duration,
+    },
+};
+
+/// The GPU compute stacks installed for machine learning work: NVIDIA's
+/// CUDA, AMD's ROCm and Intel's Level Zero with oneAPI. Each is `None` when
+/// none of its parts were found.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ComputeInfo {
+    pub cuda: Option<CudaRuntime>,
+    pub rocm: Option<RocmRuntime>,
+    pub level_zero: Option<LevelZeroRuntime>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CudaRuntime {
+    /// The version of the kernel driver, such as `550.54.14`.
+    pub driver_version: Option<String>,
+    /// The newest CUDA version the driver supports.
+    pub driver_cuda_version: Option<String>,
+    /// The version of the toolkit `nvcc` belongs to.
+    pub toolkit_version: Option<String>,
+    pub toolkit_path: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RocmRuntime {
+    pub version: Option<String>,
+    pub hip_version: Option<String>,
+    pub path: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LevelZeroRuntime {
+    /// The version of the `ze_loader` library.
+    pub loader_version: Option<String>,
+    /// The version of the oneAPI DPC++ compiler.
+    pub oneapi_version: Option<String>,
+    pub oneapi_path: Option<String>,
+}
+
+/// The compilers load their whole toolchain just to print a version.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where distributions put shared libraries.
+const LIBRARY_DIRECTORIES: &[&str] = &[
+    "/usr/lib/x86_64-linux-gnu",
+    "/usr/lib/aarch64-linux-gnu",
+    "/usr/lib64",
+    "/usr/lib",
+    "/usr/local/lib",
+];
+
+impl ComputeInfo {
+    pub fn collect() -> Self {
+        Self {
+            cuda: cuda(),
+            rocm: rocm(),
+            level_zero: level_zero(),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Compute Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        let with_path = |version: &Option<String>, path: &Option<String>| match (version, path) {
+            (Some(version), Some(path)) => format!("{} ({})", version, path),
+            (Some(version), None) => version.clone(),
+            (None, Some(path)) => format!("Unknown ({})", path),
+            (None, None) => "None".into(),
+        };
+        let cuda = self.cuda.as_ref();
+        let driver = cuda.and_then(|cuda| cuda.driver_version.clone());
+        let supported = cuda
+            .and_then(|cuda| cuda.driver_cuda_version.as_ref())
+            .map(|version| format!("CUDA {}", version));
+        options.line(format_args!(
+            "  CUDA Driver  : {}",
+            match (driver, supported) {
+                (Some(driver), Some(supported)) => format!("{} ({})", driver, supported),
+                (Some(version), None) | (None, Some(version)) => version,
+                (None, None) => "None".into(),
+            }
+        ));
+        options.line(format_args!(
+            "  CUDA Toolkit : {}",
+            cuda.map(|cuda| with_path(&cuda.toolkit_version, &cuda.toolkit_path))
+                .unwrap_or_else(|| "None".into())
+        ));
+
+        options.line(format_args!(
+            "  ROCm         : {}",
+            self.rocm
+                .as_ref()
+                .map(|rocm| {
+                    let version = match (&rocm.version, &rocm.hip_version) {
+                        (Some(version), Some(hip)) => Some(format!("{}, HIP {}", version, hip)),
+                        (None, Some(hip)) => Some(format!("HIP {}", hip)),
+                        (version, None) => version.clone(),
+                    };
+                    with_path(&version, &rocm.path)
+                })
+                .unwrap_or_else(|| "None".into())
+        ));
+
+        let level_zero = self.level_zero.as_ref();
+        options.line(format_args!(
+            "  Level Zero   : {}",
+            level_zero
+                .and_then(|level_zero| level_zero.loader_version.as_deref())
+                .unwrap_or("None")
+        ));
+        options.line(format_args!(
+            "  oneAPI       : {}",
+            level_zero
+                .map(|level_zero| with_path(&level_zero.oneapi_version, &level_zero.oneapi_path))
+                .unwrap_or_else(|| "None".into())
+        ));
+    }
+}
+
+/// The first of the directories named by `variables`, then `defaults`, that
+/// exists.
+fn install_root(variables: &[&str], defaults: &[&str]) -> Option<PathBuf> {
+    variables
+        .iter()
+        .filter_map(env::var_os)
+        .map(PathBuf::from)
+        .chain(defaults.iter().map(PathBuf::from))
+        .find(|path| path.is_dir())
+}
+
+/// Runs `command` from `root/bin` if it is there, otherwise from `PATH`.
+fn run_tool(root: Option<&Path>, command: &str, args: &[&str]) -> Option<String> {
+    let local = root
+        .map(|root| root.join("bin").join(command))
+        .filter(|path| path.exists());
+    match local {
+        Some(path) => command_output_timeout(&path.to_string_lossy(), args, PROBE_TIMEOUT),
+        None => command_output_timeout(command, args, PROBE_TIMEOUT),
+    }
+}
+
+fn cuda() -> Option<CudaRuntime> {
+    // `/proc/driver/nvidia/version` reads `NVRM version: NVIDIA UNIX x86_64
+    // Kernel Module  550.54.14  Thu Feb 22 01:44:30 UTC 2024`.
+    let driver_version = read_trimmed("/proc/driver/nvidia/version")
+        .and_then(|version| {
+            let line = version.lines().next()?.to_string();
+            line.split_once("Kernel Module")
+                .and_then(|(_, rest)| rest.split_whitespace().next().map(str::to_string))
+        })
+        .or_else(|| {
+            command_output(
+                "nvidia-smi",
+                &["--query-gpu=driver_version", "--format=csv,noheader"],
+            )
+            .and_then(|output| output.lines().next().map(|line| line.trim().to_string()))
+        });
+    let driver_cuda_version = driver_version.as_ref().and_then(|_| nvidia_cuda_version());
+
+    let root = install_root(
+        &["CUDA_PATH", "CUDA_HOME"],
+        &["/usr/local/cuda", "/opt/cuda"],
+    );
+    // `nvcc --version` ends with `Cuda compilation tools, release 12.4,
+    // V12.4.131`.
+    let toolkit_version = run_tool(root.as_deref(), "nvcc", &["--version"])
+        .and_then(|output| {
+            let line = output.lines().find(|line| line.contains("release"))?;
+            parse_version(line.split_once("release")?.1)
+        })
+        .or_else(|| {
+            let root = root.as_ref()?;
+            // Toolkits since 11.1 ship `version.json`; older ones
+            // `version.txt`, reading `CUDA Version 10.2.89`.
+            fs::read_to_string(root.join("version.json"))
+                .ok()
+                .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+                .and_then(|json| json["cuda"]["version"].as_str().map(str::to_string))
+                .or_else(|| {
+                    let text = read_trimmed(root.join("version.txt"))?;
+                    parse_version(text.trim_start_matches("CUDA Version"))
+                })
+        });
+
+    if driver_version.is_none() && toolkit_version.is_none() {
+        return None;
+    }
+    Some(CudaRuntime {
+        driver_version,
+        driver_cuda_version,
+        toolkit_version,
+        toolkit_path: root.map(|root| root.display().to_string()),
+    })
+}
+
+fn rocm() -> Option<RocmRuntime> {
+    let root = install_root(&["ROCM_PATH", "HIP_PATH"], &["/opt/rocm"]);
+    // `.info/version` reads `6.0.2-115`. On Windows, the HIP SDK is
+    // installed under a directory named after its version.
+    let version = root.as_ref().and_then(|root| {
+        read_trimmed(root.join(".info/version"))
+            .and_then(|version| parse_version(&version))
+            .or_else(|| parse_version(&root.file_name()?.to_string_lossy()))
+    });
+    // `hipconfig --version` prints `6.0.32831-204d35d16`.
+    let hip_version = run_tool(root.as_deref(), "hipconfig", &["--version"])
+        .and_then(|output| parse_version(&output));
+
+    if root.is_none() && hip_version.is_none() {
+        return None;
+    }
+    Some(RocmRuntime {
+        version,
+        hip_version,
+        path: root.map(|root| root.display().to_string()),
+    })
+}
+
+fn level_zero() -> Option<LevelZeroRuntime> {
+    let loader_version = if cfg!(target_os = "windows") {
+        command_output(
+            "powershell",
+            &[
+                "-NoProfile",
+                "-Command",
+                "(Get-Item \"$env:SystemRoot\\System32\\ze_loader.dll\").VersionInfo.FileVersion",
+            ],
+        )
+        .and_then(|version| parse_version(&version))
+    } else {
+        loader_library_version()
+    };
+
+    let root = install_root(
+        &["ONEAPI_ROOT"],
+        &[
+            "/opt/intel/oneapi",
+            "C:\\Program Files (x86)\\Intel\\oneAPI",
+        ],
+    );
+    // `icpx --version` starts with `Intel(R) oneAPI DPC++/C++ Compiler
+    // 2024.0.2 (2024.0.2.20231213)`.
+    let compiler = root.as_ref().map(|root| root.join("compiler/latest"));
+    let oneapi_version = run_tool(compiler.as_deref(), "icpx", &["--version"])
+        .and_then(|output| parse_version(&output));
+
+    if loader_version.is_none() && root.is_none() && oneapi_version.is_none() {
+        return None;
+    }
+    Some(LevelZeroRuntime {
+        loader_version,
+        oneapi_version,
+        oneapi_path: root.map(|root| root.display().to_string()),
+    })
+}
+
+/// The loader is installed as `libze_loader.so.1.15.8`, with its version in
+/// the file name.
+fn loader_library_version() -> Option<String> {
+    LIBRARY_DIRECTORIES.iter().find_map(|directory| {
+        fs::read_dir(directory).ok()?.flatten().find_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let version = name.strip_prefix("libze_loader.so.")?;
+            (version.split('.').count() == 3).then(|| version.to_string())
+        })
+    })
+}
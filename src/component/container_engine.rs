@@ -0,0 +1,203 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output_timeout, json_string},
+    },
+    serde::{Deserialize, Serialize},
+    serde_json::Value,
+    std::{env, path::PathBuf, time::Duration},
+};
+
+/// The Docker or Podman engine serving containers on this host, asked
+/// through the Docker API on its socket. Podman answers the same API.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContainerEngineInfo {
+    /// `Docker` or `Podman`, `None` when no engine answered.
+    pub engine: Option<String>,
+    /// The socket the engine was reached on, or `docker` for the CLI.
+    pub socket: Option<String>,
+    pub version: Option<String>,
+    pub api_version: Option<String>,
+    /// Such as `overlay2`, `btrfs` or `zfs`.
+    pub storage_driver: Option<String>,
+    /// `systemd` or `cgroupfs`.
+    pub cgroup_driver: Option<String>,
+    pub rootless: bool,
+    pub containers_running: Option<u64>,
+    pub containers_total: Option<u64>,
+    pub images: Option<u64>,
+    /// Why a socket that exists could not be used, such as missing
+    /// membership in the `docker` group.
+    pub error: Option<String>,
+}
+
+/// How long the engine may take to answer; `info` walks every container.
+const ENGINE_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl ContainerEngineInfo {
+    pub fn collect() -> Self {
+        let mut info = Self {
+            engine: None,
+            socket: None,
+            version: None,
+            api_version: None,
+            storage_driver: None,
+            cgroup_driver: None,
+            rootless: false,
+            containers_running: None,
+            containers_total: None,
+            images: None,
+            error: None,
+        };
+
+        let Some(socket) = sockets().into_iter().find(|socket| socket.exists()) else {
+            // Docker Desktop on Windows listens on a named pipe, which only
+            // the CLI can talk to.
+            if let Some(system) = command_output_timeout(
+                "docker",
+                &["info", "--format", "{{json .}}"],
+                ENGINE_TIMEOUT,
+            )
+            .and_then(|output| output.parse::<Value>().ok())
+            {
+                info.socket = Some("docker".into());
+                info.engine = Some("Docker".into());
+                info.read_info(&system);
+            }
+            return info;
+        };
+
+        let path = socket.to_string_lossy().into_owned();
+        info.socket = Some(path.clone());
+        let (Some(version), Some(system)) = (request(&path, "/version"), request(&path, "/info"))
+        else {
+            info.error = Some(format!("{} is not accessible", path));
+            return info;
+        };
+        // Podman lists itself as the `Podman Engine` component.
+        let podman = version["Components"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|component| json_string(component, "Name").as_deref() == Some("Podman Engine"));
+        info.engine = Some(if podman { "Podman" } else { "Docker" }.into());
+        info.version = json_string(&version, "Version");
+        info.api_version = json_string(&version, "ApiVersion");
+        info.read_info(&system);
+        info
+    }
+
+    /// Reads the answer to `/info`, which `docker info` prints as well.
+    fn read_info(&mut self, system: &Value) {
+        self.version = self
+            .version
+            .take()
+            .or_else(|| json_string(system, "ServerVersion"));
+        self.storage_driver = json_string(system, "Driver");
+        self.cgroup_driver = json_string(system, "CgroupDriver");
+        self.rootless = system["SecurityOptions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|option| option.as_str() == Some("name=rootless"));
+        self.containers_running = system["ContainersRunning"].as_u64();
+        self.containers_total = system["Containers"].as_u64();
+        self.images = system["Images"].as_u64();
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Container Engine Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if let Some(error) = &self.error {
+            options.line(options.warn_if(true, format!("  Error      : {}", error)));
+            return;
+        }
+        let Some(engine) = &self.engine else {
+            options.line("  Engine     : None");
+            return;
+        };
+        let mut version = self.version.clone().unwrap_or_else(|| "Unknown".into());
+        if let Some(api) = &self.api_version {
+            version.push_str(&format!(" (API {})", api));
+        }
+        options.line(format_args!(
+            "  Engine     : {} {}{}",
+            engine,
+            version,
+            if self.rootless { ", rootless" } else { "" }
+        ));
+        options.line(format_args!(
+            "  Socket     : {}",
+            self.socket.as_deref().unwrap_or("Unknown")
+        ));
+        options.line(format_args!(
+            "  Storage    : {}",
+            self.storage_driver.as_deref().unwrap_or("Unknown")
+        ));
+        options.line(format_args!(
+            "  Cgroups    : {}",
+            self.cgroup_driver.as_deref().unwrap_or("Unknown")
+        ));
+        let count = |count: Option<u64>| {
+            count
+                .map(|count| count.to_string())
+                .unwrap_or_else(|| "Unknown".into())
+        };
+        options.line(format_args!(
+            "  Containers : {} running of {}",
+            count(self.containers_running),
+            count(self.containers_total)
+        ));
+        options.line(format_args!("  Images     : {}", count(self.images)));
+    }
+}
+
+/// The sockets to try, in order: `DOCKER_HOST`, the rootful engines, then
+/// the rootless ones under `XDG_RUNTIME_DIR`.
+fn sockets() -> Vec<PathBuf> {
+    let mut sockets = Vec::new();
+    if let Some(path) = env::var("DOCKER_HOST")
+        .ok()
+        .as_deref()
+        .and_then(|host| host.strip_prefix("unix://"))
+    {
+        sockets.push(PathBuf::from(path));
+    }
+    sockets.push(PathBuf::from("/var/run/docker.sock"));
+    sockets.push(PathBuf::from("/run/podman/podman.sock"));
+    if let Some(runtime) = env::var_os("XDG_RUNTIME_DIR") {
+        let runtime = PathBuf::from(runtime);
+        sockets.push(runtime.join("docker.sock"));
+        sockets.push(runtime.join("podman/podman.sock"));
+    }
+    if let Some(home) = env::var_os("HOME") {
+        // Docker Desktop on macOS keeps its socket in the home directory.
+        sockets.push(PathBuf::from(home).join(".docker/run/docker.sock"));
+    }
+    sockets
+}
+
+/// Asks the engine's API with `curl`, which speaks HTTP over Unix sockets.
+fn request(socket: &str, endpoint: &str) -> Option<Value> {
+    let url = format!("http://localhost{}", endpoint);
+    let timeout = ENGINE_TIMEOUT.as_secs().to_string();
+    command_output_timeout(
+        "curl",
+        &[
+            "--fail",
+            "--silent",
+            "--max-time",
+            &timeout,
+            "--unix-socket",
+            socket,
+            &url,
+        ],
+        ENGINE_TIMEOUT + Duration::from_secs(1),
+    )?
+    .parse()
+    .ok()
+}
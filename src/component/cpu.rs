@@ -0,0 +1,399 @@
+use {
+    super::pressure::Pressure,
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, is_bsd, json_objects, parse_cpu_list, read_trimmed, sysctl},
+    },
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeSet, fs, thread, time::Duration},
+    sysinfo::{System, MINIMUM_CPU_UPDATE_INTERVAL},
+};
+
+/// The processor model, topology, caches and clock speeds.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CpuInfo {
+    pub brand: String,
+    pub vendor_id: String,
+    pub sockets: Option<usize>,
+    pub physical_cores: usize,
+    pub logical_cores: usize,
+    /// One entry per kind of cache rather than per core.
+    #[serde(default)]
+    pub caches: Vec<CpuCache>,
+    pub cores: Vec<CoreInfo>,
+    /// Only sampled on request since it takes an interval to measure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<CpuUsage>,
+    /// Time tasks waited for a CPU, on Linux.
+    pub pressure: Option<Pressure>,
+}
+
+/// A single logical core. The frequency range is only known on platforms
+/// exposing it, such as Linux through cpufreq.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CoreInfo {
+    pub name: String,
+    pub frequency_mhz: u64,
+    pub min_frequency_mhz: Option<u64>,
+    pub max_frequency_mhz: Option<u64>,
+}
+
+/// A level of the cache hierarchy, summed up over the cores sharing it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CpuCache {
+    pub level: u8,
+    /// `data`, `instruction` or `unified`.
+    pub kind: String,
+    /// The size of one instance of the cache.
+    pub size_bytes: u64,
+    /// How many separate instances there are, such as one L2 per core and
+    /// one L3 per socket.
+    pub instances: Option<usize>,
+}
+
+/// CPU usage measured over a sampling interval, in percent.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CpuUsage {
+    pub interval_ms: u64,
+    pub global_percent: f32,
+    pub per_core_percent: Vec<f32>,
+}
+
+impl CpuInfo {
+    /// Reads the CPU details from an already refreshed `sys`, sampling usage
+    /// over `usage_interval` if one is given.
+    pub fn collect(sys: &mut System, usage_interval: Option<Duration>) -> Self {
+        let usage = usage_interval.map(|interval| {
+            // Usage is computed from the difference between two refreshes.
+            let interval = interval.max(MINIMUM_CPU_UPDATE_INTERVAL);
+            sys.refresh_cpu_usage();
+            thread::sleep(interval);
+            sys.refresh_cpu_usage();
+
+            CpuUsage {
+                interval_ms: interval.as_millis() as u64,
+                global_percent: sys.global_cpu_usage(),
+                per_core_percent: sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect(),
+            }
+        });
+
+        let cores = sys
+            .cpus()
+            .iter()
+            .enumerate()
+            .map(|(index, cpu)| CoreInfo {
+                name: cpu.name().to_string(),
+                frequency_mhz: cpu.frequency(),
+                min_frequency_mhz: cpufreq_mhz(index, "cpuinfo_min_freq"),
+                max_frequency_mhz: cpufreq_mhz(index, "cpuinfo_max_freq"),
+            })
+            .collect();
+
+        let first = sys.cpus().first();
+        let (sockets, caches) = if cfg!(any(target_os = "linux", target_os = "android")) {
+            (sysfs_sockets(), sysfs_caches())
+        } else if cfg!(target_os = "macos") {
+            macos_topology()
+        } else if cfg!(target_os = "windows") {
+            windows_topology()
+        } else {
+            (None, Vec::new())
+        };
+
+        // sysinfo has no backend for OpenBSD and NetBSD, which name the CPU
+        // and count its threads in sysctls.
+        let fallback = (first.is_none() && is_bsd()).then(|| {
+            let threads = sysctl("hw.ncpuonline")
+                .or_else(|| sysctl("hw.ncpu"))
+                .and_then(|count| count.parse().ok())
+                .unwrap_or(0);
+            (sysctl("hw.model").unwrap_or_default(), threads)
+        });
+        let (brand, physical_cores, logical_cores) = match fallback {
+            Some((brand, threads)) => (brand, threads, threads),
+            None => (
+                first.map(|cpu| cpu.brand().to_string()).unwrap_or_default(),
+                sys.physical_core_count().unwrap_or(0),
+                sys.cpus().len(),
+            ),
+        };
+
+        Self {
+            brand,
+            vendor_id: first
+                .map(|cpu| cpu.vendor_id().to_string())
+                .unwrap_or_default(),
+            sockets,
+            physical_cores,
+            logical_cores,
+            caches,
+            cores,
+            usage,
+            pressure: Pressure::read("cpu"),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "  CPU    : {} ({} cores)",
+            self.brand, self.physical_cores
+        ));
+        let sockets = self
+            .sockets
+            .map(|sockets| format!("{} socket{}, ", sockets, plural(sockets)))
+            .unwrap_or_default();
+        options.line(format_args!(
+            "  Layout : {}{} core{}, {} thread{}",
+            sockets,
+            self.physical_cores,
+            plural(self.physical_cores),
+            self.logical_cores,
+            plural(self.logical_cores)
+        ));
+        if !self.caches.is_empty() {
+            let caches: Vec<String> = self
+                .caches
+                .iter()
+                .map(|cache| {
+                    let suffix = match cache.kind.as_str() {
+                        "data" => "d",
+                        "instruction" => "i",
+                        _ => "",
+                    };
+                    let instances = cache
+                        .instances
+                        .filter(|instances| *instances > 1)
+                        .map(|instances| format!(" x{}", instances))
+                        .unwrap_or_default();
+                    format!(
+                        "L{}{} {}{}",
+                        cache.level,
+                        suffix,
+                        options.bytes(cache.size_bytes),
+                        instances
+                    )
+                })
+                .collect();
+            options.line(format_args!("  Cache  : {}", caches.join(", ")));
+        }
+
+        if !self.cores.is_empty() {
+            let current = self
+                .cores
+                .iter()
+                .map(|core| core.frequency_mhz)
+                .sum::<u64>()
+                / self.cores.len() as u64;
+            let min = self
+                .cores
+                .iter()
+                .filter_map(|core| core.min_frequency_mhz)
+                .min();
+            let max = self
+                .cores
+                .iter()
+                .filter_map(|core| core.max_frequency_mhz)
+                .max();
+            options.line(format_args!(
+                "  Clock  : {}",
+                format_frequency(current, min, max)
+            ));
+        }
+
+        if let Some(usage) = &self.usage {
+            options.line(format_args!(
+                "  Usage  : {:.1}% over {} ms",
+                usage.global_percent, usage.interval_ms
+            ));
+        }
+
+        if options.cores {
+            for (index, core) in self.cores.iter().enumerate() {
+                let usage = self
+                    .usage
+                    .as_ref()
+                    .and_then(|usage| usage.per_core_percent.get(index))
+                    .map(|percent| format!(", {:.1}%", percent))
+                    .unwrap_or_default();
+                options.line(format_args!(
+                    "    Core {:<3} : {}{}",
+                    index,
+                    format_frequency(
+                        core.frequency_mhz,
+                        core.min_frequency_mhz,
+                        core.max_frequency_mhz
+                    ),
+                    usage
+                ));
+            }
+        }
+    }
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+/// Counts the distinct `physical_package_id`s of the online CPUs.
+fn sysfs_sockets() -> Option<usize> {
+    let online = read_trimmed("/sys/devices/system/cpu/online")?;
+    let packages: BTreeSet<String> = parse_cpu_list(&online)
+        .into_iter()
+        .filter_map(|cpu| {
+            read_trimmed(format!(
+                "/sys/devices/system/cpu/cpu{}/topology/physical_package_id",
+                cpu
+            ))
+        })
+        .collect();
+    (!packages.is_empty()).then_some(packages.len())
+}
+
+/// Walks `/sys/devices/system/cpu/cpu*/cache/index*`, counting each cache
+/// once per distinct set of CPUs sharing it.
+fn sysfs_caches() -> Vec<CpuCache> {
+    let Some(online) = read_trimmed("/sys/devices/system/cpu/online") else {
+        return Vec::new();
+    };
+
+    // Keyed by level and kind, holding the size and the sharing CPU lists.
+    let mut caches: Vec<(CpuCache, BTreeSet<String>)> = Vec::new();
+    for cpu in parse_cpu_list(&online) {
+        let Ok(indexes) = fs::read_dir(format!("/sys/devices/system/cpu/cpu{}/cache", cpu)) else {
+            continue;
+        };
+        for index in indexes.filter_map(Result::ok) {
+            let read = |file: &str| read_trimmed(index.path().join(file));
+            let (Some(level), Some(kind), Some(size)) = (
+                read("level").and_then(|level| level.parse().ok()),
+                read("type").map(|kind| kind.to_lowercase()),
+                read("size").and_then(|size| cache_size(&size)),
+            ) else {
+                continue;
+            };
+            let shared = read("shared_cpu_list").unwrap_or_else(|| cpu.to_string());
+            match caches
+                .iter_mut()
+                .find(|(cache, _)| cache.level == level && cache.kind == kind)
+            {
+                Some((_, sharing)) => {
+                    sharing.insert(shared);
+                }
+                None => caches.push((
+                    CpuCache {
+                        level,
+                        kind,
+                        size_bytes: size,
+                        instances: None,
+                    },
+                    BTreeSet::from([shared]),
+                )),
+            }
+        }
+    }
+
+    let mut caches: Vec<CpuCache> = caches
+        .into_iter()
+        .map(|(cache, sharing)| CpuCache {
+            instances: Some(sharing.len()),
+            ..cache
+        })
+        .collect();
+    caches.sort_by(|a, b| (a.level, &a.kind).cmp(&(b.level, &b.kind)));
+    caches
+}
+
+/// Parses the cache sizes of sysfs, such as `48K` or `2048K`.
+fn cache_size(size: &str) -> Option<u64> {
+    let (number, shift) = match size.as_bytes().last()? {
+        b'K' => (&size[..size.len() - 1], 10),
+        b'M' => (&size[..size.len() - 1], 20),
+        _ => (size, 0),
+    };
+    Some(number.parse::<u64>().ok()? << shift)
+}
+
+/// Reads the `hw` sysctls, which give per-instance sizes only.
+fn macos_topology() -> (Option<usize>, Vec<CpuCache>) {
+    let sysctl = |name: &str| {
+        command_output("sysctl", &["-n", name])?
+            .parse::<u64>()
+            .ok()
+            .filter(|value| *value > 0)
+    };
+    let caches = [
+        ("hw.l1dcachesize", 1, "data"),
+        ("hw.l1icachesize", 1, "instruction"),
+        ("hw.l2cachesize", 2, "unified"),
+        ("hw.l3cachesize", 3, "unified"),
+    ]
+    .iter()
+    .filter_map(|(name, level, kind)| {
+        Some(CpuCache {
+            level: *level,
+            kind: kind.to_string(),
+            size_bytes: sysctl(name)?,
+            instances: None,
+        })
+    })
+    .collect();
+
+    (
+        sysctl("hw.packages").map(|packages| packages as usize),
+        caches,
+    )
+}
+
+/// Asks `Win32_Processor`, one object per socket, for its L2 and L3 sizes in
+/// KiB.
+fn windows_topology() -> (Option<usize>, Vec<CpuCache>) {
+    let Some(output) = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_Processor | Select-Object L2CacheSize,L3CacheSize | \
+             ConvertTo-Json",
+        ],
+    ) else {
+        return (None, Vec::new());
+    };
+
+    let processors = json_objects(&output);
+    let caches = [("L2CacheSize", 2), ("L3CacheSize", 3)]
+        .iter()
+        .filter_map(|(key, level)| {
+            let size = processors.first()?[key].as_u64().filter(|size| *size > 0)?;
+            Some(CpuCache {
+                level: *level,
+                kind: "unified".into(),
+                size_bytes: size << 10,
+                instances: None,
+            })
+        })
+        .collect();
+
+    ((!processors.is_empty()).then_some(processors.len()), caches)
+}
+
+/// Reads a cpufreq limit, which the kernel reports in kHz.
+fn cpufreq_mhz(index: usize, name: &str) -> Option<u64> {
+    read_trimmed(format!(
+        "/sys/devices/system/cpu/cpu{}/cpufreq/{}",
+        index, name
+    ))
+    .and_then(|value| value.parse::<u64>().ok())
+    .map(|khz| khz / 1000)
+}
+
+fn format_frequency(current: u64, min: Option<u64>, max: Option<u64>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("{} MHz ({} - {} MHz)", current, min, max),
+        (None, Some(max)) => format!("{} MHz (max {} MHz)", current, max),
+        _ => format!("{} MHz", current),
+    }
+}
@@ -0,0 +1,156 @@
+use {
+    crate::format::DisplayOptions,
+    serde::{Deserialize, Serialize},
+    std::env::consts::ARCH,
+};
+
+/// The instruction-set extensions of the CPU, as the standard library
+/// detects them through CPUID on x86 and the auxiliary vector or sysctls on
+/// ARM. Useful to check that a binary built with `-C target-cpu` will run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CpuFlagsInfo {
+    pub architecture: String,
+    /// The x86-64 microarchitecture level, `v1` to `v4`, that the flags
+    /// satisfy.
+    pub level: Option<String>,
+    /// The supported extensions among the ones checked for.
+    pub flags: Vec<String>,
+    /// The checked extensions this CPU lacks.
+    pub missing: Vec<String>,
+}
+
+/// Checks each feature with `$detect!`, which only takes string literals.
+macro_rules! detect_all {
+    ($detect:ident, $($feature:tt),* $(,)?) => {
+        vec![$(($feature, $detect!($feature))),*]
+    };
+}
+
+impl CpuFlagsInfo {
+    pub fn collect() -> Self {
+        let features = features();
+        let supported = |name: &str| {
+            features
+                .iter()
+                .any(|(feature, detected)| *feature == name && *detected)
+        };
+        let level = (ARCH == "x86_64").then(|| {
+            let level = X86_64_LEVELS
+                .iter()
+                .take_while(|(_, required)| required.iter().all(|feature| supported(feature)))
+                .last()
+                .map_or("v1", |(level, _)| level);
+            level.to_string()
+        });
+        let (flags, missing): (Vec<_>, Vec<_>) =
+            features.iter().partition(|(_, detected)| *detected);
+
+        Self {
+            architecture: ARCH.into(),
+            level,
+            flags: flags.iter().map(|(name, _)| name.to_string()).collect(),
+            missing: missing.iter().map(|(name, _)| name.to_string()).collect(),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}CPU Flags Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        options.line(format_args!("  Architecture : {}", self.architecture));
+        if let Some(level) = &self.level {
+            options.line(format_args!("  Level        : x86-64-{}", level));
+        }
+        let list = |flags: &[String]| {
+            if flags.is_empty() {
+                "None".into()
+            } else {
+                flags.join(" ")
+            }
+        };
+        options.line(format_args!("  Supported    : {}", list(&self.flags)));
+        options.line(format_args!("  Missing      : {}", list(&self.missing)));
+    }
+}
+
+/// The features each x86-64 level adds on top of the previous one, as far as
+/// `is_x86_feature_detected!` can tell.
+const X86_64_LEVELS: &[(&str, &[&str])] = &[
+    (
+        "v2",
+        &["cmpxchg16b", "popcnt", "sse3", "sse4.1", "sse4.2", "ssse3"],
+    ),
+    (
+        "v3",
+        &[
+            "avx", "avx2", "bmi1", "bmi2", "f16c", "fma", "lzcnt", "movbe", "xsave",
+        ],
+    ),
+    (
+        "v4",
+        &["avx512f", "avx512bw", "avx512cd", "avx512dq", "avx512vl"],
+    ),
+];
+
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+fn features() -> Vec<(&'static str, bool)> {
+    detect_all!(
+        is_x86_feature_detected,
+        "sse",
+        "sse2",
+        "sse3",
+        "ssse3",
+        "sse4.1",
+        "sse4.2",
+        "popcnt",
+        "cmpxchg16b",
+        "movbe",
+        "xsave",
+        "avx",
+        "avx2",
+        "fma",
+        "f16c",
+        "bmi1",
+        "bmi2",
+        "lzcnt",
+        "aes",
+        "pclmulqdq",
+        "sha",
+        "rdrand",
+        "rdseed",
+        "avx512f",
+        "avx512bw",
+        "avx512cd",
+        "avx512dq",
+        "avx512vl",
+        "avx512vnni",
+    )
+}
+
+#[cfg(target_arch = "aarch64")]
+fn features() -> Vec<(&'static str, bool)> {
+    use std::arch::is_aarch64_feature_detected;
+
+    detect_all!(
+        is_aarch64_feature_detected,
+        "neon",
+        "fp16",
+        "aes",
+        "pmull",
+        "sha2",
+        "sha3",
+        "crc",
+        "lse",
+        "dotprod",
+        "rdm",
+        "sve",
+        "sve2",
+    )
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "x86", target_arch = "aarch64")))]
+fn features() -> Vec<(&'static str, bool)> {
+    Vec::new()
+}
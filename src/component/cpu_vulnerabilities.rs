@@ -0,0 +1,90 @@
+use {
+    crate::{format::DisplayOptions, util::read_trimmed},
+    serde::{Deserialize, Serialize},
+    std::fs,
+};
+
+/// How the kernel judges the CPU against speculative execution flaws such
+/// as Spectre and Meltdown, from `/sys/devices/system/cpu/vulnerabilities`
+/// on Linux.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CpuVulnerabilitiesInfo {
+    pub vulnerabilities: Vec<CpuVulnerability>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CpuVulnerability {
+    /// The kernel's name for the flaw, such as `spectre_v2`.
+    pub name: String,
+    /// The kernel's verdict as is, such as `Mitigation: TSX disabled`.
+    pub status: String,
+    /// `not_affected`, `mitigated`, `vulnerable` or `unknown`.
+    pub state: String,
+}
+
+const VULNERABILITIES: &str = "/sys/devices/system/cpu/vulnerabilities";
+
+impl CpuVulnerabilitiesInfo {
+    pub fn collect() -> Self {
+        let mut vulnerabilities: Vec<CpuVulnerability> = fs::read_dir(VULNERABILITIES)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter_map(|entry| {
+                        let status = read_trimmed(entry.path())?;
+                        Some(CpuVulnerability {
+                            name: entry.file_name().to_string_lossy().into_owned(),
+                            state: state(&status).into(),
+                            status,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        vulnerabilities.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self { vulnerabilities }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}CPU Vulnerability Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.vulnerabilities.is_empty() {
+            options.line("  Vulnerabilities: None");
+            return;
+        }
+
+        let width = self
+            .vulnerabilities
+            .iter()
+            .map(|vulnerability| vulnerability.name.len())
+            .max()
+            .unwrap_or(0);
+        // Rows are printed as is: the statuses contain `:`.
+        for vulnerability in &self.vulnerabilities {
+            println!(
+                "  {} : {}",
+                options.key(&format!("{:<width$}", vulnerability.name, width = width)),
+                options.warn_if(vulnerability.state == "vulnerable", &vulnerability.status)
+            );
+        }
+    }
+}
+
+/// Mitigations can be partial, as in `Mitigation: Enhanced IBRS; BHI:
+/// Vulnerable`, which counts as vulnerable.
+fn state(status: &str) -> &'static str {
+    if status.starts_with("Not affected") {
+        "not_affected"
+    } else if status.contains("Vulnerable") {
+        "vulnerable"
+    } else if status.starts_with("Mitigation") {
+        "mitigated"
+    } else {
+        "unknown"
+    }
+}
@@ -0,0 +1,261 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output_timeout, parse_version},
+    },
+    serde::{Deserialize, Serialize},
+    std::{env, time::Duration},
+    sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System},
+    which::which,
+};
+
+/// The graphical session the user is logged into.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DesktopInfo {
+    pub environment: Option<String>,
+    pub window_manager: Option<String>,
+    /// `x11`, `wayland` or `tty` on Linux and the BSDs.
+    pub session_type: Option<String>,
+    /// The version the window manager or compositor reports.
+    pub window_manager_version: Option<String>,
+    /// The X server, such as `X.Org 21.1.11`, in X11 sessions.
+    pub x_server: Option<String>,
+    /// Whether XWayland is running to serve X11 clients, in Wayland sessions.
+    pub xwayland: Option<bool>,
+}
+
+/// Desktop environments by the process that is their shell or session.
+const ENVIRONMENTS: &[(&str, &str)] = &[
+    ("gnome-shell", "GNOME"),
+    ("plasmashell", "KDE Plasma"),
+    ("xfce4-session", "Xfce"),
+    ("cinnamon", "Cinnamon"),
+    ("mate-session", "MATE"),
+    ("lxqt-session", "LXQt"),
+    ("lxsession", "LXDE"),
+    ("budgie-panel", "Budgie"),
+    ("cosmic-session", "COSMIC"),
+    ("enlightenment", "Enlightenment"),
+    ("deepin-session", "Deepin"),
+];
+
+/// Window managers and compositors by their process name.
+const WINDOW_MANAGERS: &[(&str, &str)] = &[
+    ("kwin_wayland", "KWin"),
+    ("kwin_x11", "KWin"),
+    ("kwin", "KWin"),
+    ("mutter", "Mutter"),
+    ("gnome-shell", "Mutter"),
+    ("xfwm4", "Xfwm4"),
+    ("muffin", "Muffin"),
+    ("marco", "Marco"),
+    ("openbox", "Openbox"),
+    ("i3", "i3"),
+    ("sway", "Sway"),
+    ("Hyprland", "Hyprland"),
+    ("bspwm", "bspwm"),
+    ("awesome", "awesome"),
+    ("dwm", "dwm"),
+    ("herbstluftwm", "herbstluftwm"),
+    ("fluxbox", "Fluxbox"),
+    ("icewm", "IceWM"),
+    ("qtile", "Qtile"),
+    ("river", "river"),
+    ("wayfire", "Wayfire"),
+    ("niri", "niri"),
+    ("labwc", "labwc"),
+    ("weston", "Weston"),
+    ("xmonad", "xmonad"),
+    ("compiz", "Compiz"),
+    ("metacity", "Metacity"),
+    ("cosmic-comp", "cosmic-comp"),
+];
+
+/// The commands that print the version of each window manager, by its name
+/// in `WINDOW_MANAGERS`. The first one on `PATH` is run with `--version`.
+const VERSION_COMMANDS: &[(&str, &[&str])] = &[
+    ("KWin", &["kwin_wayland", "kwin_x11"]),
+    ("Mutter", &["gnome-shell", "mutter"]),
+    ("Xfwm4", &["xfwm4"]),
+    ("Openbox", &["openbox"]),
+    ("i3", &["i3"]),
+    ("Sway", &["sway"]),
+    ("Hyprland", &["Hyprland"]),
+    ("bspwm", &["bspwm"]),
+    ("awesome", &["awesome"]),
+    ("Fluxbox", &["fluxbox"]),
+    ("IceWM", &["icewm"]),
+    ("Qtile", &["qtile"]),
+    ("Wayfire", &["wayfire"]),
+    ("niri", &["niri"]),
+    ("labwc", &["labwc"]),
+    ("Weston", &["weston"]),
+];
+
+/// Compositors can be slow to print their version when they probe the GPU.
+const VERSION_TIMEOUT: Duration = Duration::from_secs(3);
+
+impl DesktopInfo {
+    pub fn collect() -> Self {
+        if cfg!(target_os = "macos") {
+            return Self {
+                environment: Some("Aqua".into()),
+                window_manager: Some("Quartz Compositor".into()),
+                session_type: None,
+                window_manager_version: None,
+                x_server: None,
+                xwayland: None,
+            };
+        }
+        if cfg!(target_os = "windows") {
+            return Self {
+                environment: Some("Windows Shell".into()),
+                window_manager: Some("Desktop Window Manager".into()),
+                session_type: None,
+                window_manager_version: None,
+                x_server: None,
+                xwayland: None,
+            };
+        }
+
+        let processes = process_names();
+        let running = |table: &[(&str, &str)]| {
+            table
+                .iter()
+                .find(|(process, _)| {
+                    processes
+                        .iter()
+                        .any(|name| name == process || name.starts_with(&format!("{}-", process)))
+                })
+                .map(|(_, name)| name.to_string())
+        };
+
+        let window_manager = window_manager_from_env().or_else(|| running(WINDOW_MANAGERS));
+        let session_type = session_type();
+        let wayland = session_type.as_deref() == Some("wayland");
+
+        Self {
+            environment: environment_from_env().or_else(|| running(ENVIRONMENTS)),
+            window_manager_version: window_manager.as_deref().and_then(window_manager_version),
+            window_manager,
+            x_server: (session_type.as_deref() == Some("x11"))
+                .then(x_server)
+                .flatten(),
+            xwayland: wayland.then(|| processes.iter().any(|name| name == "Xwayland")),
+            session_type,
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Desktop Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        options.line(format_args!(
+            "  Desktop        : {}",
+            self.environment.as_deref().unwrap_or("None")
+        ));
+        let window_manager = match (&self.window_manager, &self.window_manager_version) {
+            (Some(name), Some(version)) => format!("{} {}", name, version),
+            (Some(name), None) => name.clone(),
+            (None, _) => "None".into(),
+        };
+        options.line(format_args!("  Window Manager : {}", window_manager));
+        let mut session = self
+            .session_type
+            .clone()
+            .unwrap_or_else(|| "Unknown".into());
+        if let Some(server) = &self.x_server {
+            session.push_str(&format!(" ({})", server));
+        }
+        match self.xwayland {
+            Some(true) => session.push_str(" (XWayland running)"),
+            Some(false) => session.push_str(" (no XWayland)"),
+            None => {}
+        }
+        options.line(format_args!("  Session        : {}", session));
+    }
+}
+
+fn var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// `XDG_CURRENT_DESKTOP` lists the desktop names most specific first, as in
+/// `ubuntu:GNOME`, so the last one is the environment itself.
+fn environment_from_env() -> Option<String> {
+    let desktop = var("XDG_CURRENT_DESKTOP")
+        .and_then(|desktops| desktops.rsplit(':').next().map(str::to_string))
+        .or_else(|| var("DESKTOP_SESSION"))
+        .or_else(|| var("KDE_FULL_SESSION").map(|_| "KDE".into()))?;
+    Some(match desktop.to_ascii_lowercase().as_str() {
+        "gnome" | "gnome-xorg" | "gnome-classic" => "GNOME".into(),
+        "kde" | "plasma" | "plasmawayland" => "KDE Plasma".into(),
+        "xfce" | "xfce4" => "Xfce".into(),
+        "x-cinnamon" | "cinnamon" => "Cinnamon".into(),
+        "mate" => "MATE".into(),
+        "lxqt" => "LXQt".into(),
+        "lxde" => "LXDE".into(),
+        "budgie" | "budgie-desktop" => "Budgie".into(),
+        "cosmic" => "COSMIC".into(),
+        "pantheon" => "Pantheon".into(),
+        "unity" => "Unity".into(),
+        "deepin" => "Deepin".into(),
+        _ => desktop,
+    })
+}
+
+/// Compositors that announce themselves through the variables they set for
+/// their clients.
+fn window_manager_from_env() -> Option<String> {
+    [
+        ("HYPRLAND_INSTANCE_SIGNATURE", "Hyprland"),
+        ("SWAYSOCK", "Sway"),
+        ("NIRI_SOCKET", "niri"),
+        ("I3SOCK", "i3"),
+    ]
+    .iter()
+    .find(|(variable, _)| var(variable).is_some())
+    .map(|(_, name)| name.to_string())
+}
+
+fn session_type() -> Option<String> {
+    var("XDG_SESSION_TYPE")
+        .or_else(|| var("WAYLAND_DISPLAY").map(|_| "wayland".into()))
+        .or_else(|| var("DISPLAY").map(|_| "x11".into()))
+}
+
+fn window_manager_version(name: &str) -> Option<String> {
+    let (_, commands) = VERSION_COMMANDS
+        .iter()
+        .find(|(window_manager, _)| *window_manager == name)?;
+    let command = commands.iter().find(|command| which(command).is_ok())?;
+    command_output_timeout(command, &["--version"], VERSION_TIMEOUT)
+        .and_then(|output| parse_version(&output))
+}
+
+/// Asks the X server to describe itself; `xdpyinfo` prints lines such as
+/// `vendor string:    The X.Org Foundation` and `X.Org version: 21.1.11`.
+fn x_server() -> Option<String> {
+    let output = command_output_timeout("xdpyinfo", &[], VERSION_TIMEOUT)?;
+    let field = |key: &str| {
+        output.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == key).then(|| value.trim().to_string())
+        })
+    };
+    match field("X.Org version") {
+        Some(version) => Some(format!("X.Org {}", version)),
+        None => field("vendor string"),
+    }
+}
+
+fn process_names() -> Vec<String> {
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::new());
+    sys.processes()
+        .values()
+        .map(|process| process.name().to_string_lossy().into_owned())
+        .collect()
+}
@@ -0,0 +1,96 @@
+use {
+    super::pressure::Pressure,
+    crate::format::DisplayOptions,
+    serde::{Deserialize, Serialize},
+    sysinfo::Disks,
+};
+
+/// Mounted disks and their usage.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiskInfo {
+    pub disks: Vec<DiskEntry>,
+    /// Time tasks waited for I/O, on Linux.
+    pub io_pressure: Option<Pressure>,
+}
+
+/// A single mounted disk.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiskEntry {
+    pub name: String,
+    pub file_system: String,
+    pub mount_point: String,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    pub available_bytes: u64,
+    pub removable: bool,
+}
+
+impl DiskInfo {
+    pub fn collect() -> Self {
+        let disks = Disks::new_with_refreshed_list()
+            .iter()
+            .map(|disk| DiskEntry {
+                name: disk.name().to_string_lossy().into_owned(),
+                file_system: disk.file_system().to_string_lossy().into_owned(),
+                mount_point: disk.mount_point().display().to_string(),
+                total_bytes: disk.total_space(),
+                used_bytes: disk.total_space().saturating_sub(disk.available_space()),
+                available_bytes: disk.available_space(),
+                removable: disk.is_removable(),
+            })
+            .collect();
+
+        Self {
+            disks,
+            io_pressure: Pressure::read("io"),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Disk Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.disks.is_empty() {
+            options.line("  Disks: None");
+        }
+
+        for disk in &self.disks {
+            options.line(format_args!(
+                "  {}{}{}",
+                "-".repeat(10),
+                disk.name,
+                "-".repeat(10)
+            ));
+            options.line(format_args!("    Mount Point : {}", disk.mount_point));
+            options.line(format_args!("    File System : {}", disk.file_system));
+            options.line(format_args!(
+                "    Total       : {}",
+                options.bytes(disk.total_bytes)
+            ));
+            options.line(format_args!(
+                "    Used        : {}",
+                options.warn_if(
+                    disk.used_bytes as f64 > disk.total_bytes as f64 * 0.9,
+                    options.bytes(disk.used_bytes)
+                )
+            ));
+            options.line(format_args!(
+                "    Available   : {}",
+                options.bytes(disk.available_bytes)
+            ));
+            options.line(format_args!(
+                "    Removable   : {}",
+                if disk.removable { "Yes" } else { "No" }
+            ));
+        }
+        if let Some(pressure) = &self.io_pressure {
+            options.line(format_args!(
+                "  I/O Stalls : {} (last 10s)",
+                pressure.summary()
+            ));
+        }
+    }
+}
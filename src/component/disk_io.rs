@@ -0,0 +1,196 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_objects, json_string},
+    },
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeMap, path::Path, thread, time::Duration},
+};
+
+/// Storage throughput measured over a sampling window, reported on Linux
+/// and Windows.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiskIoInfo {
+    /// How long the disks were watched for.
+    pub interval_ms: u64,
+    pub disks: Vec<DiskIoEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiskIoEntry {
+    pub name: String,
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+    pub reads_per_sec: f64,
+    pub writes_per_sec: f64,
+}
+
+/// `/proc/diskstats` counts in 512-byte sectors whatever the device uses.
+const SECTOR_BYTES: f64 = 512.0;
+
+/// The counters of one disk in `/proc/diskstats`.
+#[derive(Clone, Copy)]
+struct DiskCounters {
+    reads: u64,
+    sectors_read: u64,
+    writes: u64,
+    sectors_written: u64,
+}
+
+impl DiskIoInfo {
+    /// Samples every disk for `interval`, blocking meanwhile.
+    pub fn collect(interval: Duration) -> Self {
+        let disks = if cfg!(target_os = "windows") {
+            windows_disks(interval)
+        } else {
+            proc_disks(interval)
+        };
+
+        Self {
+            interval_ms: interval.as_millis() as u64,
+            disks,
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Disk I/O Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.disks.is_empty() {
+            options.line("  Disks: None");
+            return;
+        }
+
+        options.line(format_args!("  Sampled Over : {} ms", self.interval_ms));
+        let width = self
+            .disks
+            .iter()
+            .map(|disk| disk.name.len())
+            .max()
+            .unwrap_or(0);
+        for disk in &self.disks {
+            options.line(format_args!(
+                "  {:<width$} : read {}/s ({:.0} IOPS), write {}/s ({:.0} IOPS)",
+                disk.name,
+                options.bytes(disk.read_bytes_per_sec as u64),
+                disk.reads_per_sec,
+                options.bytes(disk.write_bytes_per_sec as u64),
+                disk.writes_per_sec,
+                width = width
+            ));
+        }
+    }
+}
+
+/// Reads `/proc/diskstats` before and after `interval`. Partitions, loop
+/// devices and RAM disks are left out.
+fn proc_disks(interval: Duration) -> Vec<DiskIoEntry> {
+    let Some(before) = diskstats() else {
+        return Vec::new();
+    };
+    thread::sleep(interval);
+    let Some(after) = diskstats() else {
+        return Vec::new();
+    };
+
+    let seconds = interval.as_secs_f64().max(f64::EPSILON);
+    let rate = |before: u64, after: u64| after.saturating_sub(before) as f64 / seconds;
+    after
+        .iter()
+        .filter_map(|(name, after)| {
+            let before = before.get(name)?;
+            Some(DiskIoEntry {
+                name: name.clone(),
+                read_bytes_per_sec: rate(before.sectors_read, after.sectors_read) * SECTOR_BYTES,
+                write_bytes_per_sec: rate(before.sectors_written, after.sectors_written)
+                    * SECTOR_BYTES,
+                reads_per_sec: rate(before.reads, after.reads),
+                writes_per_sec: rate(before.writes, after.writes),
+            })
+        })
+        .collect()
+}
+
+/// Parses the rows of whole disks, which read `259 0 nvme0n1 81543 ...`:
+/// reads, merged reads, sectors read and time reading, then the same for
+/// writes.
+fn diskstats() -> Option<BTreeMap<String, DiskCounters>> {
+    let stats = std::fs::read_to_string("/proc/diskstats").ok()?;
+    Some(
+        stats
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let name = *fields.get(2)?;
+                // Only whole disks have an entry in `/sys/block`.
+                if name.starts_with("loop")
+                    || name.starts_with("ram")
+                    || !Path::new("/sys/block").join(name).exists()
+                {
+                    return None;
+                }
+                let number = |index: usize| fields.get(index)?.parse::<u64>().ok();
+                Some((
+                    name.to_string(),
+                    DiskCounters {
+                        reads: number(3)?,
+                        sectors_read: number(5)?,
+                        writes: number(7)?,
+                        sectors_written: number(9)?,
+                    },
+                ))
+            })
+            .collect(),
+    )
+}
+
+/// Samples the physical disk performance counters, whose paths read
+/// `\\host\physicaldisk(0 c:)\disk read bytes/sec`. `Get-Counter` takes
+/// whole seconds.
+fn windows_disks(interval: Duration) -> Vec<DiskIoEntry> {
+    let seconds = interval.as_secs_f64().ceil().max(1.0) as u64;
+    let command = format!(
+        "(Get-Counter -SampleInterval {} -MaxSamples 2 -Counter \
+         '\\PhysicalDisk(*)\\Disk Read Bytes/sec','\\PhysicalDisk(*)\\Disk Write Bytes/sec',\
+         '\\PhysicalDisk(*)\\Disk Reads/sec','\\PhysicalDisk(*)\\Disk Writes/sec' | \
+         Select-Object -Last 1).CounterSamples | \
+         Select-Object Path,InstanceName,CookedValue | ConvertTo-Json",
+        seconds
+    );
+    let Some(output) = command_output("powershell", &["-NoProfile", "-Command", &command]) else {
+        return Vec::new();
+    };
+
+    let mut disks: BTreeMap<String, DiskIoEntry> = BTreeMap::new();
+    for sample in json_objects(&output) {
+        let (Some(path), Some(name), Some(value)) = (
+            json_string(&sample, "Path"),
+            json_string(&sample, "InstanceName"),
+            sample["CookedValue"].as_f64(),
+        ) else {
+            continue;
+        };
+        if name == "_total" {
+            continue;
+        }
+        let disk = disks.entry(name.clone()).or_insert_with(|| DiskIoEntry {
+            name,
+            read_bytes_per_sec: 0.0,
+            write_bytes_per_sec: 0.0,
+            reads_per_sec: 0.0,
+            writes_per_sec: 0.0,
+        });
+        let counter = path.rsplit('\\').next().unwrap_or_default();
+        match counter {
+            "disk read bytes/sec" => disk.read_bytes_per_sec = value,
+            "disk write bytes/sec" => disk.write_bytes_per_sec = value,
+            "disk reads/sec" => disk.reads_per_sec = value,
+            "disk writes/sec" => disk.writes_per_sec = value,
+            _ => {}
+        }
+    }
+    disks.into_values().collect()
+}
@@ -0,0 +1,295 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_objects, json_objects_in, json_string, read_trimmed},
+    },
+    serde::{Deserialize, Serialize},
+    std::env,
+};
+
+/// The connected monitors and the modes they run at.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DisplayInfo {
+    pub monitors: Vec<MonitorEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MonitorEntry {
+    /// The connector or display name, such as `HDMI-A-1`.
+    pub name: String,
+    pub primary: bool,
+    pub width: u32,
+    pub height: u32,
+    pub refresh_hz: Option<f64>,
+    /// How many physical pixels make up a logical one, as in `2` on a
+    /// Retina display.
+    pub scale: Option<f64>,
+}
+
+impl DisplayInfo {
+    pub fn collect() -> Self {
+        let monitors = if cfg!(target_os = "macos") {
+            macos_monitors()
+        } else if cfg!(target_os = "windows") {
+            windows_monitors()
+        } else {
+            // Compositors know their scaling factors, XWayland does not.
+            let wayland = if env::var_os("WAYLAND_DISPLAY").is_some() {
+                hyprland_monitors().or_else(wlr_monitors)
+            } else {
+                None
+            };
+            wayland
+                .or_else(xrandr_monitors)
+                .unwrap_or_else(drm_monitors)
+        };
+
+        Self { monitors }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Display Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.monitors.is_empty() {
+            options.line("  Monitors: None");
+            return;
+        }
+
+        for monitor in &self.monitors {
+            let mut mode = format!("{}x{}", monitor.width, monitor.height);
+            if let Some(refresh) = monitor.refresh_hz {
+                mode.push_str(&format!(" @ {:.2} Hz", refresh));
+            }
+            if let Some(scale) = monitor.scale {
+                mode.push_str(&format!(", scale {}", scale));
+            }
+            if monitor.primary {
+                mode.push_str(" (primary)");
+            }
+            options.line(format_args!("  {} : {}", monitor.name, mode));
+        }
+    }
+}
+
+/// Parses `xrandr --query`, where each connected output is followed by its
+/// modes and the current one is marked with `*`:
+///
+/// ```text
+/// DP-1 connected primary 2560x1440+0+0 (normal left inverted) 597mm x 336mm
+///    2560x1440     59.95*+ 143.97
+/// ```
+fn xrandr_monitors() -> Option<Vec<MonitorEntry>> {
+    env::var_os("DISPLAY")?;
+    let output = command_output("xrandr", &["--query"])?;
+
+    let mut monitors: Vec<MonitorEntry> = Vec::new();
+    let mut current = None;
+    for line in output.lines() {
+        if !line.starts_with(' ') {
+            current = None;
+            let mut fields = line.split_whitespace();
+            let (Some(name), Some("connected")) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let primary = line.contains(" connected primary ");
+            // Outputs that are connected but switched off have no geometry.
+            let Some((width, height)) = fields.find_map(|field| {
+                let (size, _) = field.split_once('+')?;
+                parse_resolution(size)
+            }) else {
+                continue;
+            };
+            current = Some(monitors.len());
+            monitors.push(MonitorEntry {
+                name: name.to_string(),
+                primary,
+                width,
+                height,
+                refresh_hz: None,
+                scale: None,
+            });
+        } else if let Some(index) = current {
+            let refresh = line
+                .split_whitespace()
+                .skip(1)
+                .find(|rate| rate.contains('*'))
+                .and_then(|rate| rate.trim_end_matches(['*', '+']).parse().ok());
+            if refresh.is_some() {
+                monitors[index].refresh_hz = refresh;
+            }
+        }
+    }
+
+    Some(monitors)
+}
+
+/// Parses `hyprctl monitors -j`.
+fn hyprland_monitors() -> Option<Vec<MonitorEntry>> {
+    env::var_os("HYPRLAND_INSTANCE_SIGNATURE")?;
+    let output = command_output("hyprctl", &["monitors", "-j"])?;
+
+    Some(
+        json_objects(&output)
+            .iter()
+            .filter_map(|monitor| {
+                Some(MonitorEntry {
+                    name: json_string(monitor, "name")?,
+                    primary: monitor["focused"].as_bool().unwrap_or(false),
+                    width: monitor["width"].as_u64()? as u32,
+                    height: monitor["height"].as_u64()? as u32,
+                    refresh_hz: monitor["refreshRate"].as_f64(),
+                    scale: monitor["scale"].as_f64(),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Parses `wlr-randr --json`, which wlroots compositors such as Sway answer.
+fn wlr_monitors() -> Option<Vec<MonitorEntry>> {
+    let output = command_output("wlr-randr", &["--json"])?;
+
+    Some(
+        json_objects(&output)
+            .iter()
+            .filter(|output| output["enabled"].as_bool().unwrap_or(false))
+            .filter_map(|output| {
+                let mode = json_objects_in(output, "modes")
+                    .into_iter()
+                    .find(|mode| mode["current"].as_bool().unwrap_or(false))?;
+                Some(MonitorEntry {
+                    name: json_string(output, "name")?,
+                    primary: false,
+                    width: mode["width"].as_u64()? as u32,
+                    height: mode["height"].as_u64()? as u32,
+                    refresh_hz: mode["refresh"].as_f64(),
+                    scale: output["scale"].as_f64(),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Lists the connected DRM connectors with their preferred mode, for when
+/// no display server answers.
+fn drm_monitors() -> Vec<MonitorEntry> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/drm") else {
+        return Vec::new();
+    };
+
+    let mut monitors: Vec<MonitorEntry> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let connector = entry.path();
+            if read_trimmed(connector.join("status"))? != "connected" {
+                return None;
+            }
+            // Connectors read `card0-HDMI-A-1`; the first mode is preferred.
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let modes = read_trimmed(connector.join("modes"))?;
+            let (width, height) = parse_resolution(modes.lines().next()?)?;
+            Some(MonitorEntry {
+                name: name
+                    .split_once('-')
+                    .map_or(name.as_str(), |(_, name)| name)
+                    .to_string(),
+                primary: false,
+                width,
+                height,
+                refresh_hz: None,
+                scale: None,
+            })
+        })
+        .collect();
+    monitors.sort_by(|a, b| a.name.cmp(&b.name));
+    monitors
+}
+
+/// Reads `system_profiler SPDisplaysDataType`, whose `_spdisplays_pixels`
+/// is the panel resolution and `_spdisplays_resolution` the logical one, as
+/// in `1512 x 982 @ 120.00Hz`.
+fn macos_monitors() -> Vec<MonitorEntry> {
+    let Some(output) = command_output("system_profiler", &["SPDisplaysDataType", "-json"]) else {
+        return Vec::new();
+    };
+    let Ok(value) = output.parse::<serde_json::Value>() else {
+        return Vec::new();
+    };
+
+    json_objects_in(&value, "SPDisplaysDataType")
+        .into_iter()
+        .flat_map(|gpu| json_objects_in(gpu, "spdisplays_ndrvs"))
+        .filter_map(|display| {
+            let resolution = json_string(display, "_spdisplays_resolution");
+            let (logical, refresh) = match resolution.as_deref().map(|r| r.split_once(" @ ")) {
+                Some(Some((logical, refresh))) => (Some(logical), Some(refresh)),
+                Some(None) => (resolution.as_deref(), None),
+                None => (None, None),
+            };
+            let logical = logical.and_then(parse_resolution);
+            let (width, height) = json_string(display, "_spdisplays_pixels")
+                .and_then(|pixels| parse_resolution(&pixels))
+                .or(logical)?;
+
+            Some(MonitorEntry {
+                name: json_string(display, "_name")?,
+                primary: json_string(display, "spdisplays_main").as_deref()
+                    == Some("spdisplays_yes"),
+                width,
+                height,
+                refresh_hz: refresh.and_then(|refresh| refresh.trim_end_matches("Hz").parse().ok()),
+                scale: logical
+                    .filter(|&(logical, _)| logical > 0)
+                    .map(|(logical, _)| f64::from(width) / f64::from(logical)),
+            })
+        })
+        .collect()
+}
+
+/// Asks the video controllers for their current mode, and the registry for
+/// the DPI applied to the desktop.
+fn windows_monitors() -> Vec<MonitorEntry> {
+    let Some(output) = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "$dpi = (Get-ItemProperty 'HKCU:\\Control Panel\\Desktop\\WindowMetrics' \
+             -ErrorAction SilentlyContinue).AppliedDPI; \
+             Get-CimInstance Win32_VideoController | \
+             Where-Object { $_.CurrentHorizontalResolution } | \
+             Select-Object Name,CurrentHorizontalResolution,CurrentVerticalResolution,\
+             CurrentRefreshRate,@{n='AppliedDPI';e={$dpi}} | \
+             ConvertTo-Json",
+        ],
+    ) else {
+        return Vec::new();
+    };
+
+    json_objects(&output)
+        .iter()
+        .enumerate()
+        .filter_map(|(index, controller)| {
+            Some(MonitorEntry {
+                name: json_string(controller, "Name")?,
+                primary: index == 0,
+                width: controller["CurrentHorizontalResolution"].as_u64()? as u32,
+                height: controller["CurrentVerticalResolution"].as_u64()? as u32,
+                refresh_hz: controller["CurrentRefreshRate"].as_f64(),
+                scale: controller["AppliedDPI"].as_f64().map(|dpi| dpi / 96.0),
+            })
+        })
+        .collect()
+}
+
+/// Parses `1920x1080`, `1920 x 1080` or the interlaced `1920x1080i`.
+fn parse_resolution(text: &str) -> Option<(u32, u32)> {
+    let (width, height) = text.split_once('x')?;
+    let height = height.split_whitespace().next()?;
+    let height = height.trim_end_matches(|c: char| !c.is_ascii_digit());
+    Some((width.trim().parse().ok()?, height.parse().ok()?))
+}
@@ -0,0 +1,198 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_objects},
+    },
+    serde::{Deserialize, Serialize},
+    std::{net::IpAddr, path::Path},
+    sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System},
+};
+
+/// Where the machine sends its DNS queries.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DnsInfo {
+    /// The name servers the system resolver is configured with.
+    pub resolvers: Vec<String>,
+    pub search_domains: Vec<String>,
+    /// The local caching resolver the name servers point at, such as
+    /// `systemd-resolved` or `dnsmasq`.
+    pub stub: Option<String>,
+    /// The servers `systemd-resolved` forwards to, when it is the stub.
+    pub upstream_resolvers: Vec<String>,
+}
+
+/// Local resolvers by their process name.
+const STUBS: &[&str] = &[
+    "systemd-resolved",
+    "dnsmasq",
+    "unbound",
+    "named",
+    "dnscrypt-proxy",
+    "stubby",
+    "coredns",
+];
+
+impl DnsInfo {
+    pub fn collect() -> Self {
+        let (resolvers, search_domains) = if cfg!(target_os = "macos") {
+            scutil_config()
+        } else if cfg!(target_os = "windows") {
+            windows_config()
+        } else {
+            std::fs::read_to_string("/etc/resolv.conf")
+                .map(|conf| parse_resolv_conf(&conf))
+                .unwrap_or_default()
+        };
+
+        let stub = local_stub(&resolvers);
+        // The stub's own file lists the servers it forwards to.
+        let upstream_resolvers = match stub.as_deref() {
+            Some("systemd-resolved") => std::fs::read_to_string("/run/systemd/resolve/resolv.conf")
+                .map(|conf| parse_resolv_conf(&conf).0)
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+
+        Self {
+            resolvers,
+            search_domains,
+            stub,
+            upstream_resolvers,
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}DNS Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        let list = |values: &[String]| {
+            if values.is_empty() {
+                "None".to_string()
+            } else {
+                values.join(", ")
+            }
+        };
+        // Rows are printed as is: IPv6 resolvers contain `:`.
+        println!("  Resolvers      : {}", list(&self.resolvers));
+        options.line(format_args!(
+            "  Search Domains : {}",
+            list(&self.search_domains)
+        ));
+        options.line(format_args!(
+            "  Local Stub     : {}",
+            self.stub.as_deref().unwrap_or("None")
+        ));
+        if self.stub.is_some() && !self.upstream_resolvers.is_empty() {
+            println!("  Upstream       : {}", list(&self.upstream_resolvers));
+        }
+    }
+}
+
+/// Reads the `nameserver`, `search` and `domain` lines of `resolv.conf`.
+fn parse_resolv_conf(conf: &str) -> (Vec<String>, Vec<String>) {
+    let mut resolvers = Vec::new();
+    let mut search_domains = Vec::new();
+    for line in conf.lines() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("nameserver") => resolvers.extend(fields.next().map(str::to_string)),
+            // The last `search` or `domain` line wins.
+            Some("search" | "domain") => {
+                search_domains = fields
+                    .take_while(|field| !field.starts_with(['#', ';']))
+                    .map(str::to_string)
+                    .collect()
+            }
+            _ => {}
+        }
+    }
+    (resolvers, search_domains)
+}
+
+/// Reads the first resolver of `scutil --dns`, whose lines read
+/// `nameserver[0] : 192.168.1.1` and `search domain[0] : lan`.
+fn scutil_config() -> (Vec<String>, Vec<String>) {
+    let Some(output) = command_output("scutil", &["--dns"]) else {
+        return Default::default();
+    };
+
+    let mut resolvers = Vec::new();
+    let mut search_domains = Vec::new();
+    let first = output
+        .split("resolver #")
+        .nth(1)
+        .unwrap_or_default()
+        .lines();
+    for line in first {
+        let Some((key, value)) = line.split_once(" : ") else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim().to_string());
+        if key.starts_with("nameserver[") {
+            resolvers.push(value);
+        } else if key.starts_with("search domain[") {
+            search_domains.push(value);
+        }
+    }
+    (resolvers, search_domains)
+}
+
+fn windows_config() -> (Vec<String>, Vec<String>) {
+    // `-InputObject @(...)` keeps a single address a list.
+    let list = |values: &str| {
+        let command = format!("ConvertTo-Json -InputObject @({})", values);
+        command_output("powershell", &["-NoProfile", "-Command", &command])
+            .map(|output| {
+                json_objects(&output)
+                    .iter()
+                    .filter_map(|value| value.as_str().map(str::to_string))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+
+    // Every adapter lists its servers; keep the first mention of each.
+    let mut resolvers: Vec<String> = Vec::new();
+    for resolver in list("Get-DnsClientServerAddress | ForEach-Object ServerAddresses") {
+        if !resolvers.contains(&resolver) {
+            resolvers.push(resolver);
+        }
+    }
+    let search_domains = list("(Get-DnsClientGlobalSetting).SuffixSearchList");
+    (resolvers, search_domains)
+}
+
+/// Finds the caching resolver the loopback name servers point at.
+fn local_stub(resolvers: &[String]) -> Option<String> {
+    let loopback = resolvers.iter().any(|resolver| {
+        resolver
+            .parse::<IpAddr>()
+            .is_ok_and(|address| address.is_loopback())
+    });
+    if !loopback {
+        return None;
+    }
+    // systemd-resolved listens on 127.0.0.53 and manages resolv.conf.
+    if resolvers.iter().any(|resolver| resolver == "127.0.0.53")
+        || Path::new("/run/systemd/resolve/stub-resolv.conf").exists()
+            && std::fs::read_link("/etc/resolv.conf")
+                .is_ok_and(|target| target.to_string_lossy().contains("systemd"))
+    {
+        return Some("systemd-resolved".into());
+    }
+
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::new());
+    STUBS
+        .iter()
+        .find(|stub| {
+            system
+                .processes()
+                .values()
+                .any(|process| process.name().to_string_lossy() == **stub)
+        })
+        .map(|stub| stub.to_string())
+}
@@ -0,0 +1,253 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_objects, json_string, read_trimmed},
+    },
+    serde::{Deserialize, Serialize},
+    std::{env::consts::ARCH, fs, path::Path},
+};
+
+/// How the machine booted: through UEFI or a legacy BIOS, whether Secure
+/// Boot checked the boot chain, and the TPM that can measure it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FirmwareInfo {
+    /// `UEFI`, `BIOS`, or `iBoot` on Apple Silicon.
+    pub boot_mode: Option<String>,
+    /// Whether Secure Boot is enforcing; `None` where it cannot be read.
+    pub secure_boot: Option<bool>,
+    /// Whether the firmware is in setup mode, accepting new Secure Boot keys
+    /// without them being signed.
+    pub setup_mode: Option<bool>,
+    /// `None` when no TPM was found.
+    pub tpm: Option<TpmInfo>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TpmInfo {
+    /// `1.2` or `2.0`.
+    pub version: Option<String>,
+    pub manufacturer: Option<String>,
+    /// Whether the TPM is enabled and activated, as TPM 1.2 can be switched
+    /// off in the firmware while still present.
+    pub enabled: Option<bool>,
+}
+
+/// The EFI global variable GUID, which `SecureBoot` and `SetupMode` live
+/// under.
+const EFI_GLOBAL_VARIABLE: &str = "8be4df61-93ca-11d2-aa0d-00e098032b8c";
+
+impl FirmwareInfo {
+    pub fn collect() -> Self {
+        let mut firmware = if cfg!(target_os = "linux") {
+            efivars()
+        } else if cfg!(target_os = "windows") {
+            windows_firmware()
+        } else if cfg!(target_os = "macos") {
+            Self {
+                boot_mode: Some(if ARCH == "aarch64" { "iBoot" } else { "UEFI" }.into()),
+                secure_boot: None,
+                setup_mode: None,
+                tpm: None,
+            }
+        } else {
+            // FreeBSD names the loader's path in `machdep.bootmethod`.
+            Self {
+                boot_mode: command_output("sysctl", &["-n", "machdep.bootmethod"]),
+                secure_boot: None,
+                setup_mode: None,
+                tpm: None,
+            }
+        };
+        firmware.tpm = if cfg!(target_os = "linux") {
+            sysfs_tpm()
+        } else if cfg!(target_os = "windows") {
+            windows_tpm()
+        } else {
+            None
+        };
+        firmware
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Firmware Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        options.line(format_args!(
+            "  Boot Mode   : {}",
+            self.boot_mode.as_deref().unwrap_or("Unknown")
+        ));
+        let state = |enabled: Option<bool>| match enabled {
+            Some(true) => "Enabled",
+            Some(false) => "Disabled",
+            None => "Unknown",
+        };
+        options.line(format_args!("  Secure Boot : {}", state(self.secure_boot)));
+        if self.setup_mode.is_some() {
+            options.line(options.warn_if(
+                self.setup_mode == Some(true),
+                format!("  Setup Mode  : {}", state(self.setup_mode)),
+            ));
+        }
+        let tpm = self.tpm.as_ref().map(|tpm| {
+            let mut text = format!(
+                "TPM {}",
+                tpm.version.as_deref().unwrap_or("of unknown version")
+            );
+            if let Some(manufacturer) = &tpm.manufacturer {
+                text.push_str(&format!(" by {}", manufacturer));
+            }
+            if tpm.enabled == Some(false) {
+                text.push_str(", disabled");
+            }
+            text
+        });
+        options.line(format_args!(
+            "  TPM         : {}",
+            tpm.as_deref().unwrap_or("None")
+        ));
+    }
+}
+
+/// The kernel only mounts `/sys/firmware/efi` when booted through UEFI. Each
+/// variable under `efivars` starts with four bytes of attributes, then the
+/// value.
+fn efivars() -> FirmwareInfo {
+    let efi = Path::new("/sys/firmware/efi");
+    if !efi.exists() {
+        return FirmwareInfo {
+            boot_mode: Some("BIOS".into()),
+            secure_boot: Some(false),
+            setup_mode: None,
+            tpm: None,
+        };
+    }
+    let variable = |name: &str| {
+        let path = efi
+            .join("efivars")
+            .join(format!("{}-{}", name, EFI_GLOBAL_VARIABLE));
+        fs::read(path).ok()?.get(4).map(|value| *value == 1)
+    };
+
+    FirmwareInfo {
+        boot_mode: Some("UEFI".into()),
+        // Without the variable the firmware has no Secure Boot at all.
+        secure_boot: Some(variable("SecureBoot").unwrap_or(false)),
+        setup_mode: variable("SetupMode"),
+        tpm: None,
+    }
+}
+
+/// `$env:firmware_type` is set by the boot manager. `Confirm-SecureBootUEFI`
+/// needs an elevated prompt and fails on legacy machines.
+fn windows_firmware() -> FirmwareInfo {
+    let output = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "$env:firmware_type; try { Confirm-SecureBootUEFI } catch { 'Unknown' }",
+        ],
+    )
+    .unwrap_or_default();
+    let mut lines = output.lines().map(str::trim);
+    let boot_mode = lines.next().filter(|mode| !mode.is_empty()).map(|mode| {
+        match mode {
+            "Legacy" => "BIOS",
+            mode => mode,
+        }
+        .to_string()
+    });
+    let secure_boot = match lines.next() {
+        Some("True") => Some(true),
+        Some("False") => Some(false),
+        _ => None,
+    };
+
+    FirmwareInfo {
+        secure_boot: secure_boot.or((boot_mode.as_deref() == Some("BIOS")).then_some(false)),
+        boot_mode,
+        setup_mode: None,
+        tpm: None,
+    }
+}
+
+/// Reads `/sys/class/tpm/tpm0`. Kernels since 5.6 name the major version;
+/// older ones only show the `caps` of TPM 1.2 devices.
+fn sysfs_tpm() -> Option<TpmInfo> {
+    let tpm = Path::new("/sys/class/tpm/tpm0");
+    if !tpm.exists() {
+        return None;
+    }
+    let caps = read_trimmed(tpm.join("device/caps")).unwrap_or_default();
+    // `caps` reads `Manufacturer: 0x49465800` and `TCG version: 1.2`.
+    let cap = |key: &str| {
+        caps.lines()
+            .find_map(|line| line.strip_prefix(key))
+            .map(|value| value.trim().to_string())
+    };
+    let version = match read_trimmed(tpm.join("tpm_version_major")).as_deref() {
+        Some("2") => Some("2.0".into()),
+        Some("1") => Some("1.2".into()),
+        // TPM 2.0 drivers have no `caps` file.
+        _ if caps.is_empty() => Some("2.0".into()),
+        _ => cap("TCG version:"),
+    };
+    let manufacturer = cap("Manufacturer:")
+        .and_then(|id| u32::from_str_radix(id.trim_start_matches("0x"), 16).ok())
+        .map(vendor_name);
+    // Only TPM 1.2 drivers tell whether the chip is switched on.
+    let flag = |file: &str| read_trimmed(tpm.join("device").join(file)).map(|value| value == "1");
+    let enabled = match (flag("enabled"), flag("active")) {
+        (Some(enabled), Some(active)) => Some(enabled && active),
+        (Some(enabled), None) => Some(enabled),
+        _ => (version.as_deref() == Some("2.0")).then_some(true),
+    };
+
+    Some(TpmInfo {
+        version,
+        manufacturer,
+        enabled,
+    })
+}
+
+/// TPM vendor IDs are four ASCII letters, such as `IFX` for Infineon.
+fn vendor_name(id: u32) -> String {
+    let name: String = id
+        .to_be_bytes()
+        .iter()
+        .filter(|byte| byte.is_ascii_alphanumeric())
+        .map(|byte| *byte as char)
+        .collect();
+    if name.is_empty() {
+        format!("{:#010x}", id)
+    } else {
+        name
+    }
+}
+
+/// `Win32_Tpm` needs an elevated prompt; its `SpecVersion` reads
+/// `2.0, 0, 1.38`.
+fn windows_tpm() -> Option<TpmInfo> {
+    let output = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance -Namespace root/cimv2/Security/MicrosoftTpm -ClassName Win32_Tpm | \
+             Select-Object SpecVersion,ManufacturerIdTxt,IsEnabled_InitialValue,\
+             IsActivated_InitialValue | ConvertTo-Json",
+        ],
+    )?;
+    let tpm = json_objects(&output).into_iter().next()?;
+    let enabled = tpm["IsEnabled_InitialValue"].as_bool();
+    let activated = tpm["IsActivated_InitialValue"].as_bool();
+
+    Some(TpmInfo {
+        version: json_string(&tpm, "SpecVersion")
+            .and_then(|version| version.split(',').next().map(str::to_string)),
+        manufacturer: json_string(&tpm, "ManufacturerIdTxt").map(|name| name.trim().to_string()),
+        enabled: enabled.map(|enabled| enabled && activated.unwrap_or(true)),
+    })
+}
@@ -0,0 +1,273 @@
+use {
+    crate::format::DisplayOptions,
+    crate::ids::IdDatabase,
+    crate::util::{
+        command_output, json_objects, json_objects_in, json_string, parse_size, read_hex_id,
+        read_trimmed,
+    },
+    serde::{Deserialize, Serialize},
+    std::path::Path,
+};
+
+/// Graphics adapters.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GpuInfo {
+    pub gpus: Vec<GpuEntry>,
+}
+
+/// A single graphics adapter. Fields the platform does not expose are `None`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GpuEntry {
+    pub vendor: String,
+    pub model: String,
+    pub driver: Option<String>,
+    pub driver_version: Option<String>,
+    pub vram_total_bytes: Option<u64>,
+    pub vram_used_bytes: Option<u64>,
+    /// The newest CUDA version the NVIDIA driver supports.
+    pub cuda_version: Option<String>,
+}
+
+impl GpuInfo {
+    pub fn collect() -> Self {
+        let gpus = if cfg!(target_os = "linux") {
+            let nvidia = collect_nvidia_gpus();
+            let mut gpus = collect_drm_gpus(!nvidia.is_empty());
+            gpus.extend(nvidia);
+            gpus
+        } else if cfg!(target_os = "windows") {
+            collect_windows_gpus()
+        } else if cfg!(target_os = "macos") {
+            collect_macos_gpus()
+        } else {
+            collect_nvidia_gpus()
+        };
+
+        Self { gpus }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}GPU Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.gpus.is_empty() {
+            options.line("  GPUs: None");
+        }
+
+        for (index, gpu) in self.gpus.iter().enumerate() {
+            let driver = match (&gpu.driver, &gpu.driver_version) {
+                (Some(driver), Some(version)) => format!("{} {}", driver, version),
+                (Some(driver), None) => driver.clone(),
+                (None, Some(version)) => version.clone(),
+                (None, None) => "Unknown".into(),
+            };
+            let driver = match &gpu.cuda_version {
+                Some(cuda) => format!("{} (CUDA {})", driver, cuda),
+                None => driver,
+            };
+            let vram = match (gpu.vram_used_bytes, gpu.vram_total_bytes) {
+                (Some(used), Some(total)) => options.usage(used, total),
+                (None, Some(total)) => options.bytes(total),
+                _ => "Unknown".into(),
+            };
+
+            options.line(format_args!(
+                "  {}GPU {}{}",
+                "-".repeat(10),
+                index,
+                "-".repeat(10)
+            ));
+            options.line(format_args!("    Vendor : {}", gpu.vendor));
+            options.line(format_args!("    Model  : {}", gpu.model));
+            options.line(format_args!("    Driver : {}", driver));
+            options.line(format_args!("    VRAM   : {}", vram));
+        }
+    }
+}
+
+const PCI_VENDOR_NVIDIA: u16 = 0x10de;
+
+/// Queries NVIDIA GPUs through `nvidia-smi`, which sits on top of NVML.
+fn collect_nvidia_gpus() -> Vec<GpuEntry> {
+    let Some(output) = command_output(
+        "nvidia-smi",
+        &[
+            "--query-gpu=name,driver_version,memory.total,memory.used",
+            "--format=csv,noheader,nounits",
+        ],
+    ) else {
+        return Vec::new();
+    };
+    let cuda_version = nvidia_cuda_version();
+
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [model, driver_version, total, used] = fields[..] else {
+                return None;
+            };
+            let mebibytes = |value: &str| value.parse::<u64>().ok().map(|value| value << 20);
+
+            Some(GpuEntry {
+                vendor: "NVIDIA".into(),
+                model: model.into(),
+                driver: Some("nvidia".into()),
+                driver_version: Some(driver_version.into()),
+                vram_total_bytes: mebibytes(total),
+                vram_used_bytes: mebibytes(used),
+                cuda_version: cuda_version.clone(),
+            })
+        })
+        .collect()
+}
+
+/// `--query-gpu` has no field for the CUDA version, but the full query
+/// names it in a line such as `CUDA Version  : 12.4`.
+pub(crate) fn nvidia_cuda_version() -> Option<String> {
+    let output = command_output("nvidia-smi", &["-q", "-d", "COMPUTE"])?;
+    output.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "CUDA Version").then(|| value.trim().to_string())
+    })
+}
+
+/// Enumerates `/sys/class/drm/card*` on Linux. NVIDIA cards are skipped when
+/// `nvidia-smi` already reported them with more detail.
+fn collect_drm_gpus(skip_nvidia: bool) -> Vec<GpuEntry> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/drm") else {
+        return Vec::new();
+    };
+
+    let mut cards: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| {
+            name.strip_prefix("card")
+                .is_some_and(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+        })
+        .collect();
+    cards.sort();
+
+    let pci_ids = IdDatabase::pci();
+
+    cards
+        .iter()
+        .filter_map(|card| {
+            let device = Path::new("/sys/class/drm").join(card).join("device");
+            let vendor_id = read_hex_id(&device.join("vendor"))?;
+            let device_id = read_hex_id(&device.join("device"))?;
+
+            if skip_nvidia && vendor_id == PCI_VENDOR_NVIDIA {
+                return None;
+            }
+
+            let (vendor, model) = pci_ids
+                .as_ref()
+                .map(|ids| ids.names(vendor_id, device_id))
+                .unwrap_or_default();
+            let driver = std::fs::read_link(device.join("driver"))
+                .ok()
+                .and_then(|link| {
+                    link.file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                });
+            let driver_version = driver
+                .as_ref()
+                .and_then(|driver| read_trimmed(format!("/sys/module/{}/version", driver)));
+
+            Some(GpuEntry {
+                vendor: vendor.unwrap_or_else(|| format!("{:#06x}", vendor_id)),
+                model: model.unwrap_or_else(|| format!("{:#06x}", device_id)),
+                driver,
+                driver_version,
+                vram_total_bytes: read_trimmed(device.join("mem_info_vram_total"))
+                    .and_then(|value| value.parse().ok()),
+                vram_used_bytes: read_trimmed(device.join("mem_info_vram_used"))
+                    .and_then(|value| value.parse().ok()),
+                cuda_version: None,
+            })
+        })
+        .collect()
+}
+
+fn collect_windows_gpus() -> Vec<GpuEntry> {
+    let Some(output) = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_VideoController | \
+             Select-Object Name,AdapterCompatibility,DriverVersion,AdapterRAM | \
+             ConvertTo-Json",
+        ],
+    ) else {
+        return Vec::new();
+    };
+
+    let controllers = json_objects(&output);
+    // The counters are per adapter LUID, which the controllers do not name,
+    // so usage is only attributed when there is no doubt.
+    let vram_used_bytes = if controllers.len() == 1 {
+        windows_vram_used()
+    } else {
+        None
+    };
+
+    controllers
+        .iter()
+        .map(|controller| GpuEntry {
+            vendor: json_string(controller, "AdapterCompatibility")
+                .unwrap_or_else(|| "Unknown".into()),
+            model: json_string(controller, "Name").unwrap_or_else(|| "Unknown".into()),
+            driver: None,
+            driver_version: json_string(controller, "DriverVersion"),
+            vram_total_bytes: controller.get("AdapterRAM").and_then(|v| v.as_u64()),
+            vram_used_bytes,
+            cuda_version: None,
+        })
+        .collect()
+}
+
+/// Sums the dedicated memory in use over the `GPU Adapter Memory` counters.
+fn windows_vram_used() -> Option<u64> {
+    let output = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "((Get-Counter '\\GPU Adapter Memory(*)\\Dedicated Usage').CounterSamples | \
+             Measure-Object -Property CookedValue -Sum).Sum",
+        ],
+    )?;
+    output.trim().parse::<f64>().ok().map(|bytes| bytes as u64)
+}
+
+fn collect_macos_gpus() -> Vec<GpuEntry> {
+    let Some(output) = command_output("system_profiler", &["SPDisplaysDataType", "-json"]) else {
+        return Vec::new();
+    };
+    let Ok(value) = output.parse::<serde_json::Value>() else {
+        return Vec::new();
+    };
+
+    json_objects_in(&value, "SPDisplaysDataType")
+        .iter()
+        .map(|display| GpuEntry {
+            vendor: json_string(display, "spdisplays_vendor")
+                .map(|vendor| vendor.trim_start_matches("sppci_vendor_").to_string())
+                .unwrap_or_else(|| "Unknown".into()),
+            model: json_string(display, "sppci_model").unwrap_or_else(|| "Unknown".into()),
+            driver: None,
+            driver_version: None,
+            vram_total_bytes: json_string(display, "spdisplays_vram")
+                .or_else(|| json_string(display, "spdisplays_vram_shared"))
+                .and_then(|vram| parse_size(&vram)),
+            vram_used_bytes: None,
+            cuda_version: None,
+        })
+        .collect()
+}
@@ -0,0 +1,218 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, command_output_timeout, json_objects_in, json_string},
+    },
+    serde::{Deserialize, Serialize},
+    std::time::Duration,
+};
+
+/// The graphics APIs the drivers offer: the Vulkan devices `vulkaninfo`
+/// enumerates, the OpenGL renderer `glxinfo` creates a context on, and the
+/// Metal support of Macs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GraphicsApiInfo {
+    /// The version of the Vulkan loader, `None` without `vulkaninfo`.
+    pub vulkan_instance_version: Option<String>,
+    pub vulkan_devices: Vec<VulkanDevice>,
+    pub opengl: Option<OpenGlInfo>,
+    /// The Metal family of each GPU, such as `spdisplays_metal3`, on macOS.
+    pub metal: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VulkanDevice {
+    pub name: String,
+    /// Such as `integrated GPU`, `discrete GPU` or `CPU` for software
+    /// renderers.
+    pub device_type: Option<String>,
+    pub api_version: Option<String>,
+    pub driver_name: Option<String>,
+    pub driver_info: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OpenGlInfo {
+    pub vendor: Option<String>,
+    pub renderer: Option<String>,
+    /// The version of the compatibility profile, with the driver after it.
+    pub version: Option<String>,
+    pub core_version: Option<String>,
+}
+
+/// Both tools load every driver and may wait on a display server.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl GraphicsApiInfo {
+    pub fn collect() -> Self {
+        let vulkan = command_output_timeout("vulkaninfo", &["--summary"], PROBE_TIMEOUT);
+        let (vulkan_instance_version, vulkan_devices) =
+            vulkan.as_deref().map(parse_vulkaninfo).unwrap_or_default();
+        let opengl = if cfg!(any(target_os = "windows", target_os = "macos")) {
+            None
+        } else {
+            command_output_timeout("glxinfo", &["-B"], PROBE_TIMEOUT)
+                .map(|output| parse_glxinfo(&output))
+        };
+
+        Self {
+            vulkan_instance_version,
+            vulkan_devices,
+            opengl,
+            metal: if cfg!(target_os = "macos") {
+                metal_families()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Graphics API Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        options.line(format_args!(
+            "  Vulkan          : {}",
+            self.vulkan_instance_version.as_deref().unwrap_or("None")
+        ));
+        for device in &self.vulkan_devices {
+            let mut details = Vec::new();
+            if let Some(kind) = &device.device_type {
+                details.push(kind.clone());
+            }
+            if let Some(version) = &device.api_version {
+                details.push(format!("Vulkan {}", version));
+            }
+            match (&device.driver_name, &device.driver_info) {
+                (Some(name), Some(info)) => details.push(format!("{} {}", name, info)),
+                (Some(name), None) => details.push(name.clone()),
+                (None, Some(info)) => details.push(info.clone()),
+                (None, None) => {}
+            }
+            // Rows are printed as is: device names can contain `:`.
+            println!("    {} ({})", device.name, details.join(", "));
+        }
+
+        if let Some(opengl) = &self.opengl {
+            options.line(format_args!(
+                "  OpenGL Renderer : {}",
+                opengl.renderer.as_deref().unwrap_or("Unknown")
+            ));
+            options.line(format_args!(
+                "  OpenGL Version  : {}",
+                opengl
+                    .core_version
+                    .as_deref()
+                    .or(opengl.version.as_deref())
+                    .unwrap_or("Unknown")
+            ));
+        }
+        if !self.metal.is_empty() {
+            options.line(format_args!(
+                "  Metal           : {}",
+                self.metal.join(", ")
+            ));
+        }
+    }
+}
+
+/// Parses the summary of `vulkaninfo`: a line `Vulkan Instance Version:
+/// 1.3.275`, then a `GPU0:` block per device of lines such as
+/// `deviceName = AMD Radeon 780M (RADV GFX1103_R1)`.
+fn parse_vulkaninfo(output: &str) -> (Option<String>, Vec<VulkanDevice>) {
+    let instance = output.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("Vulkan Instance Version:")
+            .map(|version| version.trim().to_string())
+    });
+
+    let mut blocks: Vec<Vec<(&str, &str)>> = Vec::new();
+    for line in output.lines() {
+        let line = line.trim();
+        let header = line
+            .strip_prefix("GPU")
+            .and_then(|rest| rest.strip_suffix(':'))
+            .is_some_and(|index| index.parse::<usize>().is_ok());
+        if header {
+            blocks.push(Vec::new());
+        } else if let (Some(block), Some((key, value))) = (blocks.last_mut(), line.split_once('='))
+        {
+            block.push((key.trim(), value.trim()));
+        }
+    }
+
+    let devices = blocks
+        .iter()
+        .filter_map(|block| {
+            let field = |name: &str| {
+                block
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            };
+            Some(VulkanDevice {
+                name: field("deviceName")?,
+                device_type: field("deviceType").map(|kind| device_type(&kind)),
+                api_version: field("apiVersion"),
+                driver_name: field("driverName"),
+                driver_info: field("driverInfo"),
+            })
+        })
+        .collect();
+    (instance, devices)
+}
+
+/// Turns `PHYSICAL_DEVICE_TYPE_DISCRETE_GPU` into `discrete GPU`.
+fn device_type(kind: &str) -> String {
+    match kind.trim_start_matches("PHYSICAL_DEVICE_TYPE_") {
+        "INTEGRATED_GPU" => "integrated GPU".into(),
+        "DISCRETE_GPU" => "discrete GPU".into(),
+        "VIRTUAL_GPU" => "virtual GPU".into(),
+        "CPU" => "CPU".into(),
+        other => other.to_lowercase(),
+    }
+}
+
+/// Parses `glxinfo -B`, whose lines read `OpenGL renderer string: ...`.
+fn parse_glxinfo(output: &str) -> OpenGlInfo {
+    let field = |name: &str| {
+        output.lines().find_map(|line| {
+            line.trim()
+                .strip_prefix(name)
+                .map(|value| value.trim().to_string())
+        })
+    };
+
+    OpenGlInfo {
+        vendor: field("OpenGL vendor string:"),
+        renderer: field("OpenGL renderer string:"),
+        version: field("OpenGL version string:"),
+        core_version: field("OpenGL core profile version string:"),
+    }
+}
+
+fn metal_families() -> Vec<String> {
+    let Some(output) = command_output("system_profiler", &["SPDisplaysDataType", "-json"]) else {
+        return Vec::new();
+    };
+    let Ok(value) = output.parse::<serde_json::Value>() else {
+        return Vec::new();
+    };
+    json_objects_in(&value, "SPDisplaysDataType")
+        .iter()
+        .filter_map(|display| {
+            json_string(display, "spdisplays_mtlgpufamilysupport")
+                .or_else(|| json_string(display, "spdisplays_metal"))
+        })
+        .map(|family| {
+            family
+                .trim_start_matches("spdisplays_")
+                .replace("metal", "Metal ")
+                .trim()
+                .to_string()
+        })
+        .collect()
+}
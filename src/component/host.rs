@@ -0,0 +1,80 @@
+use {
+    crate::format::DisplayOptions,
+    serde::{Deserialize, Serialize},
+    sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind, Users},
+};
+
+/// The machine's hostname and the user running `envin`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub hostname: String,
+    pub username: String,
+    /// Effective user and group IDs, only reported on Unix.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gid: Option<u32>,
+    pub home: String,
+}
+
+impl HostInfo {
+    pub fn collect() -> Self {
+        let mut sys = System::new();
+        let pid = sysinfo::get_current_pid().ok();
+        if let Some(pid) = pid {
+            sys.refresh_processes_specifics(
+                ProcessesToUpdate::Some(&[pid]),
+                true,
+                ProcessRefreshKind::new().with_user(UpdateKind::Always),
+            );
+        }
+        let process = pid.and_then(|pid| sys.process(pid));
+
+        let users = Users::new_with_refreshed_list();
+        let username = process
+            .and_then(|process| process.effective_user_id().or(process.user_id()))
+            .and_then(|uid| users.get_user_by_id(uid))
+            .map(|user| user.name().to_string())
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .unwrap_or_else(|| "Unknown".into());
+
+        #[cfg(unix)]
+        let (uid, gid) = (
+            process
+                .and_then(|process| process.effective_user_id())
+                .map(|uid| **uid),
+            process
+                .and_then(|process| process.effective_group_id())
+                .map(|gid| *gid),
+        );
+        #[cfg(not(unix))]
+        let (uid, gid) = (None, None);
+
+        let home = std::env::var("HOME")
+            .or_else(|_| std::env::var("USERPROFILE"))
+            .unwrap_or_else(|_| "Unknown".into());
+
+        Self {
+            hostname: System::host_name().unwrap_or_else(|| "Unknown".into()),
+            username,
+            uid,
+            gid,
+            home,
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Host Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        options.line(format_args!("  Hostname : {}", self.hostname));
+        options.line(format_args!("  User     : {}", self.username));
+        if let (Some(uid), Some(gid)) = (self.uid, self.gid) {
+            options.line(format_args!("  UID/GID  : {}/{}", uid, gid));
+        }
+        options.line(format_args!("  Home     : {}", self.home));
+    }
+}
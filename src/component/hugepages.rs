@@ -0,0 +1,136 @@
+use {
+    super::memory::meminfo,
+    crate::{format::DisplayOptions, util::read_trimmed},
+    serde::{Deserialize, Serialize},
+    std::{fs, path::Path},
+};
+
+/// Huge page pools and the transparent huge page policy of the Linux
+/// kernel, as databases and VM hosts tune them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HugepagesInfo {
+    /// One pool per supported page size, such as 2 MiB and 1 GiB.
+    pub pools: Vec<HugepagePool>,
+    pub default_page_size_bytes: Option<u64>,
+    /// The THP mode: `always`, `madvise` or `never`.
+    pub thp_enabled: Option<String>,
+    /// When the kernel compacts memory to make huge pages, such as `defer`.
+    pub thp_defrag: Option<String>,
+    /// Memory currently backed by transparent huge pages.
+    pub thp_anonymous_bytes: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HugepagePool {
+    pub page_size_bytes: u64,
+    pub total: u64,
+    pub free: u64,
+    /// Promised to mappings but not yet faulted in.
+    pub reserved: u64,
+    /// Allocated beyond `total` under overcommit.
+    pub surplus: u64,
+}
+
+const HUGEPAGES: &str = "/sys/kernel/mm/hugepages";
+const TRANSPARENT_HUGEPAGE: &str = "/sys/kernel/mm/transparent_hugepage";
+
+impl HugepagesInfo {
+    pub fn collect() -> Self {
+        let mut pools: Vec<HugepagePool> = fs::read_dir(HUGEPAGES)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter_map(|entry| pool(&entry.path()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        pools.sort_by_key(|pool| pool.page_size_bytes);
+        let meminfo = meminfo();
+        let thp = |file: &str| {
+            read_trimmed(Path::new(TRANSPARENT_HUGEPAGE).join(file))
+                .and_then(|modes| selected(&modes))
+        };
+
+        Self {
+            pools,
+            default_page_size_bytes: meminfo.get("Hugepagesize").copied(),
+            thp_enabled: thp("enabled"),
+            thp_defrag: thp("defrag"),
+            thp_anonymous_bytes: meminfo.get("AnonHugePages").copied(),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Hugepage Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.pools.is_empty() && self.thp_enabled.is_none() {
+            options.line("  Hugepages  : None");
+            return;
+        }
+
+        for pool in &self.pools {
+            let default = if Some(pool.page_size_bytes) == self.default_page_size_bytes {
+                " (default)"
+            } else {
+                ""
+            };
+            options.line(format_args!(
+                "  {:<10} : {} free of {}, {} reserved, {} surplus{}",
+                options.bytes(pool.page_size_bytes),
+                pool.free,
+                pool.total,
+                pool.reserved,
+                pool.surplus,
+                default
+            ));
+        }
+        options.line(format_args!(
+            "  THP        : {}",
+            self.thp_enabled.as_deref().unwrap_or("Unknown")
+        ));
+        options.line(format_args!(
+            "  THP Defrag : {}",
+            self.thp_defrag.as_deref().unwrap_or("Unknown")
+        ));
+        if let Some(bytes) = self.thp_anonymous_bytes {
+            options.line(format_args!("  THP In Use : {}", options.bytes(bytes)));
+        }
+    }
+}
+
+/// Reads a pool directory such as `hugepages-2048kB`.
+fn pool(path: &Path) -> Option<HugepagePool> {
+    let kilobytes: u64 = path
+        .file_name()?
+        .to_str()?
+        .strip_prefix("hugepages-")?
+        .strip_suffix("kB")?
+        .parse()
+        .ok()?;
+    let count = |file: &str| {
+        read_trimmed(path.join(file))
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0)
+    };
+
+    Some(HugepagePool {
+        page_size_bytes: kilobytes << 10,
+        total: count("nr_hugepages"),
+        free: count("free_hugepages"),
+        reserved: count("resv_hugepages"),
+        surplus: count("surplus_hugepages"),
+    })
+}
+
+/// The kernel lists every mode and brackets the active one, as in
+/// `always [madvise] never`.
+fn selected(modes: &str) -> Option<String> {
+    modes
+        .split_whitespace()
+        .find_map(|mode| mode.strip_prefix('[')?.strip_suffix(']'))
+        .map(str::to_string)
+}
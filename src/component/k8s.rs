@@ -0,0 +1,217 @@
+use {
+    crate::{component::cgroup::CgroupInfo, format::DisplayOptions, util::read_trimmed},
+    serde::{Deserialize, Serialize},
+    std::{env, path::Path},
+};
+
+/// The Kubernetes pod `envin` runs in, from the service account Kubernetes
+/// mounts into every pod and the variables and files the downward API
+/// exposes. Limits the pod spec does not hand down come from the cgroup.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KubernetesInfo {
+    pub in_pod: bool,
+    pub namespace: Option<String>,
+    pub pod_name: Option<String>,
+    pub node_name: Option<String>,
+    pub service_account: Option<String>,
+    /// The API server address, from `KUBERNETES_SERVICE_HOST`.
+    pub api_server: Option<String>,
+    /// In CPUs, so `250m` is `0.25`.
+    pub cpu_request: Option<f64>,
+    pub cpu_limit: Option<f64>,
+    pub memory_request_bytes: Option<u64>,
+    pub memory_limit_bytes: Option<u64>,
+}
+
+/// Where Kubernetes mounts the service account's token and namespace.
+const SERVICE_ACCOUNT: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+
+/// Where the downward API volume is mounted in the Kubernetes examples,
+/// with files such as `cpu_limit` and `mem_request`.
+const POD_INFO: &str = "/etc/podinfo";
+
+impl KubernetesInfo {
+    pub fn collect() -> Self {
+        let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        let service_account = Path::new(SERVICE_ACCOUNT);
+        let in_pod = var("KUBERNETES_SERVICE_HOST").is_some() || service_account.exists();
+        if !in_pod {
+            return Self {
+                in_pod,
+                namespace: None,
+                pod_name: None,
+                node_name: None,
+                service_account: None,
+                api_server: None,
+                cpu_request: None,
+                cpu_limit: None,
+                memory_request_bytes: None,
+                memory_limit_bytes: None,
+            };
+        }
+
+        // The downward API names are up to the pod spec; these are the
+        // ones its documentation uses.
+        let field = |variables: &[&str], file: &str| {
+            variables
+                .iter()
+                .find_map(|name| var(name))
+                .or_else(|| read_trimmed(Path::new(POD_INFO).join(file)))
+        };
+        let cgroup = CgroupInfo::collect();
+
+        Self {
+            in_pod,
+            namespace: field(&["POD_NAMESPACE", "MY_POD_NAMESPACE"], "namespace")
+                .or_else(|| read_trimmed(service_account.join("namespace"))),
+            // A pod's hostname is its name unless the spec sets another.
+            pod_name: field(&["POD_NAME", "MY_POD_NAME"], "name").or_else(|| var("HOSTNAME")),
+            node_name: field(&["NODE_NAME", "MY_NODE_NAME"], "nodename"),
+            service_account: field(
+                &["SERVICE_ACCOUNT", "MY_POD_SERVICE_ACCOUNT"],
+                "serviceaccount",
+            )
+            .or_else(|| {
+                read_trimmed(service_account.join("token"))
+                    .and_then(|token| token_service_account(&token))
+            }),
+            api_server: var("KUBERNETES_SERVICE_HOST").map(|host| {
+                match var("KUBERNETES_SERVICE_PORT") {
+                    Some(port) => format!("{}:{}", host, port),
+                    None => host,
+                }
+            }),
+            cpu_request: field(&["CPU_REQUEST", "MY_CPU_REQUEST"], "cpu_request")
+                .and_then(|cpus| parse_cpus(&cpus)),
+            cpu_limit: field(&["CPU_LIMIT", "MY_CPU_LIMIT"], "cpu_limit")
+                .and_then(|cpus| parse_cpus(&cpus))
+                .or(cgroup.cpu_quota),
+            memory_request_bytes: field(&["MEMORY_REQUEST", "MY_MEM_REQUEST"], "mem_request")
+                .and_then(|bytes| parse_quantity(&bytes)),
+            memory_limit_bytes: field(&["MEMORY_LIMIT", "MY_MEM_LIMIT"], "mem_limit")
+                .and_then(|bytes| parse_quantity(&bytes))
+                .or(cgroup.memory_limit_bytes),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Kubernetes Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if !self.in_pod {
+            options.line("  Pod             : None");
+            return;
+        }
+        let or_unknown = |value: &Option<String>| value.clone().unwrap_or_else(|| "Unknown".into());
+        options.line(format_args!(
+            "  Pod             : {}",
+            or_unknown(&self.pod_name)
+        ));
+        options.line(format_args!(
+            "  Namespace       : {}",
+            or_unknown(&self.namespace)
+        ));
+        options.line(format_args!(
+            "  Node            : {}",
+            or_unknown(&self.node_name)
+        ));
+        options.line(format_args!(
+            "  Service Account : {}",
+            or_unknown(&self.service_account)
+        ));
+        // Rows are printed as is: the API server address contains `:`.
+        println!("  API Server      : {}", or_unknown(&self.api_server));
+
+        let cpus = |cpus: Option<f64>| cpus.map(|cpus| format!("{} CPUs", cpus));
+        let bytes = |bytes: Option<u64>| bytes.map(|bytes| options.bytes(bytes));
+        let pair = |request: Option<String>, limit: Option<String>| {
+            format!(
+                "{} requested, {} limit",
+                request.unwrap_or_else(|| "none".into()),
+                limit.unwrap_or_else(|| "no".into())
+            )
+        };
+        options.line(format_args!(
+            "  CPU             : {}",
+            pair(cpus(self.cpu_request), cpus(self.cpu_limit))
+        ));
+        options.line(format_args!(
+            "  Memory          : {}",
+            pair(
+                bytes(self.memory_request_bytes),
+                bytes(self.memory_limit_bytes)
+            )
+        ));
+    }
+}
+
+/// Parses CPU quantities such as `2`, `0.5` or `250m`.
+fn parse_cpus(quantity: &str) -> Option<f64> {
+    let cpus = match quantity.strip_suffix('m') {
+        Some(millis) => millis.parse::<f64>().ok()? / 1000.0,
+        None => quantity.parse().ok()?,
+    };
+    (cpus > 0.0).then_some(cpus)
+}
+
+/// Parses memory quantities such as `134217728`, `128Mi` or `1G`. The
+/// downward API gives plain bytes unless the spec sets a divisor.
+fn parse_quantity(quantity: &str) -> Option<u64> {
+    const SUFFIXES: &[(&str, u64)] = &[
+        ("Ki", 1 << 10),
+        ("Mi", 1 << 20),
+        ("Gi", 1 << 30),
+        ("Ti", 1 << 40),
+        ("k", 1_000),
+        ("M", 1_000_000),
+        ("G", 1_000_000_000),
+        ("T", 1_000_000_000_000),
+    ];
+    let (number, multiplier) = SUFFIXES
+        .iter()
+        .find_map(|(suffix, multiplier)| Some((quantity.strip_suffix(suffix)?, *multiplier)))
+        .unwrap_or((quantity, 1));
+    let bytes = number.parse::<u64>().ok()?.checked_mul(multiplier)?;
+    (bytes > 0).then_some(bytes)
+}
+
+/// Service account tokens are JWTs whose subject reads
+/// `system:serviceaccount:<namespace>:<name>`. The payload is read without
+/// checking the signature, only to name the account.
+fn token_service_account(token: &str) -> Option<String> {
+    let payload = token.split('.').nth(1)?;
+    let claims: serde_json::Value = serde_json::from_slice(&base64_url_decode(payload)?).ok()?;
+    let subject = claims["sub"].as_str()?;
+    subject
+        .strip_prefix("system:serviceaccount:")?
+        .rsplit(':')
+        .next()
+        .map(str::to_string)
+}
+
+/// Decodes unpadded base64 with the URL-safe alphabet JWTs use.
+fn base64_url_decode(text: &str) -> Option<Vec<u8>> {
+    let value = |byte: u8| match byte {
+        b'A'..=b'Z' => Some(byte - b'A'),
+        b'a'..=b'z' => Some(byte - b'a' + 26),
+        b'0'..=b'9' => Some(byte - b'0' + 52),
+        b'-' | b'+' => Some(62),
+        b'_' | b'/' => Some(63),
+        _ => None,
+    };
+
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for byte in text.trim_end_matches('=').bytes() {
+        buffer = (buffer << 6 | u32::from(value(byte)?)) & 0xFFFF;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
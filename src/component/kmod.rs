@@ -0,0 +1,96 @@
+use {
+    crate::format::DisplayOptions,
+    serde::{Deserialize, Serialize},
+};
+
+/// Loaded kernel modules, only reported on Linux.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KernelModuleInfo {
+    pub modules: Vec<KernelModuleEntry>,
+}
+
+/// A single module as listed in `/proc/modules`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KernelModuleEntry {
+    pub name: String,
+    pub size_bytes: u64,
+    /// How many modules and other users hold a reference to it.
+    pub use_count: u32,
+    /// The modules depending on it.
+    pub used_by: Vec<String>,
+    /// `Live`, `Loading` or `Unloading`.
+    pub state: String,
+}
+
+impl KernelModuleInfo {
+    pub fn collect() -> Self {
+        let modules = std::fs::read_to_string("/proc/modules")
+            .map(|modules| modules.lines().filter_map(parse_module).collect())
+            .unwrap_or_default();
+
+        Self { modules }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Kernel Module Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.modules.is_empty() {
+            options.line("  Modules: None");
+            return;
+        }
+
+        let width = self
+            .modules
+            .iter()
+            .map(|module| module.name.len())
+            .max()
+            .unwrap_or(0);
+        println!(
+            "  {:<width$}  {:>16}  {:>4}  Used By",
+            "Module",
+            "Size",
+            "Uses",
+            width = width
+        );
+        for module in &self.modules {
+            println!(
+                "  {:<width$}  {:>16}  {:>4}  {}",
+                module.name,
+                options.bytes(module.size_bytes),
+                module.use_count,
+                module.used_by.join(", "),
+                width = width
+            );
+        }
+    }
+}
+
+/// Parses a line such as `nf_tables 290816 3 nft_compat,nft_chain_nat, Live
+/// 0x0000000000000000`.
+fn parse_module(line: &str) -> Option<KernelModuleEntry> {
+    let mut fields = line.split_whitespace();
+    let name = fields.next()?.to_string();
+    let size_bytes = fields.next()?.parse().ok()?;
+    let use_count = fields.next()?.parse().ok()?;
+    let used_by = match fields.next()? {
+        "-" => Vec::new(),
+        users => users
+            .split(',')
+            .filter(|user| !user.is_empty())
+            .map(str::to_string)
+            .collect(),
+    };
+    let state = fields.next().unwrap_or("Live").to_string();
+
+    Some(KernelModuleEntry {
+        name,
+        size_bytes,
+        use_count,
+        used_by,
+        state,
+    })
+}
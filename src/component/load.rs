@@ -0,0 +1,59 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{is_bsd, sysctl},
+    },
+    serde::{Deserialize, Serialize},
+    sysinfo::{System, IS_SUPPORTED_SYSTEM},
+};
+
+/// The 1, 5 and 15 minute load averages.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LoadInfo {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+impl LoadInfo {
+    /// Returns `None` on platforms without load averages, such as Windows.
+    pub fn collect() -> Option<Self> {
+        if cfg!(target_os = "windows") {
+            return None;
+        }
+
+        // sysinfo has no backend for OpenBSD and NetBSD; `vm.loadavg` reads
+        // `{ 0.15 0.10 0.09 }` on all the BSDs.
+        if !IS_SUPPORTED_SYSTEM && is_bsd() {
+            let load = sysctl("vm.loadavg")?;
+            let mut averages = load
+                .trim_matches(|c: char| c == '{' || c == '}' || c.is_whitespace())
+                .split_whitespace()
+                .filter_map(|average| average.parse().ok());
+            return Some(Self {
+                one: averages.next()?,
+                five: averages.next()?,
+                fifteen: averages.next()?,
+            });
+        }
+
+        let load = System::load_average();
+
+        Some(Self {
+            one: load.one,
+            five: load.five,
+            fifteen: load.fifteen,
+        })
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Load Average{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        options.line(format_args!("  1 min  : {:.2}", self.one));
+        options.line(format_args!("  5 min  : {:.2}", self.five));
+        options.line(format_args!("  15 min : {:.2}", self.fifteen));
+    }
+}
@@ -0,0 +1,110 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, read_trimmed},
+    },
+    chrono::{Local, SecondsFormat, Utc},
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeMap, env},
+};
+
+/// The active locale, the system timezone and the current time.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LocaleInfo {
+    /// The locale messages and formatting follow, from `LC_ALL` or `LANG`.
+    pub locale: Option<String>,
+    /// `LANG`, `LANGUAGE` and every `LC_*` variable that is set.
+    pub variables: BTreeMap<String, String>,
+    /// The IANA name of the timezone, such as `Europe/Berlin`.
+    pub timezone: Option<String>,
+    /// The current local time in RFC 3339.
+    pub local_time: String,
+    /// The current time in RFC 3339, in UTC.
+    pub utc_time: String,
+    /// The local offset from UTC, such as `+02:00`.
+    pub utc_offset: String,
+}
+
+impl LocaleInfo {
+    pub fn collect() -> Self {
+        let variables: BTreeMap<String, String> = env::vars()
+            .filter(|(name, value)| {
+                (name == "LANG" || name == "LANGUAGE" || name.starts_with("LC_"))
+                    && !value.is_empty()
+            })
+            .collect();
+        let locale = variables
+            .get("LC_ALL")
+            .or_else(|| variables.get("LANG"))
+            .cloned()
+            .or_else(windows_locale);
+
+        let now = Local::now();
+        Self {
+            locale,
+            variables,
+            timezone: timezone(),
+            local_time: now.to_rfc3339_opts(SecondsFormat::Secs, false),
+            utc_time: now
+                .with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+            utc_offset: now.offset().to_string(),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Locale Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        options.line(format_args!(
+            "  Locale     : {}",
+            self.locale.as_deref().unwrap_or("Unknown")
+        ));
+        for (name, value) in &self.variables {
+            options.line(format_args!("    {} : {}", name, value));
+        }
+        options.line(format_args!(
+            "  Timezone   : {}",
+            self.timezone.as_deref().unwrap_or("Unknown")
+        ));
+        options.line(format_args!(
+            "  Local Time : {} (UTC{})",
+            self.local_time, self.utc_offset
+        ));
+        options.line(format_args!("  UTC Time   : {}", self.utc_time));
+    }
+}
+
+/// Looks at `TZ` first, then where the platform keeps its timezone.
+fn timezone() -> Option<String> {
+    if let Some(zone) = env::var("TZ").ok().filter(|zone| !zone.is_empty()) {
+        return Some(zone.trim_start_matches(':').to_string());
+    }
+    if cfg!(target_os = "windows") {
+        return command_output("tzutil", &["/g"]);
+    }
+
+    // `/etc/localtime` links into the zoneinfo database on Linux, macOS and
+    // the BSDs, e.g. to `/usr/share/zoneinfo/Europe/Berlin`.
+    std::fs::read_link("/etc/localtime")
+        .ok()
+        .and_then(|target| {
+            let target = target.to_string_lossy().into_owned();
+            target
+                .split_once("zoneinfo/")
+                .map(|(_, zone)| zone.to_string())
+        })
+        .or_else(|| read_trimmed("/etc/timezone"))
+}
+
+fn windows_locale() -> Option<String> {
+    if !cfg!(target_os = "windows") {
+        return None;
+    }
+    command_output(
+        "powershell",
+        &["-NoProfile", "-Command", "(Get-Culture).Name"],
+    )
+}
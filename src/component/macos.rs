@@ -0,0 +1,138 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_string},
+    },
+    serde::{Deserialize, Serialize},
+    std::path::Path,
+};
+
+/// Mac hardware and security details sysinfo does not expose: the model
+/// identifier, the Apple silicon core layout, System Integrity Protection
+/// and Rosetta. Empty on other systems.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MacosInfo {
+    /// Such as `MacBookPro18,3`.
+    pub model_identifier: Option<String>,
+    /// Such as `MacBook Pro`.
+    pub model_name: Option<String>,
+    /// Such as `Apple M1 Pro`, or the Intel CPU's brand string.
+    pub chip: Option<String>,
+    /// Apple silicon's performance and efficiency cores.
+    pub performance_cores: Option<u32>,
+    pub efficiency_cores: Option<u32>,
+    /// The macOS build, such as `23E224`.
+    pub os_build: Option<String>,
+    /// Whether System Integrity Protection is enabled.
+    pub sip_enabled: Option<bool>,
+    /// Whether Rosetta 2 is installed, on Apple silicon.
+    pub rosetta_installed: Option<bool>,
+    /// Whether `envin` itself is an x86-64 build running under Rosetta.
+    pub translated: bool,
+}
+
+/// The runtime Rosetta 2 installs.
+const ROSETTA_RUNTIME: &str = "/Library/Apple/usr/libexec/oah/libRosettaRuntime";
+
+impl MacosInfo {
+    pub fn collect() -> Self {
+        if !cfg!(target_os = "macos") {
+            return Self {
+                model_identifier: None,
+                model_name: None,
+                chip: None,
+                performance_cores: None,
+                efficiency_cores: None,
+                os_build: None,
+                sip_enabled: None,
+                rosetta_installed: None,
+                translated: false,
+            };
+        }
+
+        let sysctl = |name: &str| command_output("sysctl", &["-n", name]);
+        let hardware = command_output("system_profiler", &["SPHardwareDataType", "-json"])
+            .and_then(|output| output.parse::<serde_json::Value>().ok())
+            .and_then(|value| value["SPHardwareDataType"].get(0).cloned())
+            .unwrap_or_default();
+        // Intel Macs have no performance levels.
+        let apple_silicon = sysctl("hw.optional.arm64").as_deref() == Some("1");
+        // `csrutil status` reads `System Integrity Protection status:
+        // enabled.`
+        let sip_enabled = command_output("csrutil", &["status"]).and_then(|status| {
+            let (_, state) = status.split_once("status:")?;
+            let state = state.trim().trim_end_matches('.');
+            match state.split_whitespace().next()? {
+                "enabled" => Some(true),
+                "disabled" => Some(false),
+                _ => None,
+            }
+        });
+
+        Self {
+            model_identifier: sysctl("hw.model")
+                .or_else(|| json_string(&hardware, "machine_model")),
+            model_name: json_string(&hardware, "machine_name"),
+            chip: json_string(&hardware, "chip_type")
+                .or_else(|| sysctl("machdep.cpu.brand_string")),
+            performance_cores: sysctl("hw.perflevel0.physicalcpu")
+                .filter(|_| apple_silicon)
+                .and_then(|cores| cores.parse().ok()),
+            efficiency_cores: sysctl("hw.perflevel1.physicalcpu")
+                .filter(|_| apple_silicon)
+                .and_then(|cores| cores.parse().ok()),
+            os_build: command_output("sw_vers", &["-buildVersion"]),
+            sip_enabled,
+            rosetta_installed: apple_silicon.then(|| Path::new(ROSETTA_RUNTIME).exists()),
+            translated: sysctl("sysctl.proc_translated").as_deref() == Some("1"),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}macOS Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.model_identifier.is_none() && self.chip.is_none() {
+            options.line("  Model    : None");
+            return;
+        }
+        let model = match (&self.model_name, &self.model_identifier) {
+            (Some(name), Some(identifier)) => format!("{} ({})", name, identifier),
+            (Some(model), None) | (None, Some(model)) => model.clone(),
+            (None, None) => "Unknown".into(),
+        };
+        options.line(format_args!("  Model    : {}", model));
+        let mut chip = self.chip.clone().unwrap_or_else(|| "Unknown".into());
+        if let (Some(performance), Some(efficiency)) =
+            (self.performance_cores, self.efficiency_cores)
+        {
+            chip.push_str(&format!(" ({}P + {}E cores)", performance, efficiency));
+        }
+        options.line(format_args!("  Chip     : {}", chip));
+        options.line(format_args!(
+            "  Build    : {}",
+            self.os_build.as_deref().unwrap_or("Unknown")
+        ));
+        options.line(options.warn_if(
+            self.sip_enabled == Some(false),
+            format!(
+                "  SIP      : {}",
+                match self.sip_enabled {
+                    Some(true) => "Enabled",
+                    Some(false) => "Disabled",
+                    None => "Unknown",
+                }
+            ),
+        ));
+        let rosetta = match (self.rosetta_installed, self.translated) {
+            (_, true) => "Translating this process",
+            (Some(true), false) => "Installed",
+            (Some(false), false) => "Not installed",
+            (None, false) => "Not applicable",
+        };
+        options.line(format_args!("  Rosetta  : {}", rosetta));
+    }
+}
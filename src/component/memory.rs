@@ -0,0 +1,387 @@
+use {
+    super::pressure::Pressure,
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_objects, json_string, read_trimmed},
+    },
+    serde::{Deserialize, Serialize},
+    std::{collections::HashMap, path::Path},
+    sysinfo::System,
+};
+
+/// Where the memory not used by programs goes. Only the Linux kernel tells
+/// every figure apart; elsewhere the closest equivalents are given and the
+/// rest left out.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MemoryBreakdown {
+    /// What can be handed to programs without swapping, caches included.
+    pub available_bytes: u64,
+    /// What nothing uses at all.
+    pub free_bytes: u64,
+    pub buffers_bytes: Option<u64>,
+    /// The page cache of file contents.
+    pub cached_bytes: Option<u64>,
+    /// `tmpfs` and shared memory segments.
+    pub shared_bytes: Option<u64>,
+    /// Modified pages waiting to be written back to disk.
+    pub dirty_bytes: Option<u64>,
+    /// Pages the kernel cannot page out, on macOS.
+    pub wired_bytes: Option<u64>,
+    /// Pages held by the memory compressor, on macOS.
+    pub compressed_bytes: Option<u64>,
+    /// Time tasks waited for memory to be reclaimed, on Linux.
+    pub pressure: Option<Pressure>,
+}
+
+impl MemoryBreakdown {
+    /// Reads the breakdown, taking the totals from an already refreshed
+    /// `sys`.
+    pub fn collect(sys: &System) -> Self {
+        let mut memory = Self {
+            available_bytes: sys.available_memory(),
+            free_bytes: sys.free_memory(),
+            buffers_bytes: None,
+            cached_bytes: None,
+            shared_bytes: None,
+            dirty_bytes: None,
+            wired_bytes: None,
+            compressed_bytes: None,
+            pressure: Pressure::read("memory"),
+        };
+
+        if cfg!(any(target_os = "linux", target_os = "android")) {
+            let meminfo = meminfo();
+            memory.buffers_bytes = meminfo.get("Buffers").copied();
+            memory.cached_bytes = meminfo.get("Cached").copied();
+            memory.shared_bytes = meminfo.get("Shmem").copied();
+            memory.dirty_bytes = meminfo.get("Dirty").copied();
+        } else if cfg!(target_os = "macos") {
+            let pages = vm_stat();
+            memory.cached_bytes = pages.get("File-backed pages").copied();
+            memory.wired_bytes = pages.get("Pages wired down").copied();
+            memory.compressed_bytes = pages.get("Pages occupied by compressor").copied();
+        } else if cfg!(target_os = "windows") {
+            windows_counters(&mut memory);
+        }
+        memory
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        let details: Vec<String> = [
+            ("free", Some(self.free_bytes)),
+            ("buffers", self.buffers_bytes),
+            ("cached", self.cached_bytes),
+            ("shared", self.shared_bytes),
+            ("dirty", self.dirty_bytes),
+            ("wired", self.wired_bytes),
+            ("compressed", self.compressed_bytes),
+        ]
+        .iter()
+        .filter_map(|(name, bytes)| Some(format!("{} {}", name, options.bytes((*bytes)?))))
+        .collect();
+        options.line(format_args!(
+            "  Avail  : {} ({})",
+            options.bytes(self.available_bytes),
+            details.join(", ")
+        ));
+    }
+}
+
+/// The swap areas behind the swap totals, and the compressed RAM in front
+/// of them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SwapBreakdown {
+    pub devices: Vec<SwapDevice>,
+    /// The compressed cache for swapped pages, on Linux.
+    pub zswap: Option<ZswapInfo>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SwapDevice {
+    pub path: String,
+    /// `partition`, `file` or `zram`.
+    pub kind: String,
+    pub size_bytes: u64,
+    pub used_bytes: u64,
+    /// Higher priorities are used first.
+    pub priority: Option<i32>,
+    /// The algorithm of a zram device, such as `zstd`.
+    pub compression: Option<String>,
+    /// What the pages stored in a zram device take up once compressed.
+    pub compressed_bytes: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ZswapInfo {
+    pub enabled: bool,
+    pub compressor: Option<String>,
+    /// The share of RAM the pool may grow to.
+    pub max_pool_percent: Option<u32>,
+    /// The swapped pages held in the pool, and what they take up there.
+    pub stored_bytes: Option<u64>,
+    pub pool_bytes: Option<u64>,
+}
+
+impl SwapBreakdown {
+    pub fn collect() -> Self {
+        if cfg!(any(target_os = "linux", target_os = "android")) {
+            Self {
+                devices: proc_swaps(),
+                zswap: zswap(),
+            }
+        } else if cfg!(target_os = "windows") {
+            Self {
+                devices: page_files(),
+                zswap: None,
+            }
+        } else {
+            Self {
+                devices: Vec::new(),
+                zswap: None,
+            }
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        // Rows are printed as is: Windows paths contain `:`.
+        for device in &self.devices {
+            let mut details = vec![device.kind.clone()];
+            if let Some(priority) = device.priority {
+                details.push(format!("priority {}", priority));
+            }
+            if let Some(compression) = &device.compression {
+                details.push(compression.clone());
+            }
+            if let Some(compressed) = device.compressed_bytes {
+                details.push(format!("compressed to {}", options.bytes(compressed)));
+            }
+            println!(
+                "    {} : {} ({})",
+                device.path,
+                options.usage(device.used_bytes, device.size_bytes),
+                details.join(", ")
+            );
+        }
+        if let Some(zswap) = &self.zswap {
+            let mut text = if zswap.enabled { "enabled" } else { "disabled" }.to_string();
+            if zswap.enabled {
+                if let Some(compressor) = &zswap.compressor {
+                    text.push_str(&format!(", {}", compressor));
+                }
+                if let Some(percent) = zswap.max_pool_percent {
+                    text.push_str(&format!(", max pool {}%", percent));
+                }
+                if let (Some(stored), Some(pool)) = (zswap.stored_bytes, zswap.pool_bytes) {
+                    text.push_str(&format!(
+                        ", {} stored in {}",
+                        options.bytes(stored),
+                        options.bytes(pool)
+                    ));
+                }
+            }
+            options.line(format_args!("  Zswap  : {}", text));
+        }
+    }
+}
+
+/// Parses `/proc/swaps`, whose rows read
+/// `/dev/zram0  partition  8388604  1024  100`, sizes in KiB.
+fn proc_swaps() -> Vec<SwapDevice> {
+    let Some(swaps) = read_trimmed("/proc/swaps") else {
+        return Vec::new();
+    };
+    swaps
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let kilobytes = |index: usize| Some(fields.get(index)?.parse::<u64>().ok()? << 10);
+            let path = fields.first()?.replace("\\040", " ");
+            let zram = path
+                .strip_prefix("/dev/")
+                .filter(|name| name.starts_with("zram"))
+                .map(|name| Path::new("/sys/block").join(name));
+            // `mm_stat` starts with the original and the compressed size.
+            let compressed_bytes = zram
+                .as_ref()
+                .and_then(|zram| read_trimmed(zram.join("mm_stat")))
+                .and_then(|stat| stat.split_whitespace().nth(1)?.parse().ok());
+
+            Some(SwapDevice {
+                kind: if zram.is_some() {
+                    "zram"
+                } else {
+                    fields.get(1)?
+                }
+                .to_string(),
+                size_bytes: kilobytes(2)?,
+                used_bytes: kilobytes(3)?,
+                priority: fields.get(4).and_then(|priority| priority.parse().ok()),
+                compression: zram
+                    .as_ref()
+                    .and_then(|zram| read_trimmed(zram.join("comp_algorithm")))
+                    .and_then(|algorithms| {
+                        algorithms
+                            .split_whitespace()
+                            .find_map(|algorithm| algorithm.strip_prefix('[')?.strip_suffix(']'))
+                            .map(str::to_string)
+                    }),
+                compressed_bytes,
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Reads the module parameters of zswap; the pool sizes are in
+/// `/proc/meminfo` since Linux 5.19.
+fn zswap() -> Option<ZswapInfo> {
+    let parameters = Path::new("/sys/module/zswap/parameters");
+    let enabled = read_trimmed(parameters.join("enabled"))?;
+    let meminfo = meminfo();
+
+    Some(ZswapInfo {
+        enabled: enabled == "Y",
+        compressor: read_trimmed(parameters.join("compressor")),
+        max_pool_percent: read_trimmed(parameters.join("max_pool_percent"))
+            .and_then(|percent| percent.parse().ok()),
+        stored_bytes: meminfo.get("Zswapped").copied(),
+        pool_bytes: meminfo.get("Zswap").copied(),
+    })
+}
+
+/// Lists the page files, whose sizes `Win32_PageFileUsage` gives in MiB.
+fn page_files() -> Vec<SwapDevice> {
+    let Some(output) = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_PageFileUsage | \
+             Select-Object Name,AllocatedBaseSize,CurrentUsage | ConvertTo-Json",
+        ],
+    ) else {
+        return Vec::new();
+    };
+    json_objects(&output)
+        .iter()
+        .filter_map(|file| {
+            Some(SwapDevice {
+                path: json_string(file, "Name")?,
+                kind: "file".into(),
+                size_bytes: file["AllocatedBaseSize"].as_u64()? << 20,
+                used_bytes: file["CurrentUsage"].as_u64().unwrap_or(0) << 20,
+                priority: None,
+                compression: None,
+                compressed_bytes: None,
+            })
+        })
+        .collect()
+}
+
+/// Parses `/proc/meminfo`, whose lines read `Cached:  4983704 kB`, into
+/// bytes. The `HugePages_*` counts have no unit and are kept as they are.
+pub(crate) fn meminfo() -> HashMap<String, u64> {
+    std::fs::read_to_string("/proc/meminfo")
+        .map(|meminfo| parse_meminfo(&meminfo))
+        .unwrap_or_default()
+}
+
+fn parse_meminfo(meminfo: &str) -> HashMap<String, u64> {
+    meminfo
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let mut value = value.split_whitespace();
+            let number: u64 = value.next()?.parse().ok()?;
+            let number = match value.next() {
+                Some("kB") => number << 10,
+                _ => number,
+            };
+            Some((key.to_string(), number))
+        })
+        .collect()
+}
+
+/// Parses `vm_stat`, which counts pages, as in `Pages wired down:  402613.`,
+/// under a header naming the page size.
+fn vm_stat() -> HashMap<String, u64> {
+    let Some(output) = command_output("vm_stat", &[]) else {
+        return HashMap::new();
+    };
+    // The header reads `Mach Virtual Memory Statistics: (page size of 16384
+    // bytes)`.
+    let page_size = output
+        .lines()
+        .next()
+        .and_then(|header| header.split("page size of ").nth(1))
+        .and_then(|size| size.split_whitespace().next()?.parse::<u64>().ok())
+        .unwrap_or(4096);
+
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let pages: u64 = value.trim().trim_end_matches('.').parse().ok()?;
+            Some((key.trim().to_string(), pages * page_size))
+        })
+        .collect()
+}
+
+/// The standby lists hold what Windows keeps cached, and the modified list
+/// what still has to be written out.
+fn windows_counters(memory: &mut MemoryBreakdown) {
+    let Some(output) = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_PerfRawData_PerfOS_Memory | Select-Object \
+             StandbyCacheCoreBytes,StandbyCacheNormalPriorityBytes,\
+             StandbyCacheReserveBytes,ModifiedPageListBytes | ConvertTo-Json",
+        ],
+    ) else {
+        return;
+    };
+    let Some(counters) = json_objects(&output).into_iter().next() else {
+        return;
+    };
+    let counter = |key: &str| counters[key].as_u64();
+
+    memory.cached_bytes = [
+        "StandbyCacheCoreBytes",
+        "StandbyCacheNormalPriorityBytes",
+        "StandbyCacheReserveBytes",
+    ]
+    .iter()
+    .map(|key| counter(key))
+    .sum();
+    memory.dirty_bytes = counter("ModifiedPageListBytes");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_meminfo_into_bytes() {
+        let meminfo = parse_meminfo(
+            "MemTotal:       16245712 kB\n\
+             Buffers:          123456 kB\n\
+             Cached:          4983704 kB\n\
+             HugePages_Total:       4\n\
+             Hugepagesize:       2048 kB\n\
+             Bogus line\n\
+             Dirty:               n/a kB\n",
+        );
+        assert_eq!(meminfo.get("MemTotal"), Some(&(16245712 << 10)));
+        assert_eq!(meminfo.get("Buffers"), Some(&(123456 << 10)));
+        assert_eq!(meminfo.get("Cached"), Some(&(4983704 << 10)));
+        assert_eq!(meminfo.get("HugePages_Total"), Some(&4));
+        assert_eq!(meminfo.get("Hugepagesize"), Some(&(2048 << 10)));
+        assert_eq!(meminfo.get("Dirty"), None);
+        assert_eq!(meminfo.get("Shmem"), None);
+        assert_eq!(meminfo.len(), 5);
+    }
+}
@@ -0,0 +1,463 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_objects, json_objects_in, json_string, parse_size},
+    },
+    serde::{Deserialize, Serialize},
+    serde_json::Value,
+    std::fs,
+};
+
+/// The installed memory modules, from the SMBIOS memory device records.
+/// Reading them needs root on Linux.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MemoryModulesInfo {
+    pub modules: Vec<MemoryModule>,
+    /// Slots the firmware lists with nothing in them.
+    pub empty_slots: usize,
+    /// Why the records could not be read, such as missing permissions.
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MemoryModule {
+    /// The slot as printed on the board, such as `DIMM_A1`.
+    pub slot: Option<String>,
+    pub bank: Option<String>,
+    pub size_bytes: Option<u64>,
+    /// Such as `DDR4` or `LPDDR5`.
+    pub memory_type: Option<String>,
+    /// Such as `DIMM` or `SODIMM`.
+    pub form_factor: Option<String>,
+    /// The rated speed, in megatransfers per second.
+    pub speed_mts: Option<u32>,
+    /// The speed the firmware actually runs the module at.
+    pub configured_speed_mts: Option<u32>,
+    pub manufacturer: Option<String>,
+    pub part_number: Option<String>,
+}
+
+/// The raw SMBIOS tables the kernel exposes, readable by root only.
+const DMI_TABLES: &str = "/sys/firmware/dmi/tables/DMI";
+
+/// The SMBIOS structure type of a memory device.
+const MEMORY_DEVICE: u8 = 17;
+
+/// The structure type that ends the table.
+const END_OF_TABLE: u8 = 127;
+
+impl MemoryModulesInfo {
+    pub fn collect() -> Self {
+        let slots = if cfg!(target_os = "windows") {
+            windows_modules()
+        } else if cfg!(target_os = "macos") {
+            macos_modules()
+        } else {
+            fs::read(DMI_TABLES)
+                .map(|table| smbios_modules(&table))
+                .map_err(|err| format!("{}: {}", DMI_TABLES, err))
+        };
+
+        match slots {
+            Ok(slots) => {
+                let (modules, empty): (Vec<_>, Vec<_>) = slots
+                    .into_iter()
+                    .partition(|module| module.size_bytes != Some(0));
+                Self {
+                    modules,
+                    empty_slots: empty.len(),
+                    error: None,
+                }
+            }
+            Err(error) => Self {
+                modules: Vec::new(),
+                empty_slots: 0,
+                error: Some(error),
+            },
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Memory Module Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if let Some(error) = &self.error {
+            options.line(options.warn_if(true, format!("  Error        : {}", error)));
+            return;
+        }
+        if self.modules.is_empty() {
+            options.line("  Modules      : None");
+        }
+
+        let or_unknown = |value: &Option<String>| value.clone().unwrap_or_else(|| "Unknown".into());
+        for (index, module) in self.modules.iter().enumerate() {
+            let name = module.slot.clone().unwrap_or_else(|| index.to_string());
+            options.line(format_args!(
+                "  {}{}{}",
+                "-".repeat(10),
+                name,
+                "-".repeat(10)
+            ));
+            options.line(format_args!(
+                "    Size         : {}",
+                module
+                    .size_bytes
+                    .map(|size| options.bytes(size))
+                    .unwrap_or_else(|| "Unknown".into())
+            ));
+            let form_factor = module
+                .form_factor
+                .as_ref()
+                .map(|form_factor| format!(" ({})", form_factor))
+                .unwrap_or_default();
+            options.line(format_args!(
+                "    Type         : {}{}",
+                or_unknown(&module.memory_type),
+                form_factor
+            ));
+            let configured = module
+                .configured_speed_mts
+                .filter(|configured| Some(*configured) != module.speed_mts)
+                .map(|configured| format!(" (configured {} MT/s)", configured))
+                .unwrap_or_default();
+            options.line(format_args!(
+                "    Speed        : {}{}",
+                module
+                    .speed_mts
+                    .map(|speed| format!("{} MT/s", speed))
+                    .unwrap_or_else(|| "Unknown".into()),
+                configured
+            ));
+            options.line(format_args!(
+                "    Manufacturer : {}",
+                or_unknown(&module.manufacturer)
+            ));
+            options.line(format_args!(
+                "    Part Number  : {}",
+                or_unknown(&module.part_number)
+            ));
+        }
+        if self.empty_slots > 0 {
+            options.line(format_args!("  Empty Slots  : {}", self.empty_slots));
+        }
+    }
+}
+
+/// Walks the SMBIOS structures, each a formatted area whose second byte is
+/// its length, followed by its strings, each ended by a NUL, and one more
+/// NUL.
+fn smbios_modules(table: &[u8]) -> Vec<MemoryModule> {
+    let mut modules = Vec::new();
+    let mut offset = 0;
+    while offset + 4 <= table.len() {
+        let kind = table[offset];
+        let length = usize::from(table[offset + 1]);
+        if kind == END_OF_TABLE || length < 4 || offset + length > table.len() {
+            break;
+        }
+        let formatted = &table[offset..offset + length];
+
+        let mut end = offset + length;
+        while end + 1 < table.len() && !(table[end] == 0 && table[end + 1] == 0) {
+            end += 1;
+        }
+        let strings: Vec<String> = table[offset + length..end]
+            .split(|byte| *byte == 0)
+            .map(|text| String::from_utf8_lossy(text).trim().to_string())
+            .collect();
+
+        if kind == MEMORY_DEVICE {
+            modules.push(memory_device(formatted, &strings));
+        }
+        offset = end + 2;
+    }
+    modules
+}
+
+/// Decodes a memory device record, whose fields past the first few only
+/// exist in newer SMBIOS versions.
+fn memory_device(record: &[u8], strings: &[String]) -> MemoryModule {
+    let byte = |at: usize| record.get(at).copied();
+    let word = |at: usize| Some(u16::from_le_bytes([byte(at)?, byte(at + 1)?]));
+    let dword = |at: usize| {
+        Some(u32::from_le_bytes([
+            byte(at)?,
+            byte(at + 1)?,
+            byte(at + 2)?,
+            byte(at + 3)?,
+        ]))
+    };
+    // Strings are numbered from 1; 0 means none.
+    let string = |at: usize| {
+        let index = usize::from(byte(at)?).checked_sub(1)?;
+        strings
+            .get(index)
+            .cloned()
+            .filter(|text| !is_placeholder(text))
+    };
+    let speed = |at: usize, extended: usize| match word(at)? {
+        0 => None,
+        0xFFFF => dword(extended).filter(|speed| *speed > 0),
+        speed => Some(u32::from(speed)),
+    };
+
+    // The size is in MiB, or KiB with bit 15 set; 0x7FFF defers to the
+    // extended size, in MiB.
+    let size_bytes = match word(0x0C) {
+        Some(0xFFFF) | None => None,
+        Some(0x7FFF) => dword(0x1C).map(|mib| u64::from(mib & 0x7FFF_FFFF) << 20),
+        Some(size) if size & 0x8000 != 0 => Some(u64::from(size & 0x7FFF) << 10),
+        Some(size) => Some(u64::from(size) << 20),
+    };
+
+    MemoryModule {
+        slot: string(0x10),
+        bank: string(0x11),
+        size_bytes,
+        memory_type: byte(0x12).and_then(memory_type),
+        form_factor: byte(0x0E).and_then(|code| {
+            match code {
+                0x03 => Some("SIMM"),
+                0x09 => Some("DIMM"),
+                0x0C => Some("RIMM"),
+                0x0D => Some("SODIMM"),
+                0x0F => Some("FB-DIMM"),
+                _ => None,
+            }
+            .map(str::to_string)
+        }),
+        speed_mts: speed(0x15, 0x54),
+        configured_speed_mts: speed(0x20, 0x58),
+        manufacturer: string(0x17),
+        part_number: string(0x1A),
+    }
+}
+
+/// The SMBIOS memory type codes, which `Win32_PhysicalMemory` shares.
+fn memory_type(code: u8) -> Option<String> {
+    let name = match code {
+        0x0F => "SDRAM",
+        0x12 => "DDR",
+        0x13 => "DDR2",
+        0x14 => "DDR2 FB-DIMM",
+        0x18 => "DDR3",
+        0x1A => "DDR4",
+        0x1B => "LPDDR",
+        0x1C => "LPDDR2",
+        0x1D => "LPDDR3",
+        0x1E => "LPDDR4",
+        0x20 => "HBM",
+        0x21 => "HBM2",
+        0x22 => "DDR5",
+        0x23 => "LPDDR5",
+        0x24 => "HBM3",
+        _ => return None,
+    };
+    Some(name.into())
+}
+
+/// Fillers left in the strings of unset fields.
+fn is_placeholder(text: &str) -> bool {
+    matches!(
+        text,
+        "" | "Unknown" | "Not Specified" | "NO DIMM" | "Undefined" | "[Empty]"
+    )
+}
+
+fn windows_modules() -> Result<Vec<MemoryModule>, String> {
+    let output = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_PhysicalMemory | Select-Object DeviceLocator,BankLabel,\
+             Capacity,SMBIOSMemoryType,FormFactor,Speed,ConfiguredClockSpeed,Manufacturer,\
+             PartNumber | ConvertTo-Json",
+        ],
+    )
+    .ok_or("Win32_PhysicalMemory could not be queried")?;
+
+    let number = |module: &Value, key: &str| {
+        module[key]
+            .as_u64()
+            .or_else(|| module[key].as_str()?.parse().ok())
+    };
+    Ok(json_objects(&output)
+        .iter()
+        .map(|module| {
+            let text =
+                |key: &str| json_string(module, key).filter(|text| !is_placeholder(text.trim()));
+            MemoryModule {
+                slot: text("DeviceLocator"),
+                bank: text("BankLabel"),
+                size_bytes: number(module, "Capacity"),
+                memory_type: number(module, "SMBIOSMemoryType")
+                    .and_then(|code| u8::try_from(code).ok())
+                    .and_then(memory_type),
+                form_factor: match number(module, "FormFactor") {
+                    Some(7) => Some("SIMM".into()),
+                    Some(8) => Some("DIMM".into()),
+                    Some(11) => Some("RIMM".into()),
+                    Some(12) => Some("SODIMM".into()),
+                    _ => None,
+                },
+                speed_mts: number(module, "Speed").map(|speed| speed as u32),
+                configured_speed_mts: number(module, "ConfiguredClockSpeed")
+                    .map(|speed| speed as u32),
+                manufacturer: text("Manufacturer").map(|name| name.trim().to_string()),
+                part_number: text("PartNumber").map(|part| part.trim().to_string()),
+            }
+        })
+        .collect())
+}
+
+/// Intel Macs list each slot under `_items`; Apple Silicon describes its
+/// unified memory in a single record.
+fn macos_modules() -> Result<Vec<MemoryModule>, String> {
+    let output = command_output("system_profiler", &["SPMemoryDataType", "-json"])
+        .ok_or("system_profiler could not be run")?;
+    let report: Value = output
+        .parse()
+        .map_err(|err| format!("system_profiler: {}", err))?;
+
+    let mut modules = Vec::new();
+    for entry in json_objects_in(&report, "SPMemoryDataType") {
+        let slots = json_objects_in(entry, "_items");
+        let slots = if slots.is_empty() { vec![entry] } else { slots };
+        for slot in slots {
+            let text = |key: &str| json_string(slot, key).filter(|text| !is_placeholder(text));
+            let size = text("dimm_size").or_else(|| text("SPMemoryDataType"));
+            modules.push(MemoryModule {
+                slot: text("_name").filter(|_| slot["dimm_size"].is_string()),
+                bank: None,
+                // Empty slots read `Empty` rather than a size.
+                size_bytes: match size.as_deref() {
+                    Some("Empty") => Some(0),
+                    size => size.and_then(parse_size),
+                },
+                memory_type: text("dimm_type").filter(|kind| kind != "Empty"),
+                form_factor: None,
+                speed_mts: text("dimm_speed")
+                    .and_then(|speed| speed.split_whitespace().next()?.parse().ok()),
+                configured_speed_mts: None,
+                manufacturer: text("dimm_manufacturer"),
+                part_number: text("dimm_part_number"),
+            });
+        }
+    }
+    Ok(modules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A structure of `kind` with `formatted` after its header, then
+    /// `strings`.
+    fn structure(kind: u8, formatted: &[u8], strings: &[&str]) -> Vec<u8> {
+        let mut bytes = vec![kind, 4 + formatted.len() as u8, 0x00, 0x10];
+        bytes.extend_from_slice(formatted);
+        for text in strings {
+            bytes.extend_from_slice(text.as_bytes());
+            bytes.push(0);
+        }
+        if strings.is_empty() {
+            bytes.push(0);
+        }
+        bytes.push(0);
+        bytes
+    }
+
+    /// An SMBIOS 2.8 memory device of `size` in MiB, naming its slot, bank,
+    /// manufacturer and part number with strings 1 to 4.
+    fn device_record(size: u16, extended_size: u32) -> Vec<u8> {
+        let mut record = [0u8; 0x28 - 4];
+        let mut set = |at: usize, bytes: &[u8]| {
+            record[at - 4..at - 4 + bytes.len()].copy_from_slice(bytes);
+        };
+        set(0x0C, &size.to_le_bytes());
+        set(0x0E, &[0x09]);
+        set(0x10, &[1, 2, 0x1A]);
+        set(0x15, &3200u16.to_le_bytes());
+        set(0x17, &[3]);
+        set(0x1A, &[4]);
+        set(0x1C, &extended_size.to_le_bytes());
+        set(0x20, &2933u16.to_le_bytes());
+        record.to_vec()
+    }
+
+    fn end_of_table() -> Vec<u8> {
+        structure(END_OF_TABLE, &[], &[])
+    }
+
+    #[test]
+    fn reads_a_populated_slot_past_other_structures() {
+        let mut table = structure(0, &[1, 2, 0, 0], &["American Megatrends", "1.2.3"]);
+        table.extend(structure(
+            MEMORY_DEVICE,
+            &device_record(16 * 1024, 0),
+            &["DIMM_A1", "BANK 0", "Samsung", "M378A2K43CB1-CTD"],
+        ));
+        table.extend(structure(
+            MEMORY_DEVICE,
+            &device_record(0x7FFF, 64 * 1024),
+            &["DIMM_A2", "BANK 1", "Samsung", "M378A4G43AB2-CWE"],
+        ));
+        table.extend(end_of_table());
+
+        let modules = smbios_modules(&table);
+        assert_eq!(modules.len(), 2);
+        let module = &modules[0];
+        assert_eq!(module.slot.as_deref(), Some("DIMM_A1"));
+        assert_eq!(module.bank.as_deref(), Some("BANK 0"));
+        assert_eq!(module.size_bytes, Some(16 << 30));
+        assert_eq!(module.memory_type.as_deref(), Some("DDR4"));
+        assert_eq!(module.form_factor.as_deref(), Some("DIMM"));
+        assert_eq!(module.speed_mts, Some(3200));
+        assert_eq!(module.configured_speed_mts, Some(2933));
+        assert_eq!(module.manufacturer.as_deref(), Some("Samsung"));
+        assert_eq!(module.part_number.as_deref(), Some("M378A2K43CB1-CTD"));
+        assert_eq!(modules[1].slot.as_deref(), Some("DIMM_A2"));
+        assert_eq!(modules[1].size_bytes, Some(64 << 30));
+    }
+
+    #[test]
+    fn reads_an_empty_slot() {
+        let mut table = structure(
+            MEMORY_DEVICE,
+            &device_record(0, 0),
+            &["DIMM_B1", "BANK 2", "NO DIMM", "NO DIMM"],
+        );
+        table.extend(end_of_table());
+
+        let modules = smbios_modules(&table);
+        assert_eq!(modules.len(), 1);
+        assert_eq!(modules[0].slot.as_deref(), Some("DIMM_B1"));
+        assert_eq!(modules[0].size_bytes, Some(0));
+        assert_eq!(modules[0].manufacturer, None);
+        assert_eq!(modules[0].part_number, None);
+    }
+
+    #[test]
+    fn stops_at_a_truncated_table() {
+        let first = structure(
+            MEMORY_DEVICE,
+            &device_record(8 * 1024, 0),
+            &["DIMM_A1", "BANK 0", "Micron", "8ATF1G64AZ"],
+        );
+        let mut table = first.clone();
+        table.extend(&first[..0x10]);
+        assert_eq!(smbios_modules(&table).len(), 1);
+        assert_eq!(smbios_modules(&first[..0x10]).len(), 0);
+        assert_eq!(smbios_modules(&[MEMORY_DEVICE, 0x28]).len(), 0);
+
+        // A string table cut off before its terminator still names what it
+        // holds.
+        let module = &smbios_modules(&first[..first.len() - 1])[0];
+        assert_eq!(module.slot.as_deref(), Some("DIMM_A1"));
+        assert_eq!(module.part_number.as_deref(), Some("8ATF1G64AZ"));
+    }
+}
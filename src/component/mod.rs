@@ -0,0 +1,57 @@
+//! One module per report section. Each exposes an `*Info` struct with a
+//! `collect` constructor and a `display` method printing it as text.
+
+pub mod android;
+pub mod audio;
+pub mod battery;
+pub mod bluetooth;
+pub mod board;
+pub mod cgroup;
+pub mod cloud;
+pub mod compute;
+pub mod container_engine;
+pub mod cpu;
+pub mod cpu_flags;
+pub mod cpu_vulnerabilities;
+pub mod desktop;
+pub mod disk;
+pub mod disk_io;
+pub mod display;
+pub mod dns;
+pub mod firmware;
+pub mod gpu;
+pub mod graphics_api;
+pub mod host;
+pub mod hugepages;
+pub mod k8s;
+pub mod kmod;
+pub mod load;
+pub mod locale;
+pub mod macos;
+pub mod memory;
+pub mod memory_modules;
+pub mod mounts;
+pub mod net_usage;
+pub mod network;
+pub mod numa;
+pub mod packages;
+pub mod pci;
+pub mod ports;
+pub mod power;
+pub mod pressure;
+pub mod process;
+pub mod project;
+pub mod public_ip;
+pub mod routes;
+pub mod rust;
+pub mod sensor;
+pub mod services;
+pub mod shell;
+pub mod smart;
+pub mod system;
+pub mod toolchain;
+pub mod uptime;
+pub mod usb;
+pub mod virt;
+pub mod wifi;
+pub mod windows;
@@ -0,0 +1,183 @@
+use {
+    crate::{format::DisplayOptions, util::command_output},
+    serde::{Deserialize, Serialize},
+    sysinfo::Disks,
+};
+
+/// The mount table, without the kernel's pseudo filesystems.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MountsInfo {
+    pub mounts: Vec<MountEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MountEntry {
+    pub device: String,
+    pub mount_point: String,
+    pub file_system: String,
+    /// Such as `rw`, `noatime` or `nosuid`, in the order the system lists
+    /// them.
+    pub options: Vec<String>,
+}
+
+/// Filesystems that expose kernel state rather than hold files.
+const PSEUDO_FILE_SYSTEMS: &[&str] = &[
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devfs",
+    "devpts",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "proc",
+    "pstore",
+    "rpc_pipefs",
+    "securityfs",
+    "selinuxfs",
+    "sysfs",
+    "tracefs",
+];
+
+impl MountsInfo {
+    pub fn collect() -> Self {
+        let mounts = if cfg!(any(target_os = "linux", target_os = "android")) {
+            std::fs::read_to_string("/proc/self/mounts")
+                .map(|mounts| mounts.lines().filter_map(parse_proc_mount).collect())
+                .unwrap_or_default()
+        } else if cfg!(target_os = "windows") {
+            volumes()
+        } else {
+            command_output("mount", &[])
+                .map(|mounts| mounts.lines().filter_map(parse_bsd_mount).collect())
+                .unwrap_or_default()
+        };
+
+        Self {
+            mounts: mounts
+                .into_iter()
+                .filter(|mount: &MountEntry| {
+                    !PSEUDO_FILE_SYSTEMS.contains(&mount.file_system.as_str())
+                })
+                .collect(),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Mount Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.mounts.is_empty() {
+            options.line("  Mounts: None");
+            return;
+        }
+
+        let width = |field: fn(&MountEntry) -> &str| {
+            self.mounts
+                .iter()
+                .map(|mount| field(mount).len())
+                .max()
+                .unwrap_or(0)
+        };
+        let (device_width, point_width, type_width) = (
+            width(|mount| &mount.device),
+            width(|mount| &mount.mount_point),
+            width(|mount| &mount.file_system),
+        );
+        // Rows are printed as is: Windows mount points contain `:`.
+        for mount in &self.mounts {
+            println!(
+                "  {:<device_width$}  {:<point_width$}  {:<type_width$}  {}",
+                mount.device,
+                mount.mount_point,
+                mount.file_system,
+                mount.options.join(","),
+                device_width = device_width,
+                point_width = point_width,
+                type_width = type_width
+            );
+        }
+    }
+}
+
+/// Parses a line of `/proc/self/mounts`, such as `/dev/nvme0n1p2 / ext4
+/// rw,relatime 0 0`. Spaces in paths are escaped as `\040`.
+fn parse_proc_mount(line: &str) -> Option<MountEntry> {
+    let mut fields = line.split_whitespace();
+    let device = unescape(fields.next()?);
+    let mount_point = unescape(fields.next()?);
+    let file_system = fields.next()?.to_string();
+    let options = fields.next()?.split(',').map(str::to_string).collect();
+
+    Some(MountEntry {
+        device,
+        mount_point,
+        file_system,
+        options,
+    })
+}
+
+/// Decodes the octal escapes of the kernel, as in `My\040Disk`.
+fn unescape(field: &str) -> String {
+    let mut text = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(index) = rest.find('\\') {
+        text.push_str(&rest[..index]);
+        let code = rest
+            .get(index + 1..index + 4)
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        match code {
+            Some(code) => {
+                text.push(code as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                text.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Parses a line of `mount` on macOS and the BSDs, such as
+/// `/dev/disk3s1s1 on / (apfs, sealed, local, read-only, journaled)`.
+fn parse_bsd_mount(line: &str) -> Option<MountEntry> {
+    let (device, rest) = line.split_once(" on ")?;
+    let (mount_point, options) = rest.rsplit_once(" (")?;
+    let mut options = options
+        .trim_end_matches(')')
+        .split(", ")
+        .map(str::to_string);
+
+    Some(MountEntry {
+        device: device.to_string(),
+        mount_point: mount_point.to_string(),
+        file_system: options.next()?,
+        options: options.collect(),
+    })
+}
+
+/// Lists the volumes, which Windows knows no mount options for beyond being
+/// read-only.
+fn volumes() -> Vec<MountEntry> {
+    Disks::new_with_refreshed_list()
+        .iter()
+        .map(|disk| MountEntry {
+            device: disk.name().to_string_lossy().into_owned(),
+            mount_point: disk.mount_point().display().to_string(),
+            file_system: disk.file_system().to_string_lossy().into_owned(),
+            options: vec![if disk.is_read_only() { "ro" } else { "rw" }.into()],
+        })
+        .collect()
+}
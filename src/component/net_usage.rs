@@ -0,0 +1,80 @@
+use {
+    crate::format::DisplayOptions,
+    serde::{Deserialize, Serialize},
+    std::{thread, time::Duration},
+    sysinfo::Networks,
+};
+
+/// Network throughput measured over a sampling window.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetUsageInfo {
+    /// How long the interfaces were watched for.
+    pub interval_ms: u64,
+    pub interfaces: Vec<NetUsageEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetUsageEntry {
+    pub name: String,
+    pub received_bytes_per_sec: f64,
+    pub transmitted_bytes_per_sec: f64,
+    pub received_packets_per_sec: f64,
+    pub transmitted_packets_per_sec: f64,
+}
+
+impl NetUsageInfo {
+    /// Reads the interface counters, waits `interval` and reads them again.
+    pub fn collect(interval: Duration) -> Self {
+        let mut networks = Networks::new_with_refreshed_list();
+        thread::sleep(interval);
+        networks.refresh();
+
+        let seconds = interval.as_secs_f64().max(f64::EPSILON);
+        let mut interfaces: Vec<NetUsageEntry> = networks
+            .iter()
+            .map(|(name, data)| NetUsageEntry {
+                name: name.clone(),
+                received_bytes_per_sec: data.received() as f64 / seconds,
+                transmitted_bytes_per_sec: data.transmitted() as f64 / seconds,
+                received_packets_per_sec: data.packets_received() as f64 / seconds,
+                transmitted_packets_per_sec: data.packets_transmitted() as f64 / seconds,
+            })
+            .collect();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            interval_ms: interval.as_millis() as u64,
+            interfaces,
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Network Usage Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.interfaces.is_empty() {
+            options.line("  Interfaces: None");
+            return;
+        }
+
+        options.line(format_args!("  Sampled Over : {} ms", self.interval_ms));
+        let width = self
+            .interfaces
+            .iter()
+            .map(|interface| interface.name.len())
+            .max()
+            .unwrap_or(0);
+        for interface in &self.interfaces {
+            options.line(format_args!(
+                "  {:<width$} : RX {}/s, TX {}/s",
+                interface.name,
+                options.bytes(interface.received_bytes_per_sec as u64),
+                options.bytes(interface.transmitted_bytes_per_sec as u64),
+                width = width
+            ));
+        }
+    }
+}
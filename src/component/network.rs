@@ -0,0 +1,94 @@
+use {
+    crate::format::DisplayOptions,
+    serde::{Deserialize, Serialize},
+    sysinfo::Networks,
+};
+
+/// Network interfaces and their traffic counters since boot.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkInfo {
+    pub interfaces: Vec<InterfaceInfo>,
+}
+
+/// A single network interface.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InterfaceInfo {
+    pub name: String,
+    pub mac_address: String,
+    pub ip_addresses: Vec<String>,
+    pub received_bytes: u64,
+    pub transmitted_bytes: u64,
+    pub received_packets: u64,
+    pub transmitted_packets: u64,
+    pub receive_errors: u64,
+    pub transmit_errors: u64,
+}
+
+impl NetworkInfo {
+    pub fn collect() -> Self {
+        let networks = Networks::new_with_refreshed_list();
+
+        let mut interfaces: Vec<InterfaceInfo> = networks
+            .iter()
+            .map(|(name, data)| InterfaceInfo {
+                name: name.clone(),
+                mac_address: data.mac_address().to_string(),
+                ip_addresses: data
+                    .ip_networks()
+                    .iter()
+                    .map(|network| format!("{}/{}", network.addr, network.prefix))
+                    .collect(),
+                received_bytes: data.total_received(),
+                transmitted_bytes: data.total_transmitted(),
+                received_packets: data.total_packets_received(),
+                transmitted_packets: data.total_packets_transmitted(),
+                receive_errors: data.total_errors_on_received(),
+                transmit_errors: data.total_errors_on_transmitted(),
+            })
+            .collect();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self { interfaces }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Network Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.interfaces.is_empty() {
+            options.line("  Interfaces: None");
+        }
+
+        for interface in &self.interfaces {
+            let ip_addresses = if interface.ip_addresses.is_empty() {
+                "None".to_string()
+            } else {
+                interface.ip_addresses.join(", ")
+            };
+
+            options.line(format_args!(
+                "  {}{}{}",
+                "-".repeat(10),
+                interface.name,
+                "-".repeat(10)
+            ));
+            options.line(format_args!("    MAC Address  : {}", interface.mac_address));
+            options.line(format_args!("    IP Addresses : {}", ip_addresses));
+            options.line(format_args!(
+                "    Received     : {} ({} packets, {} errors)",
+                options.bytes(interface.received_bytes),
+                interface.received_packets,
+                interface.receive_errors
+            ));
+            options.line(format_args!(
+                "    Transmitted  : {} ({} packets, {} errors)",
+                options.bytes(interface.transmitted_bytes),
+                interface.transmitted_packets,
+                interface.transmit_errors
+            ));
+        }
+    }
+}
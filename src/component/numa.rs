@@ -0,0 +1,151 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{parse_cpu_list, read_trimmed},
+    },
+    serde::{Deserialize, Serialize},
+    std::{fs, path::Path},
+};
+
+/// The NUMA nodes of the machine, read from sysfs on Linux.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NumaInfo {
+    pub nodes: Vec<NumaNode>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NumaNode {
+    pub id: u32,
+    pub cpus: Vec<usize>,
+    pub memory_total_bytes: Option<u64>,
+    pub memory_free_bytes: Option<u64>,
+    /// The relative cost of reaching each node's memory from this one,
+    /// indexed by node; 10 is local.
+    pub distances: Vec<u32>,
+}
+
+const NODE_ROOT: &str = "/sys/devices/system/node";
+
+impl NumaInfo {
+    pub fn collect() -> Self {
+        let mut nodes: Vec<NumaNode> = fs::read_dir(NODE_ROOT)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter_map(|entry| {
+                        let id = entry
+                            .file_name()
+                            .to_str()?
+                            .strip_prefix("node")?
+                            .parse()
+                            .ok()?;
+                        Some(node(id, &entry.path()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        nodes.sort_by_key(|node| node.id);
+
+        Self { nodes }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}NUMA Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.nodes.is_empty() {
+            options.line("  Nodes: None");
+            return;
+        }
+
+        for node in &self.nodes {
+            options.line(format_args!(
+                "  {}Node {}{}",
+                "-".repeat(10),
+                node.id,
+                "-".repeat(10)
+            ));
+            options.line(format_args!(
+                "    CPUs      : {}",
+                if node.cpus.is_empty() {
+                    "None".into()
+                } else {
+                    cpu_ranges(&node.cpus)
+                }
+            ));
+            let memory = match (node.memory_total_bytes, node.memory_free_bytes) {
+                (Some(total), Some(free)) => options.usage(total.saturating_sub(free), total),
+                (Some(total), None) => options.bytes(total),
+                _ => "Unknown".into(),
+            };
+            options.line(format_args!("    Memory    : {}", memory));
+            if !node.distances.is_empty() {
+                options.line(format_args!(
+                    "    Distances : {}",
+                    node.distances
+                        .iter()
+                        .map(u32::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ));
+            }
+        }
+    }
+}
+
+fn node(id: u32, path: &Path) -> NumaNode {
+    let meminfo = read_trimmed(path.join("meminfo")).unwrap_or_default();
+    // Lines read `Node 0 MemTotal:  6147400 kB`.
+    let memory = |key: &str| {
+        meminfo.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            if !name.ends_with(key) {
+                return None;
+            }
+            let kilobytes: u64 = value.trim().trim_end_matches(" kB").parse().ok()?;
+            Some(kilobytes << 10)
+        })
+    };
+
+    NumaNode {
+        id,
+        cpus: read_trimmed(path.join("cpulist"))
+            .map(|list| parse_cpu_list(&list))
+            .unwrap_or_default(),
+        memory_total_bytes: memory("MemTotal"),
+        memory_free_bytes: memory("MemFree"),
+        distances: read_trimmed(path.join("distance"))
+            .map(|distances| {
+                distances
+                    .split_whitespace()
+                    .filter_map(|distance| distance.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Folds runs of consecutive CPUs back into ranges, as in `0-3, 8-11`.
+fn cpu_ranges(cpus: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, last)) if *last + 1 == cpu => *last = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(first, last)| {
+            if first == last {
+                first.to_string()
+            } else {
+                format!("{}-{}", first, last)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
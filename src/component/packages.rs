@@ -0,0 +1,243 @@
+use {
+    crate::{format::DisplayOptions, util::command_output_timeout},
+    serde::{Deserialize, Serialize},
+    std::{
+        env, fs,
+        path::{Path, PathBuf},
+        time::Duration,
+    },
+};
+
+/// How long a package manager may take to list what it installed.
+const LIST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The package managers found and how many packages each installed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PackagesInfo {
+    pub managers: Vec<PackageManagerEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PackageManagerEntry {
+    pub name: String,
+    pub packages: u64,
+}
+
+/// Counts the packages of one manager, or `None` if it is not installed.
+type Counter = fn() -> Option<u64>;
+
+const COUNTERS: &[(&str, Counter)] = &[
+    ("dpkg", dpkg),
+    ("rpm", rpm),
+    ("pacman", pacman),
+    ("apk", apk),
+    ("xbps", xbps),
+    ("portage", portage),
+    ("nix", nix),
+    ("pkg", freebsd_pkg),
+    ("flatpak", flatpak),
+    ("snap", snap),
+    ("brew", brew),
+    ("port", macports),
+    ("winget", winget),
+    ("scoop", scoop),
+    ("choco", chocolatey),
+];
+
+impl PackagesInfo {
+    /// Lists the managers that installed at least one package.
+    pub fn collect() -> Self {
+        let managers = COUNTERS
+            .iter()
+            .filter_map(|(name, count)| {
+                Some(PackageManagerEntry {
+                    name: name.to_string(),
+                    packages: count().filter(|&packages| packages > 0)?,
+                })
+            })
+            .collect();
+
+        Self { managers }
+    }
+
+    /// `1234 (dpkg), 12 (flatpak)`, or `None`.
+    pub fn summary(&self) -> String {
+        if self.managers.is_empty() {
+            return "None".into();
+        }
+        self.managers
+            .iter()
+            .map(|manager| format!("{} ({})", manager.packages, manager.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Package Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        if self.managers.is_empty() {
+            options.line("  Package Managers: None");
+        }
+        let width = self
+            .managers
+            .iter()
+            .map(|manager| manager.name.len())
+            .max()
+            .unwrap_or(0);
+        for manager in &self.managers {
+            options.line(format_args!(
+                "  {:<width$} : {} packages",
+                manager.name,
+                manager.packages,
+                width = width
+            ));
+        }
+    }
+}
+
+/// Counts the lines a listing command prints, less its header lines.
+fn count_lines(command: &str, args: &[&str], header: usize) -> Option<u64> {
+    let output = command_output_timeout(command, args, LIST_TIMEOUT)?;
+    let lines = output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .count();
+    Some(lines.saturating_sub(header) as u64)
+}
+
+/// Counts the directories in `path`.
+fn count_dirs(path: impl AsRef<Path>) -> Option<u64> {
+    let entries = fs::read_dir(path).ok()?;
+    Some(
+        entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+            .count() as u64,
+    )
+}
+
+fn dpkg() -> Option<u64> {
+    let status = fs::read_to_string("/var/lib/dpkg/status").ok()?;
+    Some(
+        status
+            .lines()
+            .filter(|line| *line == "Status: install ok installed")
+            .count() as u64,
+    )
+}
+
+fn rpm() -> Option<u64> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    count_lines("rpm", &["-qa"], 0)
+}
+
+fn pacman() -> Option<u64> {
+    count_dirs("/var/lib/pacman/local")
+}
+
+fn apk() -> Option<u64> {
+    let installed = fs::read_to_string("/lib/apk/db/installed").ok()?;
+    Some(
+        installed
+            .lines()
+            .filter(|line| line.starts_with("P:"))
+            .count() as u64,
+    )
+}
+
+fn xbps() -> Option<u64> {
+    count_lines("xbps-query", &["-l"], 0)
+}
+
+fn portage() -> Option<u64> {
+    let categories = fs::read_dir("/var/db/pkg").ok()?;
+    Some(
+        categories
+            .filter_map(Result::ok)
+            .filter_map(|category| count_dirs(category.path()))
+            .sum(),
+    )
+}
+
+fn nix() -> Option<u64> {
+    let system = Path::new("/run/current-system/sw");
+    if !system.exists() {
+        return None;
+    }
+    count_lines(
+        "nix-store",
+        &["--query", "--requisites", &system.to_string_lossy()],
+        0,
+    )
+}
+
+fn freebsd_pkg() -> Option<u64> {
+    if !cfg!(target_os = "freebsd") {
+        return None;
+    }
+    count_lines("pkg", &["info"], 0)
+}
+
+fn flatpak() -> Option<u64> {
+    count_lines("flatpak", &["list"], 0)
+}
+
+fn snap() -> Option<u64> {
+    count_lines("snap", &["list"], 1)
+}
+
+/// Counts the formulae and casks under the Homebrew prefix.
+fn brew() -> Option<u64> {
+    let prefix = ["/opt/homebrew", "/usr/local", "/home/linuxbrew/.linuxbrew"]
+        .iter()
+        .map(PathBuf::from)
+        .find(|prefix| prefix.join("Cellar").is_dir())?;
+    let formulae = count_dirs(prefix.join("Cellar")).unwrap_or(0);
+    let casks = count_dirs(prefix.join("Caskroom")).unwrap_or(0);
+    Some(formulae + casks)
+}
+
+fn macports() -> Option<u64> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    count_lines("port", &["installed"], 1)
+}
+
+/// Counts the rows below the dashed line of `winget list`.
+fn winget() -> Option<u64> {
+    if !cfg!(target_os = "windows") {
+        return None;
+    }
+    let output = command_output_timeout(
+        "winget",
+        &["list", "--accept-source-agreements"],
+        LIST_TIMEOUT,
+    )?;
+    let rows = output
+        .lines()
+        .skip_while(|line| !line.starts_with("---"))
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .count();
+    Some(rows as u64)
+}
+
+fn scoop() -> Option<u64> {
+    let root = env::var("SCOOP")
+        .map(PathBuf::from)
+        .or_else(|_| env::var("USERPROFILE").map(|home| Path::new(&home).join("scoop")))
+        .ok()?;
+    // Scoop installs itself as an app too.
+    count_dirs(root.join("apps")).map(|count| count.saturating_sub(1))
+}
+
+fn chocolatey() -> Option<u64> {
+    let root = env::var("ChocolateyInstall").ok()?;
+    count_dirs(Path::new(&root).join("lib"))
+}
@@ -0,0 +1,194 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        ids::IdDatabase,
+        util::{command_output, json_objects, json_string, read_hex_id, read_trimmed},
+    },
+    serde::{Deserialize, Serialize},
+    std::path::Path,
+};
+
+/// PCI devices, reported on Linux and Windows.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PciInfo {
+    pub devices: Vec<PciDevice>,
+}
+
+/// A single device. Names come from the PCI ID database where the platform
+/// does not provide them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PciDevice {
+    /// The bus address, as in `0000:01:00.0`, or the instance ID on Windows.
+    pub address: String,
+    pub class: String,
+    /// The vendor ID in hex, as in `10de`.
+    pub vendor_id: String,
+    /// The device ID in hex, as in `2684`.
+    pub device_id: String,
+    pub vendor: Option<String>,
+    pub device: Option<String>,
+    /// The kernel driver bound to the device, if any.
+    pub driver: Option<String>,
+}
+
+impl PciInfo {
+    pub fn collect() -> Self {
+        let devices = if cfg!(target_os = "windows") {
+            collect_windows_devices()
+        } else {
+            collect_sysfs_devices()
+        };
+
+        Self { devices }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}PCI Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.devices.is_empty() {
+            options.line("  Devices: None");
+            return;
+        }
+
+        // Rows are printed as is: addresses contain `:`.
+        for device in &self.devices {
+            let name = match (&device.vendor, &device.device) {
+                (Some(vendor), Some(device)) => format!("{} {}", vendor, device),
+                (Some(vendor), None) => vendor.clone(),
+                _ => "Unknown".into(),
+            };
+            println!(
+                "  {} {}: {} [{}:{}] (driver: {})",
+                device.address,
+                device.class,
+                name,
+                device.vendor_id,
+                device.device_id,
+                device.driver.as_deref().unwrap_or("None")
+            );
+        }
+    }
+}
+
+/// Enumerates `/sys/bus/pci/devices` on Linux.
+fn collect_sysfs_devices() -> Vec<PciDevice> {
+    let root = Path::new("/sys/bus/pci/devices");
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let pci_ids = IdDatabase::pci();
+    let mut devices: Vec<PciDevice> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let device = entry.path();
+            let vendor_id = read_hex_id(&device.join("vendor"))?;
+            let device_id = read_hex_id(&device.join("device"))?;
+            // `class` holds the class, subclass and programming interface,
+            // as in `0x030000`.
+            let class = read_trimmed(device.join("class"))
+                .and_then(|class| u32::from_str_radix(class.trim_start_matches("0x"), 16).ok())
+                .unwrap_or(0);
+            let (class, subclass) = ((class >> 16) as u8, (class >> 8) as u8);
+
+            let (vendor, name) = pci_ids
+                .as_ref()
+                .map(|ids| ids.names(vendor_id, device_id))
+                .unwrap_or_default();
+            let class = pci_ids
+                .as_ref()
+                .and_then(|ids| ids.class_name(class, subclass))
+                .unwrap_or_else(|| base_class_name(class).into());
+            let driver = std::fs::read_link(device.join("driver"))
+                .ok()
+                .and_then(|link| {
+                    link.file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                });
+
+            Some(PciDevice {
+                address: entry.file_name().to_string_lossy().into_owned(),
+                class,
+                vendor_id: format!("{:04x}", vendor_id),
+                device_id: format!("{:04x}", device_id),
+                vendor,
+                device: name,
+                driver,
+            })
+        })
+        .collect();
+    devices.sort_by(|a, b| a.address.cmp(&b.address));
+    devices
+}
+
+fn collect_windows_devices() -> Vec<PciDevice> {
+    let Some(output) = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_PnPEntity | \
+             Where-Object { $_.PNPDeviceID -like 'PCI\\*' } | \
+             Select-Object PNPDeviceID,PNPClass,Name,Manufacturer,Service | \
+             ConvertTo-Json",
+        ],
+    ) else {
+        return Vec::new();
+    };
+
+    json_objects(&output)
+        .iter()
+        .filter_map(|entity| {
+            // Instance IDs read `PCI\VEN_8086&DEV_0D57&SUBSYS_...`.
+            let address = json_string(entity, "PNPDeviceID")?;
+            let id = |key: &str| {
+                address
+                    .split(['\\', '&'])
+                    .find_map(|part| part.strip_prefix(key))
+                    .map(str::to_ascii_lowercase)
+            };
+
+            Some(PciDevice {
+                vendor_id: id("VEN_")?,
+                device_id: id("DEV_")?,
+                class: json_string(entity, "PNPClass").unwrap_or_else(|| "Unknown".into()),
+                vendor: json_string(entity, "Manufacturer"),
+                device: json_string(entity, "Name"),
+                driver: json_string(entity, "Service"),
+                address,
+            })
+        })
+        .collect()
+}
+
+/// The names of the PCI base classes, for when no ID database is installed.
+fn base_class_name(class: u8) -> &'static str {
+    match class {
+        0x00 => "Unclassified device",
+        0x01 => "Mass storage controller",
+        0x02 => "Network controller",
+        0x03 => "Display controller",
+        0x04 => "Multimedia controller",
+        0x05 => "Memory controller",
+        0x06 => "Bridge",
+        0x07 => "Communication controller",
+        0x08 => "Generic system peripheral",
+        0x09 => "Input device controller",
+        0x0a => "Docking station",
+        0x0b => "Processor",
+        0x0c => "Serial bus controller",
+        0x0d => "Wireless controller",
+        0x0e => "Intelligent controller",
+        0x0f => "Satellite communications controller",
+        0x10 => "Encryption controller",
+        0x11 => "Signal processing controller",
+        0x12 => "Processing accelerators",
+        0x13 => "Non-Essential Instrumentation",
+        0x40 => "Coprocessor",
+        _ => "Unassigned class",
+    }
+}
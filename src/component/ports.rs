@@ -0,0 +1,333 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_objects, json_string},
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::HashMap,
+        fs,
+        net::{Ipv4Addr, Ipv6Addr},
+    },
+};
+
+/// The sockets waiting for connections or datagrams.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PortsInfo {
+    pub sockets: Vec<ListeningSocket>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ListeningSocket {
+    /// `tcp` or `udp`.
+    pub protocol: String,
+    /// The address bound to, `0.0.0.0` or `::` for every address.
+    pub address: String,
+    pub port: u16,
+    /// The owning process, when permissions allow finding it.
+    pub pid: Option<u32>,
+    pub process: Option<String>,
+}
+
+/// The `st` column of `/proc/net/tcp` for `TCP_LISTEN`.
+const TCP_LISTEN: &str = "0A";
+
+impl PortsInfo {
+    pub fn collect() -> Self {
+        let mut sockets = if cfg!(any(target_os = "linux", target_os = "android")) {
+            proc_sockets()
+        } else if cfg!(target_os = "windows") {
+            windows_sockets()
+        } else {
+            lsof_sockets()
+        };
+        sockets.sort_by(|a, b| {
+            (a.port, &a.protocol, &a.address).cmp(&(b.port, &b.protocol, &b.address))
+        });
+        sockets.dedup_by(|a, b| {
+            (a.port, &a.protocol, &a.address, a.pid) == (b.port, &b.protocol, &b.address, b.pid)
+        });
+
+        Self { sockets }
+    }
+
+    /// Keeps the sockets bound to `port` only.
+    pub fn retain_port(&mut self, port: u16) {
+        self.sockets.retain(|socket| socket.port == port);
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Port Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.sockets.is_empty() {
+            options.line("  Listening Sockets: None");
+            return;
+        }
+
+        // Rows are printed as is: IPv6 addresses contain `:`.
+        println!(
+            "  {:<5}  {:<40}  {:>5}  Process",
+            "Proto", "Address", "Port"
+        );
+        for socket in &self.sockets {
+            let process = match (&socket.process, socket.pid) {
+                (Some(name), Some(pid)) => format!("{} ({})", name, pid),
+                (None, Some(pid)) => pid.to_string(),
+                (Some(name), None) => name.clone(),
+                (None, None) => "Unknown".into(),
+            };
+            println!(
+                "  {:<5}  {:<40}  {:>5}  {}",
+                socket.protocol, socket.address, socket.port, process
+            );
+        }
+    }
+}
+
+/// Reads the socket tables under `/proc/net`.
+fn proc_sockets() -> Vec<ListeningSocket> {
+    let owners = socket_owners();
+    let mut sockets = Vec::new();
+
+    for (file, protocol) in [
+        ("/proc/net/tcp", "tcp"),
+        ("/proc/net/tcp6", "tcp"),
+        ("/proc/net/udp", "udp"),
+        ("/proc/net/udp6", "udp"),
+    ] {
+        if let Ok(table) = fs::read_to_string(file) {
+            sockets.extend(parse_proc_table(&table, protocol, &owners));
+        }
+    }
+    sockets
+}
+
+/// Parses one socket table, whose rows read
+/// `0: 0100007F:0277 00000000:0000 0A ... 12345`, the last field being the
+/// inode the owning process holds open.
+fn parse_proc_table(
+    table: &str,
+    protocol: &str,
+    owners: &HashMap<u64, (u32, Option<String>)>,
+) -> Vec<ListeningSocket> {
+    let mut sockets = Vec::new();
+    for line in table.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (Some(local), Some(remote), Some(state), Some(inode)) =
+            (fields.get(1), fields.get(2), fields.get(3), fields.get(9))
+        else {
+            continue;
+        };
+        // Unconnected UDP sockets have no remote end.
+        let listening = match protocol {
+            "tcp" => *state == TCP_LISTEN,
+            _ => remote.ends_with(":0000"),
+        };
+        let Some((address, port)) = local.split_once(':') else {
+            continue;
+        };
+        let (Some(address), Ok(port)) = (proc_address(address), u16::from_str_radix(port, 16))
+        else {
+            continue;
+        };
+        if !listening || port == 0 {
+            continue;
+        }
+        let owner = inode.parse().ok().and_then(|inode| owners.get(&inode));
+
+        sockets.push(ListeningSocket {
+            protocol: protocol.into(),
+            address,
+            port,
+            pid: owner.map(|(pid, _)| *pid),
+            process: owner.and_then(|(_, name)| name.clone()),
+        });
+    }
+    sockets
+}
+
+/// Addresses are printed as 32-bit words in host byte order, one word for
+/// IPv4 and four for IPv6.
+fn proc_address(hex: &str) -> Option<String> {
+    let word = |hex: &str| u32::from_str_radix(hex, 16).ok().map(u32::from_be);
+    match hex.len() {
+        8 => Some(Ipv4Addr::from(word(hex)?).to_string()),
+        32 => {
+            let mut octets = [0u8; 16];
+            for (index, chunk) in octets.chunks_mut(4).enumerate() {
+                let start = index * 8;
+                chunk.copy_from_slice(&word(hex.get(start..start + 8)?)?.to_be_bytes());
+            }
+            Some(Ipv6Addr::from(octets).to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Maps socket inodes to the process holding them, by the `socket:[12345]`
+/// links under `/proc/<pid>/fd`. Only processes of the same user are
+/// readable without privileges.
+fn socket_owners() -> HashMap<u64, (u32, Option<String>)> {
+    let mut owners = HashMap::new();
+    let Ok(processes) = fs::read_dir("/proc") else {
+        return owners;
+    };
+
+    for process in processes.filter_map(Result::ok) {
+        let Ok(pid) = process.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        let Ok(descriptors) = fs::read_dir(process.path().join("fd")) else {
+            continue;
+        };
+        let name = fs::read_to_string(process.path().join("comm"))
+            .ok()
+            .map(|name| name.trim().to_string());
+        for descriptor in descriptors.filter_map(Result::ok) {
+            let Ok(target) = fs::read_link(descriptor.path()) else {
+                continue;
+            };
+            let inode = target
+                .to_string_lossy()
+                .strip_prefix("socket:[")
+                .and_then(|inode| inode.strip_suffix(']'))
+                .and_then(|inode| inode.parse().ok());
+            if let Some(inode) = inode {
+                owners.entry(inode).or_insert_with(|| (pid, name.clone()));
+            }
+        }
+    }
+    owners
+}
+
+/// Parses `lsof` on macOS and the BSDs, whose rows read
+/// `sshd 412 root 3u IPv6 0x1234 0t0 TCP *:22 (LISTEN)`.
+fn lsof_sockets() -> Vec<ListeningSocket> {
+    let Some(output) = command_output("lsof", &["-nP", "-iTCP", "-sTCP:LISTEN", "-iUDP"]) else {
+        return Vec::new();
+    };
+
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let protocol = match *fields.get(7)? {
+                "TCP" => "tcp",
+                "UDP" => "udp",
+                _ => return None,
+            };
+            // Connected UDP sockets name their peer after `->`.
+            let name = *fields.get(8)?;
+            if name.contains("->") {
+                return None;
+            }
+            let (address, port) = name.rsplit_once(':')?;
+            let address = match address {
+                "*" if fields.get(4) == Some(&"IPv6") => "::",
+                "*" => "0.0.0.0",
+                address => address.trim_matches(['[', ']']),
+            };
+
+            Some(ListeningSocket {
+                protocol: protocol.into(),
+                address: address.to_string(),
+                port: port.parse().ok()?,
+                pid: fields.get(1)?.parse().ok(),
+                process: Some(fields[0].to_string()),
+            })
+        })
+        .collect()
+}
+
+fn windows_sockets() -> Vec<ListeningSocket> {
+    let Some(output) = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "$names = @{}; Get-Process | ForEach-Object { $names[$_.Id] = $_.ProcessName }; \
+             @(Get-NetTCPConnection -State Listen | \
+               Select-Object @{n='Protocol';e={'tcp'}},LocalAddress,LocalPort,OwningProcess) + \
+             @(Get-NetUDPEndpoint | \
+               Select-Object @{n='Protocol';e={'udp'}},LocalAddress,LocalPort,OwningProcess) | \
+             Select-Object *,@{n='ProcessName';e={$names[[int]$_.OwningProcess]}} | \
+             ConvertTo-Json",
+        ],
+    ) else {
+        return Vec::new();
+    };
+
+    json_objects(&output)
+        .iter()
+        .filter_map(|socket| {
+            Some(ListeningSocket {
+                protocol: json_string(socket, "Protocol")?,
+                address: json_string(socket, "LocalAddress")?,
+                port: socket["LocalPort"].as_u64()? as u16,
+                pid: socket["OwningProcess"].as_u64().map(|pid| pid as u32),
+                process: json_string(socket, "ProcessName"),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode";
+
+    #[test]
+    fn decodes_addresses_in_host_byte_order() {
+        assert_eq!(proc_address("0100007F").as_deref(), Some("127.0.0.1"));
+        assert_eq!(proc_address("00000000").as_deref(), Some("0.0.0.0"));
+        assert_eq!(
+            proc_address("00000000000000000000000001000000").as_deref(),
+            Some("::1")
+        );
+        assert_eq!(
+            proc_address("B80D0120000000000000000001000000").as_deref(),
+            Some("2001:db8::1")
+        );
+        assert_eq!(proc_address("0100007"), None);
+        assert_eq!(proc_address("0100007G"), None);
+    }
+
+    #[test]
+    fn keeps_listening_tcp_sockets() {
+        let table = format!(
+            "{}\n{}\n{}\n",
+            HEADER,
+            "   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 4242 1 0000000000000000 100 0 0 10 0",
+            "   1: 0100007F:8E3A 0100007F:0277 01 00000000:00000000 00:00000000 00000000  1000        0 4343 1 0000000000000000 20 4 30 10 -1",
+        );
+        let owners = HashMap::from([(4242, (412, Some("cupsd".to_string())))]);
+        let sockets = parse_proc_table(&table, "tcp", &owners);
+        assert_eq!(sockets.len(), 1);
+        assert_eq!(sockets[0].protocol, "tcp");
+        assert_eq!(sockets[0].address, "127.0.0.1");
+        assert_eq!(sockets[0].port, 631);
+        assert_eq!(sockets[0].pid, Some(412));
+        assert_eq!(sockets[0].process.as_deref(), Some("cupsd"));
+    }
+
+    #[test]
+    fn keeps_unconnected_udp6_sockets() {
+        let table = format!(
+            "{}\n{}\n{}\n",
+            HEADER,
+            "  10: 00000000000000000000000000000000:14E9 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000   107        0 5151 2 0000000000000000 0",
+            "  11: 00000000000000000000000001000000:D431 00000000000000000000000001000000:0035 01 00000000:00000000 00:00000000 00000000  1000        0 5252 2 0000000000000000 0",
+        );
+        let sockets = parse_proc_table(&table, "udp", &HashMap::new());
+        assert_eq!(sockets.len(), 1);
+        assert_eq!(sockets[0].address, "::");
+        assert_eq!(sockets[0].port, 5353);
+        assert_eq!(sockets[0].pid, None);
+    }
+}
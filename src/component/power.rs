@@ -0,0 +1,118 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, is_bsd, read_trimmed, sysctl},
+    },
+    serde::{Deserialize, Serialize},
+    std::path::Path,
+};
+
+/// The power settings that throttle the machine: the CPU frequency governor
+/// on Linux, and the system power profile or plan.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PowerInfo {
+    /// The cpufreq governor of the first CPU, such as `powersave` or
+    /// `schedutil`.
+    pub governor: Option<String>,
+    /// The cpufreq driver, such as `intel_pstate` or `amd-pstate-epp`.
+    pub driver: Option<String>,
+    /// The energy/performance hint of that driver, such as
+    /// `balance_performance`.
+    pub energy_preference: Option<String>,
+    /// `power-saver`, `balanced` or `performance` on Linux, or the name of
+    /// the Windows power plan.
+    pub profile: Option<String>,
+    /// Whether macOS Low Power Mode is on.
+    pub low_power_mode: Option<bool>,
+}
+
+const CPUFREQ: &str = "/sys/devices/system/cpu/cpu0/cpufreq";
+
+impl PowerInfo {
+    pub fn collect() -> Self {
+        let cpufreq = Path::new(CPUFREQ);
+        let cpufreq = |file: &str| read_trimmed(cpufreq.join(file));
+        let mut power = Self {
+            governor: cpufreq("scaling_governor"),
+            driver: cpufreq("scaling_driver"),
+            energy_preference: cpufreq("energy_performance_preference"),
+            profile: None,
+            low_power_mode: None,
+        };
+
+        if cfg!(target_os = "windows") {
+            power.profile = windows_plan();
+        } else if cfg!(target_os = "macos") {
+            power.low_power_mode = macos_low_power_mode();
+        } else if is_bsd() {
+            // FreeBSD names the driver as a device, such as
+            // `hwpstate_intel0`, whose hint runs from 0 (performance) to
+            // 100 (power saving).
+            power.driver = sysctl("dev.cpufreq.0.freq_driver");
+            power.energy_preference = sysctl("dev.hwpstate_intel.0.epp");
+        } else {
+            // The ACPI platform profile is what power-profiles-daemon and
+            // tuned set where the firmware supports it.
+            power.profile = command_output("powerprofilesctl", &["get"])
+                .or_else(|| read_trimmed("/sys/firmware/acpi/platform_profile"));
+        }
+        power
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Power Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        let driver = self
+            .driver
+            .as_ref()
+            .map(|driver| format!(" ({})", driver))
+            .unwrap_or_default();
+        options.line(format_args!(
+            "  Governor   : {}{}",
+            self.governor.as_deref().unwrap_or("None"),
+            driver
+        ));
+        if let Some(preference) = &self.energy_preference {
+            options.line(format_args!("  Preference : {}", preference));
+        }
+        if let Some(low_power_mode) = self.low_power_mode {
+            options.line(options.warn_if(
+                low_power_mode,
+                format!(
+                    "  Low Power  : {}",
+                    if low_power_mode { "On" } else { "Off" }
+                ),
+            ));
+        } else {
+            options.line(options.warn_if(
+                matches!(self.profile.as_deref(), Some("power-saver" | "low-power")),
+                format!(
+                    "  Profile    : {}",
+                    self.profile.as_deref().unwrap_or("None")
+                ),
+            ));
+        }
+    }
+}
+
+/// `powercfg` prints `Power Scheme GUID: 381b4222-...  (Balanced)`, the
+/// name in the system language.
+fn windows_plan() -> Option<String> {
+    let output = command_output("powercfg", &["/getactivescheme"])?;
+    let (_, name) = output.trim().rsplit_once('(')?;
+    Some(name.trim_end_matches(')').to_string())
+}
+
+/// `pmset -g` lists the settings of the active power source, among them
+/// ` lowpowermode 1`.
+fn macos_low_power_mode() -> Option<bool> {
+    let output = command_output("pmset", &["-g"])?;
+    output.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("lowpowermode")?;
+        Some(value.trim() == "1")
+    })
+}
@@ -0,0 +1,69 @@
+use {
+    crate::util::read_trimmed,
+    serde::{Deserialize, Serialize},
+};
+
+/// How much time tasks spent stalled waiting on a resource, from the
+/// pressure stall information of Linux 4.20 and later.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Pressure {
+    /// Time at least one task was stalled.
+    pub some: PressureAverages,
+    /// Time every task was stalled at once, which the CPU line lacks on
+    /// older kernels.
+    pub full: Option<PressureAverages>,
+}
+
+/// The share of wall time stalled, in percent, averaged over the last 10
+/// seconds, minute and 5 minutes.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PressureAverages {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+    /// All stalled time since boot.
+    pub total_usec: u64,
+}
+
+impl Pressure {
+    /// Reads `/proc/pressure/<resource>`, where the resource is `cpu`,
+    /// `memory` or `io`. Returns `None` on kernels without PSI.
+    pub fn read(resource: &str) -> Option<Self> {
+        let pressure = read_trimmed(format!("/proc/pressure/{}", resource))?;
+        let line = |kind: &str| {
+            pressure
+                .lines()
+                .find_map(|line| line.strip_prefix(kind)?.strip_prefix(' '))
+                .and_then(averages)
+        };
+
+        Some(Self {
+            some: line("some")?,
+            full: line("full"),
+        })
+    }
+
+    /// The 10 second averages, as in `some 0.18%, full 0.17%`.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("some {:.2}%", self.some.avg10);
+        if let Some(full) = &self.full {
+            summary.push_str(&format!(", full {:.2}%", full.avg10));
+        }
+        summary
+    }
+}
+
+/// Parses `avg10=0.18 avg60=0.27 avg300=0.22 total=18896630`.
+fn averages(line: &str) -> Option<PressureAverages> {
+    let field = |name: &str| {
+        line.split_whitespace()
+            .find_map(|field| field.strip_prefix(name)?.strip_prefix('='))
+    };
+
+    Some(PressureAverages {
+        avg10: field("avg10")?.parse().ok()?,
+        avg60: field("avg60")?.parse().ok()?,
+        avg300: field("avg300")?.parse().ok()?,
+        total_usec: field("total")?.parse().ok()?,
+    })
+}
@@ -0,0 +1,252 @@
+use {
+    crate::format::DisplayOptions,
+    regex::Regex,
+    serde::{Deserialize, Serialize},
+    std::{
+        cmp::Ordering,
+        collections::{BTreeMap, HashSet},
+        thread,
+    },
+    sysinfo::{
+        ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind, Users,
+        MINIMUM_CPU_UPDATE_INTERVAL,
+    },
+};
+
+/// Sort key for the process listing, heaviest first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProcessSort {
+    #[default]
+    Cpu,
+    #[value(alias = "mem")]
+    Memory,
+}
+
+impl ProcessSort {
+    /// The key after this one, wrapping around, as the dashboard cycles
+    /// through them.
+    pub fn next(self) -> Self {
+        match self {
+            ProcessSort::Cpu => ProcessSort::Memory,
+            ProcessSort::Memory => ProcessSort::Cpu,
+        }
+    }
+
+    /// Orders processes heaviest first.
+    pub fn compare(self, a: &ProcessEntry, b: &ProcessEntry) -> Ordering {
+        match self {
+            ProcessSort::Cpu => b.cpu_usage.total_cmp(&a.cpu_usage),
+            ProcessSort::Memory => b.memory_bytes.cmp(&a.memory_bytes),
+        }
+    }
+}
+
+/// How the process listing is filtered, sorted and trimmed.
+#[derive(Clone, Debug, Default)]
+pub struct ProcessOptions {
+    pub sort: ProcessSort,
+    /// Only keep the `top` heaviest processes.
+    pub top: Option<usize>,
+    /// Only keep processes whose name matches.
+    pub name: Option<Regex>,
+    /// Only keep processes owned by this user.
+    pub user: Option<String>,
+    /// Only keep these processes, if any are given.
+    pub pids: Vec<u32>,
+}
+
+impl ProcessOptions {
+    fn matches(&self, process: &ProcessEntry) -> bool {
+        self.name
+            .as_ref()
+            .is_none_or(|name| name.is_match(&process.name))
+            && self
+                .user
+                .as_ref()
+                .is_none_or(|user| process.user.as_ref() == Some(user))
+            && (self.pids.is_empty() || self.pids.contains(&process.pid))
+    }
+}
+
+/// Running processes.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    pub processes: Vec<ProcessEntry>,
+}
+
+/// A single running process.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProcessEntry {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    /// The owner, when it can be told.
+    pub user: Option<String>,
+    /// The full command line, which kernel threads do not have.
+    pub command: Option<String>,
+    pub cpu_usage: f32,
+    /// The resident set size.
+    pub memory_bytes: u64,
+    pub status: String,
+}
+
+impl ProcessInfo {
+    pub fn collect(options: &ProcessOptions) -> Self {
+        let mut sys = System::new();
+        let users = Users::new_with_refreshed_list();
+        let refresh_kind = ProcessRefreshKind::new()
+            .with_cpu()
+            .with_memory()
+            .with_user(UpdateKind::OnlyIfNotSet)
+            .with_cmd(UpdateKind::OnlyIfNotSet);
+
+        // CPU usage is computed from the difference between two refreshes.
+        sys.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+        thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+
+        let mut processes: Vec<ProcessEntry> = sys
+            .processes()
+            .values()
+            .filter(|process| process.thread_kind().is_none())
+            .map(|process| ProcessEntry {
+                pid: process.pid().as_u32(),
+                parent_pid: process.parent().map(|parent| parent.as_u32()),
+                name: process.name().to_string_lossy().into_owned(),
+                user: process
+                    .user_id()
+                    .and_then(|uid| users.get_user_by_id(uid))
+                    .map(|user| user.name().to_string()),
+                command: Some(
+                    process
+                        .cmd()
+                        .iter()
+                        .map(|arg| arg.to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                )
+                .filter(|command| !command.is_empty()),
+                cpu_usage: process.cpu_usage(),
+                memory_bytes: process.memory(),
+                status: process.status().to_string(),
+            })
+            .filter(|process| options.matches(process))
+            .collect();
+
+        processes.sort_by(|a, b| options.sort.compare(a, b));
+
+        if let Some(top) = options.top {
+            processes.truncate(top);
+        }
+
+        Self { processes }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        self.print_rows(
+            options,
+            self.processes
+                .iter()
+                .map(|process| (process, String::new())),
+        );
+    }
+
+    /// Like [`display`](Self::display), but indents each process under its
+    /// parent, children in PID order.
+    pub fn display_tree(&self, options: &DisplayOptions) {
+        let pids: HashSet<u32> = self.processes.iter().map(|process| process.pid).collect();
+        let mut children: BTreeMap<Option<u32>, Vec<&ProcessEntry>> = BTreeMap::new();
+        for process in &self.processes {
+            // Processes whose parent is gone, left out or themselves, like
+            // the Windows Idle process, become roots.
+            let parent = process
+                .parent_pid
+                .filter(|parent| pids.contains(parent) && *parent != process.pid);
+            children.entry(parent).or_default().push(process);
+        }
+        for siblings in children.values_mut() {
+            siblings.sort_by_key(|process| process.pid);
+        }
+
+        // Processes in a cycle of parents are never reached from a root, so
+        // after the roots each cycle is printed from its lowest PID.
+        let mut unreached: Vec<&ProcessEntry> = self.processes.iter().collect();
+        unreached.sort_by_key(|process| process.pid);
+        let starts = children.get(&None).into_iter().flatten().copied();
+
+        let mut rows = Vec::with_capacity(self.processes.len());
+        let mut printed = HashSet::new();
+        for start in starts.chain(unreached) {
+            let mut stack = vec![(start, 0)];
+            while let Some((process, depth)) = stack.pop() {
+                if !printed.insert(process.pid) {
+                    continue;
+                }
+                let prefix = if depth == 0 {
+                    String::new()
+                } else {
+                    format!("{}└─ ", "   ".repeat(depth - 1))
+                };
+                rows.push((process, prefix));
+                if let Some(kids) = children.get(&Some(process.pid)) {
+                    stack.extend(kids.iter().rev().map(|kid| (*kid, depth + 1)));
+                }
+            }
+        }
+        self.print_rows(options, rows.into_iter());
+    }
+
+    /// Prints one row per process, the command after `prefix`.
+    fn print_rows<'a>(
+        &self,
+        options: &DisplayOptions,
+        rows: impl Iterator<Item = (&'a ProcessEntry, String)>,
+    ) {
+        options.line(format_args!(
+            "{}Process Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.processes.is_empty() {
+            options.line("  Processes: None");
+            return;
+        }
+
+        // Rows are printed as is: process names may contain `:`.
+        let user_width = self
+            .processes
+            .iter()
+            .filter_map(|process| process.user.as_ref().map(String::len))
+            .max()
+            .unwrap_or(0)
+            .max("User".len());
+        println!(
+            "  {:>8}  {:<user_width$}  {:>6}  {:>16}  {:<10}  Command",
+            "PID",
+            "User",
+            "CPU %",
+            "RSS",
+            "Status",
+            user_width = user_width
+        );
+        for (process, prefix) in rows {
+            println!(
+                "  {:>8}  {:<user_width$}  {:>6.1}  {:>16}  {:<10}  {}{}",
+                process.pid,
+                process.user.as_deref().unwrap_or("-"),
+                process.cpu_usage,
+                options.bytes(process.memory_bytes),
+                process.status,
+                prefix,
+                // Keep arguments with line breaks on one row.
+                process
+                    .command
+                    .as_deref()
+                    .unwrap_or(&process.name)
+                    .replace(['\n', '\r'], " "),
+                user_width = user_width
+            );
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use {
+    crate::format::DisplayOptions,
+    serde::{Deserialize, Serialize},
+    std::collections::HashMap,
+    toml::Value,
+};
+
+/// A dependency declared in `Cargo.toml` and the version locked for it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DependencyInfo {
+    pub name: String,
+    pub specified_version: String,
+    pub resolved_version: String,
+}
+
+/// The Cargo project in the current directory.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProjectInfo {
+    pub name: String,
+    pub version: String,
+    pub dependencies: Vec<DependencyInfo>,
+}
+
+impl ProjectInfo {
+    pub fn collect() -> Self {
+        let cargo_toml_path = std::path::Path::new("Cargo.toml");
+        let cargo_lock_path = std::path::Path::new("Cargo.lock");
+
+        let mut name = "Unknown".to_string();
+        let mut version = "Unknown".to_string();
+        let mut dependencies = Vec::new();
+
+        if cargo_toml_path.exists() {
+            if let Ok(cargo_toml_content) = std::fs::read_to_string(cargo_toml_path) {
+                if let Ok(cargo_toml_value) = cargo_toml_content.parse::<Value>() {
+                    let package = cargo_toml_value.get("package");
+                    name = package
+                        .and_then(|pkg| pkg.get("name"))
+                        .and_then(|name| name.as_str())
+                        .unwrap_or("Unknown")
+                        .to_string();
+                    version = package
+                        .and_then(|pkg| pkg.get("version"))
+                        .and_then(|version| version.as_str())
+                        .unwrap_or("Unknown")
+                        .to_string();
+
+                    let mut dep_versions = HashMap::new();
+
+                    if cargo_lock_path.exists() {
+                        if let Ok(content) = std::fs::read_to_string(cargo_lock_path) {
+                            if let Ok(cargo_lock_value) = content.parse::<Value>() {
+                                if let Some(packages) =
+                                    cargo_lock_value.get("package").and_then(|v| v.as_array())
+                                {
+                                    for package in packages {
+                                        if let Some(name) =
+                                            package.get("name").and_then(|v| v.as_str())
+                                        {
+                                            if let Some(version) =
+                                                package.get("version").and_then(|v| v.as_str())
+                                            {
+                                                dep_versions
+                                                    .insert(name.to_string(), version.to_string());
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(deps) = cargo_toml_value
+                        .get("dependencies")
+                        .and_then(|v| v.as_table())
+                    {
+                        for (dep_name, value) in deps {
+                            let specified_version = if let Some(version_str) = value.as_str() {
+                                version_str.to_string()
+                            } else if let Some(table) = value.as_table() {
+                                if let Some(version_str) =
+                                    table.get("version").and_then(|v| v.as_str())
+                                {
+                                    version_str.to_string()
+                                } else {
+                                    "Unknown".into()
+                                }
+                            } else {
+                                "Unknown".into()
+                            };
+
+                            let resolved_version = dep_versions
+                                .get(dep_name)
+                                .cloned()
+                                .unwrap_or_else(|| "Unknown".into());
+
+                            dependencies.push(DependencyInfo {
+                                name: dep_name.clone(),
+                                specified_version,
+                                resolved_version,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Self {
+            name,
+            version,
+            dependencies,
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Project Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        options.line(format_args!("  Name    : {}", self.name));
+        options.line(format_args!("  Version : {}", self.version));
+
+        if self.dependencies.is_empty() {
+            options.line("  Dependencies: None");
+        } else {
+            options.line("  Dependencies:");
+            for dep in &self.dependencies {
+                options.line(format_args!(
+                    "    - {} {} ({})",
+                    dep.name, dep.specified_version, dep.resolved_version
+                ));
+            }
+        }
+    }
+}
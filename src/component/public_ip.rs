@@ -0,0 +1,83 @@
+use {
+    crate::{format::DisplayOptions, util::command_output_timeout},
+    serde::{Deserialize, Serialize},
+    std::{net::IpAddr, time::Duration},
+};
+
+/// Where the public address is asked for unless configured otherwise. It
+/// answers over both IPv4 and IPv6 with the bare address.
+pub const DEFAULT_ENDPOINT: &str = "https://api64.ipify.org";
+
+/// How long each lookup may take.
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The addresses the machine reaches the internet from, as seen by an
+/// outside service. Collecting this contacts that service, which is why the
+/// section is never included by default.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PublicIpInfo {
+    pub endpoint: String,
+    pub ipv4: Option<String>,
+    pub ipv6: Option<String>,
+    /// Whether the endpoint answered over either protocol.
+    pub connected: bool,
+}
+
+impl PublicIpInfo {
+    /// Asks `endpoint`, which must answer with the bare address, over IPv4
+    /// and IPv6 in turn.
+    pub fn collect(endpoint: &str) -> Self {
+        let ipv4 = lookup(endpoint, "-4").filter(IpAddr::is_ipv4);
+        let ipv6 = lookup(endpoint, "-6").filter(IpAddr::is_ipv6);
+
+        Self {
+            endpoint: endpoint.to_string(),
+            connected: ipv4.is_some() || ipv6.is_some(),
+            ipv4: ipv4.map(|address| address.to_string()),
+            ipv6: ipv6.map(|address| address.to_string()),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Public IP Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        // Rows are printed as is: the endpoint and IPv6 addresses contain `:`.
+        println!("  Endpoint     : {}", self.endpoint);
+        options.line(options.warn_if(
+            !self.connected,
+            format!(
+                "  Connectivity : {}",
+                if self.connected { "online" } else { "offline" }
+            ),
+        ));
+        options.line(format_args!(
+            "  IPv4         : {}",
+            self.ipv4.as_deref().unwrap_or("None")
+        ));
+        println!(
+            "  IPv6         : {}",
+            self.ipv6.as_deref().unwrap_or("None")
+        );
+    }
+}
+
+/// Fetches `endpoint` with `curl`, forcing the protocol with `family`.
+fn lookup(endpoint: &str, family: &str) -> Option<IpAddr> {
+    let timeout = LOOKUP_TIMEOUT.as_secs().to_string();
+    let output = command_output_timeout(
+        "curl",
+        &[
+            "--fail",
+            "--silent",
+            family,
+            "--max-time",
+            &timeout,
+            endpoint,
+        ],
+        LOOKUP_TIMEOUT + Duration::from_secs(1),
+    )?;
+    output.trim().parse().ok()
+}
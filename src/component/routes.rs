@@ -0,0 +1,359 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_objects, json_string},
+    },
+    serde::{Deserialize, Serialize},
+    std::net::{Ipv4Addr, Ipv6Addr},
+};
+
+/// The main routing table, IPv4 routes first.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RoutesInfo {
+    pub routes: Vec<RouteEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RouteEntry {
+    /// `ipv4` or `ipv6`.
+    pub family: String,
+    /// The network in CIDR notation, `0.0.0.0/0` or `::/0` for the default
+    /// route.
+    pub destination: String,
+    /// The next hop, or `None` for networks reached directly.
+    pub gateway: Option<String>,
+    pub interface: Option<String>,
+    pub metric: Option<u32>,
+}
+
+impl RouteEntry {
+    pub fn is_default(&self) -> bool {
+        self.destination == "0.0.0.0/0" || self.destination == "::/0"
+    }
+}
+
+/// Linux route flags, from `linux/route.h` and `linux/ipv6_route.h`.
+const RTF_UP: u32 = 0x0001;
+const RTF_REJECT: u32 = 0x0200;
+const RTF_LOCAL: u32 = 0x8000_0000;
+
+impl RoutesInfo {
+    pub fn collect() -> Self {
+        let mut routes = if cfg!(target_os = "windows") {
+            windows_routes()
+        } else if cfg!(any(target_os = "linux", target_os = "android")) {
+            let mut routes = proc_ipv4_routes();
+            routes.extend(proc_ipv6_routes());
+            routes
+        } else {
+            netstat_routes()
+        };
+        routes.sort_by_key(|route| (route.family.clone(), !route.is_default(), route.metric));
+
+        Self { routes }
+    }
+
+    /// The default routes, most preferred first.
+    pub fn default_gateways(&self) -> impl Iterator<Item = &RouteEntry> {
+        self.routes.iter().filter(|route| route.is_default())
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Route Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        let gateways: Vec<String> = self
+            .default_gateways()
+            .map(|route| {
+                format!(
+                    "{} via {}",
+                    route.gateway.as_deref().unwrap_or("link"),
+                    route.interface.as_deref().unwrap_or("Unknown")
+                )
+            })
+            .collect();
+        // Rows are printed as is: IPv6 addresses contain `:`.
+        if gateways.is_empty() {
+            options.line(options.warn_if(true, "  Default Gateways: None"));
+        } else {
+            println!("  Default Gateways : {}", gateways.join(", "));
+        }
+
+        if self.routes.is_empty() {
+            return;
+        }
+        let width = self
+            .routes
+            .iter()
+            .map(|route| route.destination.len())
+            .max()
+            .unwrap_or(0);
+        println!(
+            "  {:<width$}  {:<25}  {:<12}  Metric",
+            "Destination",
+            "Gateway",
+            "Interface",
+            width = width
+        );
+        for route in &self.routes {
+            println!(
+                "  {:<width$}  {:<25}  {:<12}  {}",
+                route.destination,
+                route.gateway.as_deref().unwrap_or("-"),
+                route.interface.as_deref().unwrap_or("-"),
+                route
+                    .metric
+                    .map(|metric| metric.to_string())
+                    .unwrap_or_else(|| "-".into()),
+                width = width
+            );
+        }
+    }
+}
+
+/// Parses `/proc/net/route`, which holds addresses as little-endian hex:
+///
+/// ```text
+/// Iface  Destination  Gateway   Flags  RefCnt  Use  Metric  Mask      ...
+/// eth0   00000000     0101A8C0  0003   0       0    100     00000000  ...
+/// ```
+fn proc_ipv4_routes() -> Vec<RouteEntry> {
+    std::fs::read_to_string("/proc/net/route")
+        .map(|table| parse_ipv4_routes(&table))
+        .unwrap_or_default()
+}
+
+fn parse_ipv4_routes(table: &str) -> Vec<RouteEntry> {
+    let address = |hex: &str| u32::from_str_radix(hex, 16).ok().map(u32::from_be);
+
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let flags = u32::from_str_radix(fields.get(3)?, 16).ok()?;
+            if flags & RTF_UP == 0 || flags & RTF_REJECT != 0 {
+                return None;
+            }
+            let destination = Ipv4Addr::from(address(fields[1])?);
+            let gateway = Ipv4Addr::from(address(fields[2])?);
+            let prefix = address(fields.get(7)?)?.count_ones();
+
+            Some(RouteEntry {
+                family: "ipv4".into(),
+                destination: format!("{}/{}", destination, prefix),
+                gateway: (!gateway.is_unspecified()).then(|| gateway.to_string()),
+                interface: Some(fields[0].to_string()),
+                metric: fields.get(6)?.parse().ok(),
+            })
+        })
+        .collect()
+}
+
+/// Parses `/proc/net/ipv6_route`, whose rows hold the destination, its
+/// prefix length, the source and its prefix length, the next hop, the
+/// metric, two counters, the flags and the interface, all in hex. Routes of
+/// the local table and multicast are left out.
+fn proc_ipv6_routes() -> Vec<RouteEntry> {
+    std::fs::read_to_string("/proc/net/ipv6_route")
+        .map(|table| parse_ipv6_routes(&table))
+        .unwrap_or_default()
+}
+
+fn parse_ipv6_routes(table: &str) -> Vec<RouteEntry> {
+    let address = |hex: &str| u128::from_str_radix(hex, 16).ok().map(Ipv6Addr::from);
+
+    table
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let flags = u32::from_str_radix(fields.get(8)?, 16).ok()?;
+            let interface = fields.get(9)?.to_string();
+            let destination = address(fields[0])?;
+            if flags & RTF_UP == 0
+                || flags & (RTF_REJECT | RTF_LOCAL) != 0
+                || interface == "lo"
+                || destination.is_multicast()
+            {
+                return None;
+            }
+            let prefix = u8::from_str_radix(fields[1], 16).ok()?;
+            let gateway = address(fields[4])?;
+
+            Some(RouteEntry {
+                family: "ipv6".into(),
+                destination: format!("{}/{}", destination, prefix),
+                gateway: (!gateway.is_unspecified()).then(|| gateway.to_string()),
+                interface: Some(interface),
+                metric: u32::from_str_radix(fields[5], 16).ok(),
+            })
+        })
+        .collect()
+}
+
+/// Parses `netstat -rn` on macOS and the BSDs, whose tables follow an
+/// `Internet:` or `Internet6:` line and a header naming the columns:
+///
+/// ```text
+/// Internet:
+/// Destination        Gateway            Flags               Netif Expire
+/// default            192.168.1.1        UGScg                 en0
+/// ```
+fn netstat_routes() -> Vec<RouteEntry> {
+    let Some(output) = command_output("netstat", &["-rn"]) else {
+        return Vec::new();
+    };
+
+    let mut routes = Vec::new();
+    let mut family = None;
+    let mut interface_column = None;
+    for line in output.lines() {
+        match line.trim() {
+            "Internet:" => family = Some("ipv4"),
+            "Internet6:" => family = Some("ipv6"),
+            _ => {}
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.first() == Some(&"Destination") {
+            interface_column = fields.iter().position(|field| *field == "Netif");
+            continue;
+        }
+        let (Some(family), Some(&destination), Some(&gateway)) =
+            (family, fields.first(), fields.get(1))
+        else {
+            continue;
+        };
+        if fields.len() < 3 {
+            continue;
+        }
+
+        let destination = match (destination, family) {
+            ("default", "ipv4") => "0.0.0.0/0".to_string(),
+            ("default", _) => "::/0".to_string(),
+            (destination, _) => destination.to_string(),
+        };
+        routes.push(RouteEntry {
+            family: family.into(),
+            destination,
+            // Directly attached networks name the link, as in `link#4`.
+            gateway: (!gateway.starts_with("link#") && gateway.contains(['.', ':']))
+                .then(|| gateway.to_string()),
+            interface: interface_column
+                .and_then(|column| fields.get(column))
+                .map(|interface| interface.to_string()),
+            metric: None,
+        });
+    }
+    routes
+}
+
+fn windows_routes() -> Vec<RouteEntry> {
+    let Some(output) = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-NetRoute -PolicyStore ActiveStore | \
+             Select-Object DestinationPrefix,NextHop,InterfaceAlias,RouteMetric,AddressFamily | \
+             ConvertTo-Json",
+        ],
+    ) else {
+        return Vec::new();
+    };
+
+    json_objects(&output)
+        .iter()
+        .filter_map(|route| {
+            let destination = json_string(route, "DestinationPrefix")?;
+            // Leave out host, broadcast and multicast routes.
+            if destination.ends_with("/32")
+                || destination.ends_with("/128")
+                || destination.starts_with("224.")
+                || destination.starts_with("ff00:")
+            {
+                return None;
+            }
+            // `AddressFamily` serializes as 2 for IPv4 and 23 for IPv6.
+            let family = match route["AddressFamily"].as_u64() {
+                Some(23) => "ipv6",
+                _ => "ipv4",
+            };
+            let gateway =
+                json_string(route, "NextHop").filter(|hop| hop != "0.0.0.0" && hop != "::");
+
+            Some(RouteEntry {
+                family: family.into(),
+                destination,
+                gateway,
+                interface: json_string(route, "InterfaceAlias"),
+                metric: route["RouteMetric"].as_u64().map(|metric| metric as u32),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_ipv4_routes() {
+        let routes = parse_ipv4_routes(
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n\
+             eth0\t00000000\t0101A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0\n\
+             eth0\t0001A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0\n\
+             eth1\t0000000A\t00000000\t0000\t0\t0\t0\t000000FF\t0\t0\t0\n\
+             eth1\t0000A8C0\t00000000\t0201\t0\t0\t0\t0000FFFF\t0\t0\t0\n",
+        );
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].family, "ipv4");
+        assert_eq!(routes[0].destination, "0.0.0.0/0");
+        assert_eq!(routes[0].gateway.as_deref(), Some("192.168.1.1"));
+        assert_eq!(routes[0].interface.as_deref(), Some("eth0"));
+        assert_eq!(routes[0].metric, Some(100));
+        assert_eq!(routes[1].destination, "192.168.1.0/24");
+        assert_eq!(routes[1].gateway, None);
+    }
+
+    #[test]
+    fn decodes_ipv6_routes() {
+        let routes = parse_ipv6_routes(
+            "00000000000000000000000000000000 00 00000000000000000000000000000000 00 \
+             fe800000000000000000000000000001 00000400 00000001 00000000 00450003     eth0\n\
+             20010db8000000000000000000000000 40 00000000000000000000000000000000 00 \
+             00000000000000000000000000000000 00000100 00000001 00000000 00000001     eth0\n\
+             00000000000000000000000000000001 80 00000000000000000000000000000000 00 \
+             00000000000000000000000000000000 00000000 00000002 00000000 80200001       lo\n\
+             ff000000000000000000000000000000 08 00000000000000000000000000000000 00 \
+             00000000000000000000000000000000 00000100 00000001 00000000 00000001     eth0\n",
+        );
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].destination, "::/0");
+        assert_eq!(routes[0].gateway.as_deref(), Some("fe80::1"));
+        assert_eq!(routes[0].metric, Some(1024));
+        assert_eq!(routes[1].destination, "2001:db8::/64");
+        assert_eq!(routes[1].gateway, None);
+    }
+
+    #[test]
+    fn finds_the_default_gateways() {
+        let mut routes = parse_ipv4_routes(
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+             eth0\t0001A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\n\
+             eth0\t00000000\t0101A8C0\t0003\t0\t0\t100\t00000000\n",
+        );
+        routes.extend(parse_ipv6_routes(
+            "00000000000000000000000000000000 00 00000000000000000000000000000000 00 \
+             fe800000000000000000000000000001 00000400 00000001 00000000 00450003     eth0\n",
+        ));
+        let info = RoutesInfo { routes };
+        let gateways: Vec<&str> = info
+            .default_gateways()
+            .filter_map(|route| route.gateway.as_deref())
+            .collect();
+        assert_eq!(gateways, ["192.168.1.1", "fe80::1"]);
+    }
+}
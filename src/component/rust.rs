@@ -0,0 +1,137 @@
+use {
+    crate::format::DisplayOptions,
+    rustc_version::{version, version_meta, Channel},
+    serde::{Deserialize, Serialize},
+    std::process::Command,
+    which::which,
+};
+
+/// The Rust compiler and the toolchain tools found on `PATH`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RustInfo {
+    pub compiler: CompilerInfo,
+    pub cargo: ToolInfo,
+    pub rustup: ToolInfo,
+}
+
+impl RustInfo {
+    pub fn collect() -> Self {
+        Self {
+            compiler: CompilerInfo::collect(),
+            cargo: ToolInfo::collect("Cargo", "cargo"),
+            rustup: ToolInfo::collect("Rustup", "rustup"),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Rust Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        self.compiler.display(options);
+        self.cargo.display(options);
+        self.rustup.display(options);
+    }
+}
+
+/// Details of the active `rustc`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CompilerInfo {
+    pub version: String,
+    pub host: String,
+    pub release: String,
+    pub commit_hash: String,
+    pub commit_date: String,
+    pub channel: String,
+}
+
+impl CompilerInfo {
+    pub fn collect() -> Self {
+        match version() {
+            Ok(ver) => {
+                let meta = version_meta().unwrap();
+                Self {
+                    version: ver.to_string(),
+                    host: meta.host.clone(),
+                    release: meta.short_version_string.clone(),
+                    commit_hash: meta.commit_hash.clone().unwrap_or_else(|| "Unknown".into()),
+                    commit_date: meta.commit_date.clone().unwrap_or_else(|| "Unknown".into()),
+                    channel: match meta.channel {
+                        Channel::Dev => "Dev".into(),
+                        Channel::Nightly => "Nightly".into(),
+                        Channel::Beta => "Beta".into(),
+                        Channel::Stable => "Stable".into(),
+                    },
+                }
+            }
+            Err(_) => Self {
+                version: "rustc not found.".into(),
+                host: "Unknown".into(),
+                release: "Unknown".into(),
+                commit_hash: "Unknown".into(),
+                commit_date: "Unknown".into(),
+                channel: "Unknown".into(),
+            },
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "  {}Compiler{}",
+            "-".repeat(10),
+            "-".repeat(10)
+        ));
+        options.line(format_args!("    Version     : {}", self.version));
+        options.line(format_args!("    Host        : {}", self.host));
+        options.line(format_args!("    Release     : {}", self.release));
+        options.line(format_args!("    Commit Hash : {}", self.commit_hash));
+        options.line(format_args!("    Commit Date : {}", self.commit_date));
+        options.line(format_args!("    Channel     : {}", self.channel));
+    }
+}
+
+/// A toolchain tool and its version.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ToolInfo {
+    pub name: String,
+    pub version: String,
+}
+
+impl ToolInfo {
+    pub fn collect(name: &str, command: &str) -> Self {
+        if which(command).is_ok() {
+            let output = Command::new(command).arg("--version").output().ok();
+            if let Some(output) = output {
+                let version_info = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                let version = extract_version_number(&version_info);
+                Self {
+                    name: name.into(),
+                    version,
+                }
+            } else {
+                Self {
+                    name: name.into(),
+                    version: format!("Failed to get {} version.", name),
+                }
+            }
+        } else {
+            Self {
+                name: name.into(),
+                version: format!("{} not found.", name),
+            }
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!("  {}{}", "-".repeat(10), "-".repeat(10)));
+        options.line(format_args!("    {} Version: {}", self.name, self.version));
+    }
+}
+
+fn extract_version_number(info: &str) -> String {
+    info.split_whitespace()
+        .nth(1)
+        .unwrap_or("Unknown")
+        .to_string()
+}
@@ -0,0 +1,373 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, parse_cpu_list, read_trimmed},
+    },
+    serde::{Deserialize, Serialize},
+    std::{fs, path::Path},
+    sysinfo::Components,
+};
+
+/// Temperature sensors, in degrees Celsius, fans, and whether the heat is
+/// slowing the machine down.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SensorInfo {
+    pub sensors: Vec<SensorEntry>,
+    #[serde(default)]
+    pub fans: Vec<FanEntry>,
+    /// `None` where the platform tells nothing about throttling.
+    pub throttling: Option<ThrottleInfo>,
+}
+
+/// A fan, from hwmon on Linux or the SMC on macOS.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FanEntry {
+    pub label: String,
+    pub rpm: Option<u32>,
+    pub min_rpm: Option<u32>,
+    pub max_rpm: Option<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ThrottleInfo {
+    pub cpu_throttled: Option<bool>,
+    /// The highest clock the CPU may run at right now, relative to its
+    /// maximum. Thermal and power limits lower it, and so can the user.
+    pub cpu_speed_limit_percent: Option<u32>,
+    /// How often Intel CPUs have throttled since boot, summed over cores.
+    pub cpu_throttle_events: Option<u64>,
+    pub gpu_throttled: Option<bool>,
+}
+
+/// A single temperature sensor. Unavailable readings are `None`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SensorEntry {
+    pub label: String,
+    pub temperature: Option<f32>,
+    pub max: Option<f32>,
+    pub critical: Option<f32>,
+}
+
+impl SensorEntry {
+    /// Whether the reading has reached the sensor's max or critical limit.
+    pub fn is_hot(&self) -> bool {
+        self.temperature.is_some_and(|temperature| {
+            [self.max, self.critical]
+                .into_iter()
+                .flatten()
+                .any(|limit| limit > 0.0 && temperature >= limit)
+        })
+    }
+}
+
+impl SensorInfo {
+    pub fn collect() -> Self {
+        let mut sensors: Vec<SensorEntry> = Components::new_with_refreshed_list()
+            .iter()
+            .map(|component| SensorEntry {
+                label: component.label().to_string(),
+                temperature: finite(component.temperature()),
+                max: finite(component.max()),
+                critical: component.critical().and_then(finite),
+            })
+            .collect();
+        if sensors.is_empty() && cfg!(target_os = "openbsd") {
+            sensors = openbsd_sensors();
+        }
+        sensors.sort_by(|a, b| a.label.cmp(&b.label));
+
+        let mut throttling = if cfg!(target_os = "linux") {
+            linux_throttling()
+        } else if cfg!(target_os = "macos") {
+            macos_throttling()
+        } else {
+            ThrottleInfo {
+                cpu_throttled: None,
+                cpu_speed_limit_percent: None,
+                cpu_throttle_events: None,
+                gpu_throttled: None,
+            }
+        };
+        throttling.gpu_throttled = nvidia_throttled();
+        let fans = if cfg!(target_os = "macos") {
+            smc_fans()
+        } else {
+            hwmon_fans()
+        };
+        let known = throttling.cpu_throttled.is_some()
+            || throttling.cpu_speed_limit_percent.is_some()
+            || throttling.cpu_throttle_events.is_some()
+            || throttling.gpu_throttled.is_some();
+
+        Self {
+            sensors,
+            fans,
+            throttling: known.then_some(throttling),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Sensor Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.sensors.is_empty() {
+            options.line("  Sensors: None");
+        }
+
+        for sensor in &self.sensors {
+            options.line(format_args!(
+                "  {} : {} (max {}, critical {})",
+                sensor.label,
+                options.warn_if(sensor.is_hot(), format_celsius(sensor.temperature)),
+                format_celsius(sensor.max),
+                format_celsius(sensor.critical)
+            ));
+        }
+
+        for fan in &self.fans {
+            let range = match (fan.min_rpm, fan.max_rpm) {
+                (Some(min), Some(max)) => format!(" ({} - {} RPM)", min, max),
+                (None, Some(max)) => format!(" (max {} RPM)", max),
+                _ => String::new(),
+            };
+            // A fan at rest is normal unless it is meant to keep spinning.
+            let stalled = fan.rpm == Some(0) && fan.min_rpm.is_some_and(|min| min > 0);
+            options.line(format_args!(
+                "  {} : {}{}",
+                fan.label,
+                options.warn_if(
+                    stalled,
+                    fan.rpm
+                        .map(|rpm| format!("{} RPM", rpm))
+                        .unwrap_or_else(|| "Unknown".into())
+                ),
+                range
+            ));
+        }
+
+        let Some(throttling) = &self.throttling else {
+            return;
+        };
+        let state = |throttled: Option<bool>| match throttled {
+            Some(true) => "Yes",
+            Some(false) => "No",
+            None => "Unknown",
+        };
+        let mut details = Vec::new();
+        if let Some(limit) = throttling
+            .cpu_speed_limit_percent
+            .filter(|limit| *limit < 100)
+        {
+            details.push(format!("limited to {}% of max clock", limit));
+        }
+        if let Some(events) = throttling.cpu_throttle_events {
+            details.push(format!("{} events since boot", events));
+        }
+        let details = if details.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", details.join(", "))
+        };
+        options.line(options.warn_if(
+            throttling.cpu_throttled == Some(true),
+            format!(
+                "  CPU Throttled : {}{}",
+                state(throttling.cpu_throttled),
+                details
+            ),
+        ));
+        if throttling.gpu_throttled.is_some() {
+            options.line(options.warn_if(
+                throttling.gpu_throttled == Some(true),
+                format!("  GPU Throttled : {}", state(throttling.gpu_throttled)),
+            ));
+        }
+    }
+}
+
+/// Reads the `fan*_input` files of every hwmon chip, labelled by
+/// `fan*_label` where the driver provides one.
+fn hwmon_fans() -> Vec<FanEntry> {
+    let Ok(chips) = fs::read_dir("/sys/class/hwmon") else {
+        return Vec::new();
+    };
+    let mut fans = Vec::new();
+    for chip in chips.filter_map(Result::ok) {
+        let path = chip.path();
+        let name = read_trimmed(path.join("name")).unwrap_or_else(|| "hwmon".into());
+        let Ok(files) = fs::read_dir(&path) else {
+            continue;
+        };
+        let mut indexes: Vec<u32> = files
+            .filter_map(Result::ok)
+            .filter_map(|file| {
+                let file = file.file_name().into_string().ok()?;
+                file.strip_prefix("fan")?
+                    .strip_suffix("_input")?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        indexes.sort_unstable();
+
+        for index in indexes {
+            let read = |suffix: &str| read_trimmed(path.join(format!("fan{}_{}", index, suffix)));
+            let rpm = |suffix: &str| read(suffix).and_then(|rpm| rpm.parse().ok());
+            fans.push(FanEntry {
+                label: read("label").unwrap_or_else(|| format!("{} fan{}", name, index)),
+                rpm: rpm("input"),
+                min_rpm: rpm("min").filter(|min| *min > 0),
+                max_rpm: rpm("max").filter(|max| *max > 0),
+            });
+        }
+    }
+    fans
+}
+
+/// The SMC is only readable through `powermetrics`, which needs root and
+/// prints lines such as `Fan: 1830 rpm`. Apple Silicon laptops without fans
+/// print none.
+fn smc_fans() -> Vec<FanEntry> {
+    let Some(output) = command_output("powermetrics", &["--samplers", "smc", "-n", "1", "-i", "1"])
+    else {
+        return Vec::new();
+    };
+    output
+        .lines()
+        .filter_map(|line| {
+            let (label, value) = line.split_once(':')?;
+            let label = label.trim();
+            if !label.starts_with("Fan") {
+                return None;
+            }
+            let rpm = value.trim().strip_suffix("rpm")?.trim();
+            Some(FanEntry {
+                label: label.to_string(),
+                rpm: rpm.parse::<f32>().ok().map(|rpm| rpm as u32),
+                min_rpm: None,
+                max_rpm: None,
+            })
+        })
+        .collect()
+}
+
+/// A processor cooling device in a non-zero state means the kernel is
+/// holding the CPU back to cool it. The speed limit compares the cpufreq
+/// ceiling with the hardware maximum.
+fn linux_throttling() -> ThrottleInfo {
+    let cpu = Path::new("/sys/devices/system/cpu");
+    let cpus = read_trimmed(cpu.join("online"))
+        .map(|online| parse_cpu_list(&online))
+        .unwrap_or_default();
+    let number = |path: &Path| read_trimmed(path).and_then(|value| value.parse::<u64>().ok());
+
+    let cooling: Vec<u64> = fs::read_dir("/sys/class/thermal")
+        .map(|devices| {
+            devices
+                .filter_map(Result::ok)
+                .filter(|device| {
+                    read_trimmed(device.path().join("type"))
+                        .is_some_and(|kind| kind.contains("Processor") || kind.contains("cpufreq"))
+                })
+                .filter_map(|device| number(&device.path().join("cur_state")))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let cpu_speed_limit_percent = cpus
+        .iter()
+        .filter_map(|index| {
+            let cpufreq = cpu.join(format!("cpu{}/cpufreq", index));
+            let ceiling = number(&cpufreq.join("scaling_max_freq"))?;
+            let maximum = number(&cpufreq.join("cpuinfo_max_freq")).filter(|max| *max > 0)?;
+            Some((ceiling * 100 / maximum) as u32)
+        })
+        .min();
+
+    let events: Vec<u64> = cpus
+        .iter()
+        .filter_map(|index| {
+            number(&cpu.join(format!("cpu{}/thermal_throttle/core_throttle_count", index)))
+        })
+        .collect();
+
+    ThrottleInfo {
+        cpu_throttled: (!cooling.is_empty()).then(|| cooling.iter().any(|state| *state > 0)),
+        cpu_speed_limit_percent,
+        cpu_throttle_events: (!events.is_empty()).then(|| events.iter().sum()),
+        gpu_throttled: None,
+    }
+}
+
+/// `pmset -g therm` prints `CPU_Speed_Limit = 100` once the system has a
+/// thermal level to report, and nothing of the sort before.
+fn macos_throttling() -> ThrottleInfo {
+    let cpu_speed_limit_percent = command_output("pmset", &["-g", "therm"]).and_then(|output| {
+        output.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "CPU_Speed_Limit").then(|| value.trim().parse().ok())?
+        })
+    });
+
+    ThrottleInfo {
+        cpu_throttled: Some(cpu_speed_limit_percent.is_some_and(|limit| limit < 100)),
+        cpu_speed_limit_percent,
+        cpu_throttle_events: None,
+        gpu_throttled: None,
+    }
+}
+
+/// Asks `nvidia-smi` for the thermal slowdown reasons of each GPU, which
+/// read `Active` or `Not Active`.
+fn nvidia_throttled() -> Option<bool> {
+    let output = command_output(
+        "nvidia-smi",
+        &[
+            "--query-gpu=clocks_throttle_reasons.hw_thermal_slowdown,\
+             clocks_throttle_reasons.sw_thermal_slowdown",
+            "--format=csv,noheader",
+        ],
+    )?;
+    let reasons: Vec<&str> = output.split([',', '\n']).map(str::trim).collect();
+    (!reasons.is_empty()).then(|| reasons.contains(&"Active"))
+}
+
+/// Sensors report `NaN` when a reading is unavailable.
+fn finite(value: f32) -> Option<f32> {
+    value.is_finite().then_some(value)
+}
+
+fn format_celsius(value: Option<f32>) -> String {
+    value
+        .map(|value| format!("{:.1}°C", value))
+        .unwrap_or_else(|| "Unknown".into())
+}
+
+/// sysinfo has no OpenBSD backend, but `sysctl hw.sensors` lists lines such
+/// as `hw.sensors.cpu0.temp0=45.00 degC`.
+fn openbsd_sensors() -> Vec<SensorEntry> {
+    let Some(output) = command_output("sysctl", &["hw.sensors"]) else {
+        return Vec::new();
+    };
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.split_once('=')?;
+            let name = name.strip_prefix("hw.sensors.")?;
+            let (device, sensor) = name.split_once('.')?;
+            if !sensor.starts_with("temp") {
+                return None;
+            }
+            let temperature = value.split_whitespace().next()?.parse().ok();
+            Some(SensorEntry {
+                label: format!("{} {}", device, sensor),
+                temperature,
+                max: None,
+                critical: None,
+            })
+        })
+        .collect()
+}
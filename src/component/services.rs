@@ -0,0 +1,160 @@
+use {
+    crate::{format::DisplayOptions, util::command_output},
+    serde::{Deserialize, Serialize},
+    std::path::Path,
+};
+
+/// systemd units that failed and the state of the units asked about, only
+/// reported on Linux with systemd.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ServicesInfo {
+    /// Whether systemd manages the machine.
+    pub systemd: bool,
+    pub failed: Vec<UnitEntry>,
+    /// The units given with `--service`, in that order.
+    pub units: Vec<UnitEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnitEntry {
+    pub name: String,
+    /// `loaded`, `not-found`, `masked` and so on.
+    pub load_state: String,
+    /// `active`, `inactive`, `failed` and so on.
+    pub active_state: String,
+    /// The finer state, such as `running` or `exited`.
+    pub sub_state: String,
+    pub description: String,
+}
+
+impl UnitEntry {
+    fn is_failed(&self) -> bool {
+        self.active_state == "failed" || self.load_state == "not-found"
+    }
+}
+
+impl ServicesInfo {
+    /// Lists the failed units and looks up each of `units`, which may leave
+    /// out the `.service` suffix.
+    pub fn collect(units: &[String]) -> Self {
+        // `/run/systemd/system` exists exactly when systemd is PID 1.
+        if !Path::new("/run/systemd/system").is_dir() {
+            return Self {
+                systemd: false,
+                failed: Vec::new(),
+                units: Vec::new(),
+            };
+        }
+
+        Self {
+            systemd: true,
+            failed: failed_units(),
+            units: units.iter().filter_map(|unit| show_unit(unit)).collect(),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Service Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if !self.systemd {
+            options.line("  systemd: None");
+            return;
+        }
+
+        if self.failed.is_empty() {
+            options.line("  Failed Units: None");
+        } else {
+            options.line("  Failed Units:");
+            for unit in &self.failed {
+                options.line(options.warn_if(
+                    true,
+                    format!(
+                        "    {} : {} ({})",
+                        unit.name, unit.sub_state, unit.description
+                    ),
+                ));
+            }
+        }
+
+        if !self.units.is_empty() {
+            options.line("  Units:");
+        }
+        for unit in &self.units {
+            let state = if unit.load_state == "loaded" {
+                format!("{} ({})", unit.active_state, unit.sub_state)
+            } else {
+                unit.load_state.clone()
+            };
+            options
+                .line(options.warn_if(unit.is_failed(), format!("    {} : {}", unit.name, state)));
+        }
+    }
+}
+
+/// Parses `systemctl list-units --failed`, whose rows read `nginx.service
+/// loaded failed failed A high performance web server`.
+fn failed_units() -> Vec<UnitEntry> {
+    let Some(output) = command_output(
+        "systemctl",
+        &[
+            "list-units",
+            "--failed",
+            "--plain",
+            "--no-legend",
+            "--no-pager",
+        ],
+    ) else {
+        return Vec::new();
+    };
+
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?.to_string();
+            let load_state = fields.next()?.to_string();
+            let active_state = fields.next()?.to_string();
+            let sub_state = fields.next()?.to_string();
+            Some(UnitEntry {
+                name,
+                load_state,
+                active_state,
+                sub_state,
+                description: fields.collect::<Vec<_>>().join(" "),
+            })
+        })
+        .collect()
+}
+
+fn show_unit(unit: &str) -> Option<UnitEntry> {
+    let output = command_output(
+        "systemctl",
+        &[
+            "show",
+            unit,
+            "--property=Id,LoadState,ActiveState,SubState,Description",
+            "--no-pager",
+        ],
+    )?;
+    let property = |name: &str| {
+        output
+            .lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix('='))
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    Some(UnitEntry {
+        name: Some(property("Id"))
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(|| unit.to_string()),
+        load_state: property("LoadState"),
+        active_state: property("ActiveState"),
+        sub_state: property("SubState"),
+        description: property("Description"),
+    })
+}
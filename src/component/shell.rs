@@ -0,0 +1,199 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, parse_version},
+    },
+    serde::{Deserialize, Serialize},
+    std::{collections::HashSet, env, path::Path},
+    sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind},
+};
+
+/// The shell `envin` was started from and the terminal it runs in.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShellInfo {
+    pub name: String,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    pub terminal: Option<String>,
+}
+
+const SHELLS: &[&str] = &[
+    "bash",
+    "zsh",
+    "fish",
+    "sh",
+    "dash",
+    "ksh",
+    "mksh",
+    "tcsh",
+    "csh",
+    "nu",
+    "elvish",
+    "xonsh",
+    "ion",
+    "oil",
+    "osh",
+    "pwsh",
+    "powershell",
+    "cmd",
+];
+
+/// Terminal emulators by process name, for when none of the variables they
+/// set made it through.
+const TERMINALS: &[(&str, &str)] = &[
+    ("gnome-terminal-server", "GNOME Terminal"),
+    ("gnome-terminal", "GNOME Terminal"),
+    ("kgx", "GNOME Console"),
+    ("konsole", "Konsole"),
+    ("xfce4-terminal", "Xfce Terminal"),
+    ("mate-terminal", "MATE Terminal"),
+    ("lxterminal", "LXTerminal"),
+    ("tilix", "Tilix"),
+    ("terminator", "Terminator"),
+    ("alacritty", "Alacritty"),
+    ("kitty", "kitty"),
+    ("wezterm-gui", "WezTerm"),
+    ("foot", "foot"),
+    ("ghostty", "Ghostty"),
+    ("xterm", "XTerm"),
+    ("urxvt", "rxvt-unicode"),
+    ("st", "st"),
+    ("WindowsTerminal", "Windows Terminal"),
+    ("conhost", "Windows Console"),
+    ("iTerm2", "iTerm2"),
+    ("Terminal", "Terminal"),
+    ("code", "VS Code"),
+    ("sshd", "SSH"),
+];
+
+impl ShellInfo {
+    pub fn collect() -> Self {
+        let ancestors = ancestors();
+        let parent = ancestors
+            .iter()
+            .find(|(name, _)| SHELLS.contains(&name.as_str()));
+        let (name, path) = match parent {
+            Some((name, path)) => (name.clone(), path.clone()),
+            None => match env::var("SHELL") {
+                Ok(shell) => (shell_name(&shell), Some(shell)),
+                Err(_) => ("Unknown".into(), None),
+            },
+        };
+        let version = match name.as_str() {
+            // `sh` and `dash` have no version flag and `cmd` would open a
+            // new prompt.
+            "sh" | "dash" | "cmd" | "Unknown" => None,
+            "powershell" | "pwsh" => command_output(
+                path.as_deref().unwrap_or(&name),
+                &[
+                    "-NoProfile",
+                    "-Command",
+                    "$PSVersionTable.PSVersion.ToString()",
+                ],
+            ),
+            _ => command_output(path.as_deref().unwrap_or(&name), &["--version"])
+                .and_then(|output| parse_version(&output)),
+        };
+
+        Self {
+            name,
+            path,
+            version,
+            terminal: terminal_from_env().or_else(|| {
+                ancestors.iter().find_map(|(name, _)| {
+                    TERMINALS
+                        .iter()
+                        .find(|(process, _)| process == name)
+                        .map(|(_, terminal)| terminal.to_string())
+                })
+            }),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Shell Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        match &self.version {
+            Some(version) => options.line(format_args!("  Shell    : {} {}", self.name, version)),
+            None => options.line(format_args!("  Shell    : {}", self.name)),
+        }
+        options.line(format_args!(
+            "  Path     : {}",
+            self.path.as_deref().unwrap_or("Unknown")
+        ));
+        options.line(format_args!(
+            "  Terminal : {}",
+            self.terminal.as_deref().unwrap_or("Unknown")
+        ));
+    }
+}
+
+/// `/usr/bin/zsh` and `C:\...\pwsh.exe` become `zsh` and `pwsh`.
+fn shell_name(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|name| name.to_string_lossy().trim_start_matches('-').to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// The names and executables of the processes above `envin`, nearest first.
+fn ancestors() -> Vec<(String, Option<String>)> {
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::new().with_exe(UpdateKind::Always),
+    );
+
+    let mut ancestors = Vec::new();
+    let mut seen = HashSet::new();
+    let mut pid: Option<Pid> = sysinfo::get_current_pid()
+        .ok()
+        .and_then(|pid| sys.process(pid))
+        .and_then(|process| process.parent());
+    while let Some(process) = pid.and_then(|pid| sys.process(pid)) {
+        if !seen.insert(process.pid()) {
+            break;
+        }
+        let name = process.name().to_string_lossy();
+        ancestors.push((
+            shell_name(&name),
+            process.exe().map(|exe| exe.display().to_string()),
+        ));
+        pid = process.parent();
+    }
+    ancestors
+}
+
+/// Most terminals announce themselves through the environment.
+fn terminal_from_env() -> Option<String> {
+    let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+    if let Some(program) = var("TERM_PROGRAM") {
+        return Some(match program.as_str() {
+            "iTerm.app" => "iTerm2".into(),
+            "Apple_Terminal" => "Terminal".into(),
+            "vscode" => "VS Code".into(),
+            _ => match var("TERM_PROGRAM_VERSION") {
+                Some(version) => format!("{} {}", program, version),
+                None => program,
+            },
+        });
+    }
+    [
+        ("WT_SESSION", "Windows Terminal"),
+        ("KONSOLE_VERSION", "Konsole"),
+        ("KITTY_WINDOW_ID", "kitty"),
+        ("ALACRITTY_WINDOW_ID", "Alacritty"),
+        ("WEZTERM_EXECUTABLE", "WezTerm"),
+        ("GHOSTTY_RESOURCES_DIR", "Ghostty"),
+        ("TILIX_ID", "Tilix"),
+        ("GNOME_TERMINAL_SCREEN", "GNOME Terminal"),
+        ("TERMINAL_EMULATOR", "JetBrains"),
+    ]
+    .iter()
+    .find(|(variable, _)| var(variable).is_some())
+    .map(|(_, terminal)| terminal.to_string())
+}
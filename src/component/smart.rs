@@ -0,0 +1,207 @@
+use {
+    crate::{format::DisplayOptions, util::json_objects_in},
+    serde::{Deserialize, Serialize},
+    serde_json::Value,
+    std::process::Command,
+    which::which,
+};
+
+/// Drive health as reported by `smartctl` from smartmontools. Reading SMART
+/// data needs root, or an elevated prompt on Windows; without it the drives
+/// are listed with the error `smartctl` gave.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SmartInfo {
+    /// Whether `smartctl` is installed.
+    pub available: bool,
+    pub drives: Vec<SmartDrive>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SmartDrive {
+    pub device: String,
+    pub model: Option<String>,
+    /// `ATA`, `NVMe` or `SCSI`.
+    pub protocol: Option<String>,
+    /// Whether the drive's overall self-assessment passed.
+    pub healthy: Option<bool>,
+    pub temperature_celsius: Option<i64>,
+    pub power_on_hours: Option<u64>,
+    /// Sectors remapped after going bad, from attribute 5 on ATA drives.
+    pub reallocated_sectors: Option<u64>,
+    /// How much of the rated endurance is used up, for SSDs.
+    pub wear_percent_used: Option<u64>,
+    /// Why the drive could not be read, such as missing permissions.
+    pub error: Option<String>,
+}
+
+/// ATA attributes whose normalized value counts down from 100 as the flash
+/// wears: Samsung's Wear_Leveling_Count, SSD_Life_Left and Intel's
+/// Media_Wearout_Indicator.
+const WEAR_ATTRIBUTES: &[u64] = &[177, 231, 233];
+
+impl SmartInfo {
+    pub fn collect() -> Self {
+        if which("smartctl").is_err() {
+            return Self {
+                available: false,
+                drives: Vec::new(),
+            };
+        }
+
+        let scan = smartctl(&["--scan", "--json"]).unwrap_or_default();
+        let drives = json_objects_in(&scan, "devices")
+            .into_iter()
+            .filter_map(|device| {
+                let name = device["name"].as_str()?;
+                let kind = device["type"].as_str().unwrap_or("auto");
+                Some(drive(name, kind))
+            })
+            .collect();
+
+        Self {
+            available: true,
+            drives,
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}SMART Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if !self.available {
+            options.line("  smartctl: None");
+            return;
+        }
+        if self.drives.is_empty() {
+            options.line("  Drives: None");
+            return;
+        }
+
+        for drive in &self.drives {
+            options.line(format_args!(
+                "  {}{}{}",
+                "-".repeat(10),
+                drive.device,
+                "-".repeat(10)
+            ));
+            if let Some(error) = &drive.error {
+                options
+                    .line(options.warn_if(true, format!("    Error                : {}", error)));
+                continue;
+            }
+            options.line(format_args!(
+                "    Model                : {}",
+                drive.model.as_deref().unwrap_or("Unknown")
+            ));
+            let health = match drive.healthy {
+                Some(true) => "PASSED",
+                Some(false) => "FAILED",
+                None => "Unknown",
+            };
+            options.line(options.warn_if(
+                drive.healthy == Some(false),
+                format!("    Health               : {}", health),
+            ));
+            let number = |value: Option<u64>, unit: &str| {
+                value
+                    .map(|value| format!("{}{}", value, unit))
+                    .unwrap_or_else(|| "Unknown".into())
+            };
+            options.line(format_args!(
+                "    Temperature          : {}",
+                drive
+                    .temperature_celsius
+                    .map(|temperature| format!("{}°C", temperature))
+                    .unwrap_or_else(|| "Unknown".into())
+            ));
+            options.line(format_args!(
+                "    Power On Hours       : {}",
+                number(drive.power_on_hours, "")
+            ));
+            if drive.protocol.as_deref() == Some("ATA") {
+                options.line(options.warn_if(
+                    drive.reallocated_sectors.is_some_and(|sectors| sectors > 0),
+                    format!(
+                        "    Reallocated Sectors  : {}",
+                        number(drive.reallocated_sectors, "")
+                    ),
+                ));
+            }
+            if drive.wear_percent_used.is_some() {
+                options.line(options.warn_if(
+                    drive.wear_percent_used.is_some_and(|used| used >= 90),
+                    format!(
+                        "    Wear                 : {}",
+                        number(drive.wear_percent_used, "% used")
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+/// Runs `smartctl` and parses its JSON. Its exit status is a bit mask that
+/// is non-zero for merely worrying drives, so it is not checked.
+fn smartctl(args: &[&str]) -> Option<Value> {
+    let output = Command::new("smartctl").args(args).output().ok()?;
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+fn drive(device: &str, kind: &str) -> SmartDrive {
+    let mut drive = SmartDrive {
+        device: device.to_string(),
+        model: None,
+        protocol: None,
+        healthy: None,
+        temperature_celsius: None,
+        power_on_hours: None,
+        reallocated_sectors: None,
+        wear_percent_used: None,
+        error: None,
+    };
+    let Some(report) = smartctl(&["--json", "--all", "--device", kind, device]) else {
+        drive.error = Some("smartctl gave no output".into());
+        return drive;
+    };
+
+    // Failing to open the device, typically for lack of permissions, is
+    // reported as an error message.
+    drive.error = json_objects_in(&report["smartctl"], "messages")
+        .into_iter()
+        .find(|message| message["severity"] == "error")
+        .and_then(|message| message["string"].as_str())
+        .map(str::to_string);
+    if drive.error.is_some() && report.get("smart_status").is_none() {
+        return drive;
+    }
+    drive.error = None;
+
+    drive.model = report["model_name"].as_str().map(str::to_string);
+    drive.protocol = report["device"]["protocol"].as_str().map(str::to_string);
+    drive.healthy = report["smart_status"]["passed"].as_bool();
+    drive.temperature_celsius = report["temperature"]["current"].as_i64();
+    drive.power_on_hours = report["power_on_time"]["hours"].as_u64();
+
+    let nvme = &report["nvme_smart_health_information_log"];
+    if nvme.is_object() {
+        drive.wear_percent_used = nvme["percentage_used"].as_u64();
+    }
+    let attributes = json_objects_in(&report["ata_smart_attributes"], "table");
+    let attribute = |id: u64| {
+        attributes
+            .iter()
+            .find(|attribute| attribute["id"].as_u64() == Some(id))
+    };
+    if let Some(reallocated) = attribute(5) {
+        drive.reallocated_sectors = reallocated["raw"]["value"].as_u64();
+    }
+    if let Some(wear) = WEAR_ATTRIBUTES.iter().find_map(|id| attribute(*id)) {
+        drive.wear_percent_used = wear["value"]
+            .as_u64()
+            .map(|remaining| 100u64.saturating_sub(remaining));
+    }
+    drive
+}
@@ -0,0 +1,122 @@
+use {
+    super::{
+        cpu::CpuInfo,
+        memory::{MemoryBreakdown, SwapBreakdown},
+    },
+    crate::{
+        format::DisplayOptions,
+        util::{is_bsd, sysctl},
+    },
+    serde::{Deserialize, Serialize},
+    std::time::Duration,
+    sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System},
+};
+
+/// Operating system, CPU, memory, swap and shell.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SystemInfo {
+    pub os: String,
+    pub os_name: String,
+    pub os_version: String,
+    /// Lowercase distribution identifier such as `debian`, `arch` or `macos`.
+    pub distribution_id: String,
+    pub kernel: String,
+    pub cpu: CpuInfo,
+    pub memory_used_bytes: u64,
+    pub memory_total_bytes: u64,
+    /// How the memory splits up beyond used and total.
+    pub memory: Option<MemoryBreakdown>,
+    pub swap_used_bytes: u64,
+    pub swap_total_bytes: u64,
+    /// The swap areas behind the totals.
+    pub swap: Option<SwapBreakdown>,
+    pub shell: String,
+}
+
+impl SystemInfo {
+    /// Collects the section, sampling CPU usage over `cpu_usage_interval` if
+    /// one is given.
+    pub fn collect(cpu_usage_interval: Option<Duration>) -> Self {
+        let mut sys = System::new_with_specifics(
+            RefreshKind::new()
+                .with_cpu(CpuRefreshKind::everything())
+                .with_memory(MemoryRefreshKind::everything()),
+        );
+
+        let os_name = System::name()
+            .or_else(|| is_bsd().then(|| sysctl("kern.ostype")).flatten())
+            .unwrap_or_else(|| "Unknown".into());
+        let os_version = System::os_version()
+            .or_else(|| is_bsd().then(|| sysctl("kern.osrelease")).flatten())
+            .unwrap_or_else(|| "".into());
+        let os = format!("{} {}", os_name, os_version).trim_end().to_string();
+        let cpu = CpuInfo::collect(&mut sys, cpu_usage_interval);
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "Unknown".into());
+
+        Self {
+            os,
+            os_name,
+            os_version,
+            distribution_id: System::distribution_id(),
+            kernel: System::kernel_version()
+                .or_else(|| is_bsd().then(|| sysctl("kern.osrelease")).flatten())
+                .unwrap_or_else(|| "Unknown".into()),
+            cpu,
+            memory_used_bytes: sys.used_memory(),
+            memory_total_bytes: match sys.total_memory() {
+                // sysinfo has no backend for OpenBSD and NetBSD.
+                0 if is_bsd() => sysctl("hw.physmem64")
+                    .or_else(|| sysctl("hw.physmem"))
+                    .and_then(|bytes| bytes.parse().ok())
+                    .unwrap_or(0),
+                total => total,
+            },
+            memory: Some(MemoryBreakdown::collect(&sys)),
+            swap_used_bytes: sys.used_swap(),
+            swap_total_bytes: sys.total_swap(),
+            swap: Some(SwapBreakdown::collect()),
+            shell,
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}System Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        options.line(format_args!("  OS     : {}", self.os));
+        options.line(format_args!("  Kernel : {}", self.kernel));
+        self.cpu.display(options);
+        options.line(format_args!(
+            "  Memory : {}",
+            options.usage(self.memory_used_bytes, self.memory_total_bytes)
+        ));
+        if let Some(memory) = &self.memory {
+            memory.display(options);
+        }
+        options.line(format_args!(
+            "  Swap   : {}",
+            options.usage(self.swap_used_bytes, self.swap_total_bytes)
+        ));
+        if let Some(swap) = &self.swap {
+            swap.display(options);
+        }
+        let stalls: Vec<String> = [
+            ("CPU", self.cpu.pressure.as_ref()),
+            (
+                "memory",
+                self.memory
+                    .as_ref()
+                    .and_then(|memory| memory.pressure.as_ref()),
+            ),
+        ]
+        .iter()
+        .filter_map(|(resource, pressure)| Some(format!("{} {}", resource, (*pressure)?.summary())))
+        .collect();
+        if !stalls.is_empty() {
+            options.line(format_args!("  Stalls : {} (last 10s)", stalls.join("; ")));
+        }
+        options.line(format_args!("  Shell  : {}", self.shell));
+    }
+}
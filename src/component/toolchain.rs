@@ -0,0 +1,104 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output_timeout, parse_version},
+    },
+    serde::{Deserialize, Serialize},
+    std::{thread, time::Duration},
+    which::which,
+};
+
+/// How long a single tool may take to print its version.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// The tools probed, each with the first of its commands found on `PATH`
+/// and the arguments that make it print its version.
+const TOOLS: &[(&str, &[&str], &[&str])] = &[
+    ("rustc", &["rustc"], &["--version"]),
+    ("cargo", &["cargo"], &["--version"]),
+    ("gcc", &["gcc"], &["--version"]),
+    ("clang", &["clang"], &["--version"]),
+    ("python", &["python3", "python"], &["--version"]),
+    ("node", &["node"], &["--version"]),
+    ("go", &["go"], &["version"]),
+    ("java", &["java"], &["-version"]),
+];
+
+/// Versions of common compilers and runtimes on `PATH`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ToolchainInfo {
+    pub tools: Vec<ToolEntry>,
+}
+
+/// A single tool. `path` is `None` when it is not installed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ToolEntry {
+    pub name: String,
+    pub path: Option<String>,
+    pub version: Option<String>,
+}
+
+impl ToolchainInfo {
+    pub fn collect() -> Self {
+        // Probe every tool at once, as some take a while to start.
+        let tools = thread::scope(|scope| {
+            let probes: Vec<_> = TOOLS
+                .iter()
+                .map(|&(name, commands, args)| scope.spawn(move || probe(name, commands, args)))
+                .collect();
+            probes
+                .into_iter()
+                .filter_map(|probe| probe.join().ok())
+                .collect()
+        });
+
+        Self { tools }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Toolchain Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        let width = self
+            .tools
+            .iter()
+            .map(|tool| tool.name.len())
+            .max()
+            .unwrap_or(0);
+        for tool in &self.tools {
+            let version = match (&tool.path, &tool.version) {
+                (None, _) => "None".into(),
+                (Some(path), Some(version)) => format!("{} ({})", version, path),
+                (Some(path), None) => format!("Unknown ({})", path),
+            };
+            options.line(format_args!(
+                "  {:<width$} : {}",
+                tool.name,
+                version,
+                width = width
+            ));
+        }
+    }
+}
+
+fn probe(name: &str, commands: &[&str], args: &[&str]) -> ToolEntry {
+    let found = commands
+        .iter()
+        .find_map(|command| Some((*command, which(command).ok()?)));
+    let Some((command, path)) = found else {
+        return ToolEntry {
+            name: name.into(),
+            path: None,
+            version: None,
+        };
+    };
+
+    ToolEntry {
+        name: name.into(),
+        path: Some(path.display().to_string()),
+        version: command_output_timeout(command, args, PROBE_TIMEOUT)
+            .and_then(|output| parse_version(&output)),
+    }
+}
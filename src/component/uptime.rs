@@ -0,0 +1,72 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{format_duration, is_bsd, sysctl},
+    },
+    chrono::{DateTime, SecondsFormat, Utc},
+    serde::{Deserialize, Serialize},
+    sysinfo::System,
+};
+
+/// How long the machine has been running and when it booted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UptimeInfo {
+    pub uptime_secs: u64,
+    pub boot_time_unix: u64,
+    /// The boot time in RFC 3339, in UTC.
+    pub boot_time: String,
+}
+
+impl UptimeInfo {
+    pub fn collect() -> Self {
+        let (boot_time_unix, uptime_secs) = match System::boot_time() {
+            0 if is_bsd() => {
+                let boot = bsd_boot_time().unwrap_or(0);
+                let now = u64::try_from(Utc::now().timestamp()).unwrap_or(0);
+                (
+                    boot,
+                    if boot > 0 {
+                        now.saturating_sub(boot)
+                    } else {
+                        0
+                    },
+                )
+            }
+            boot => (boot, System::uptime()),
+        };
+        let boot_time = DateTime::from_timestamp(boot_time_unix as i64, 0)
+            .map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true))
+            .unwrap_or_else(|| "Unknown".into());
+
+        Self {
+            uptime_secs,
+            boot_time_unix,
+            boot_time,
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Uptime Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        options.line(format_args!(
+            "  Uptime : {} ({} seconds)",
+            format_duration(self.uptime_secs),
+            self.uptime_secs
+        ));
+        options.line(format_args!("  Booted : {}", self.boot_time));
+    }
+}
+
+/// `kern.boottime` reads `{ sec = 1712345678, usec = 120345 } Fri Apr  5
+/// 19:34:38 2024` on FreeBSD and NetBSD, and is the bare seconds on OpenBSD.
+fn bsd_boot_time() -> Option<u64> {
+    let boot = sysctl("kern.boottime")?;
+    let seconds = match boot.split_once("sec = ") {
+        Some((_, rest)) => rest.split(|c: char| !c.is_ascii_digit()).next()?,
+        None => boot.trim(),
+    };
+    seconds.parse().ok()
+}
@@ -0,0 +1,104 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        ids::IdDatabase,
+        util::{read_hex_id, read_trimmed},
+    },
+    serde::{Deserialize, Serialize},
+    std::path::Path,
+};
+
+/// Connected USB devices, only reported on Linux.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UsbInfo {
+    pub devices: Vec<UsbDevice>,
+}
+
+/// A single device. Names come from the device itself, falling back to the
+/// USB ID database.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UsbDevice {
+    pub bus: u32,
+    pub address: u32,
+    /// The vendor ID in hex, as in `046d`.
+    pub vendor_id: String,
+    /// The product ID in hex, as in `c52b`.
+    pub product_id: String,
+    pub vendor: Option<String>,
+    pub product: Option<String>,
+    /// The negotiated speed, such as 480 for USB 2.0 high speed.
+    pub speed_mbps: Option<f64>,
+}
+
+impl UsbInfo {
+    pub fn collect() -> Self {
+        let root = Path::new("/sys/bus/usb/devices");
+        let Ok(entries) = std::fs::read_dir(root) else {
+            return Self {
+                devices: Vec::new(),
+            };
+        };
+
+        let usb_ids = IdDatabase::usb();
+        // Interfaces such as `1-1:1.0` sit next to the devices.
+        let mut devices: Vec<UsbDevice> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| !entry.file_name().to_string_lossy().contains(':'))
+            .filter_map(|entry| {
+                let device = entry.path();
+                let vendor_id = read_hex_id(&device.join("idVendor"))?;
+                let product_id = read_hex_id(&device.join("idProduct"))?;
+                let (known_vendor, known_product) = usb_ids
+                    .as_ref()
+                    .map(|ids| ids.names(vendor_id, product_id))
+                    .unwrap_or_default();
+                let number = |file: &str| read_trimmed(device.join(file))?.parse::<u32>().ok();
+
+                Some(UsbDevice {
+                    bus: number("busnum")?,
+                    address: number("devnum")?,
+                    vendor_id: format!("{:04x}", vendor_id),
+                    product_id: format!("{:04x}", product_id),
+                    vendor: read_trimmed(device.join("manufacturer")).or(known_vendor),
+                    product: read_trimmed(device.join("product")).or(known_product),
+                    speed_mbps: read_trimmed(device.join("speed"))
+                        .and_then(|speed| speed.parse().ok()),
+                })
+            })
+            .collect();
+        devices.sort_by_key(|device| (device.bus, device.address));
+
+        Self { devices }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}USB Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.devices.is_empty() {
+            options.line("  Devices: None");
+            return;
+        }
+
+        // Rows are printed as is: device names may contain `:`.
+        println!("  Bus Dev  ID         {:>10}  Name", "Speed");
+        for device in &self.devices {
+            let speed = device
+                .speed_mbps
+                .map(|speed| format!("{} Mbps", speed))
+                .unwrap_or_else(|| "Unknown".into());
+            let name = match (&device.vendor, &device.product) {
+                (Some(vendor), Some(product)) => format!("{} {}", vendor, product),
+                (Some(name), None) | (None, Some(name)) => name.clone(),
+                (None, None) => "Unknown".into(),
+            };
+            println!(
+                "  {:03} {:03}  {}:{}  {:>10}  {}",
+                device.bus, device.address, device.vendor_id, device.product_id, speed, name
+            );
+        }
+    }
+}
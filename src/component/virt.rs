@@ -0,0 +1,266 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, command_output_timeout, is_bsd, read_trimmed, sysctl},
+    },
+    serde::{Deserialize, Serialize},
+    std::{env, path::Path, time::Duration},
+};
+
+/// Whether `envin` runs inside a virtual machine or a container.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VirtInfo {
+    /// The hypervisor vendor, if the machine is virtual.
+    pub hypervisor: Option<String>,
+    /// The container runtime, if `envin` runs inside one.
+    pub container: Option<String>,
+    /// The Windows Subsystem for Linux, if `envin` runs under it.
+    pub wsl: Option<WslInfo>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WslInfo {
+    /// `1` for the translation layer, `2` for the lightweight VM.
+    pub version: u8,
+    pub distribution: Option<String>,
+    /// The Windows build underneath, such as `10.0.22631.3447`.
+    pub windows_build: Option<String>,
+    /// Whether Windows programs can be started from Linux.
+    pub interop: bool,
+}
+
+impl VirtInfo {
+    pub fn collect() -> Self {
+        Self {
+            hypervisor: cpuid_hypervisor()
+                .or_else(dmi_hypervisor)
+                .or_else(macos_hypervisor)
+                .or_else(bsd_hypervisor),
+            container: container(),
+            wsl: wsl(),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Virtualization Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+        options.line(format_args!(
+            "  Hypervisor : {}",
+            self.hypervisor.as_deref().unwrap_or("None")
+        ));
+        options.line(format_args!(
+            "  Container  : {}",
+            self.container.as_deref().unwrap_or("None")
+        ));
+        if let Some(wsl) = &self.wsl {
+            let mut text = format!("WSL{}", wsl.version);
+            if let Some(distribution) = &wsl.distribution {
+                text.push_str(&format!(" ({})", distribution));
+            }
+            if let Some(build) = &wsl.windows_build {
+                text.push_str(&format!(", Windows {}", build));
+            }
+            text.push_str(if wsl.interop {
+                ", interop enabled"
+            } else {
+                ", interop disabled"
+            });
+            options.line(format_args!("  WSL        : {}", text));
+        }
+    }
+}
+
+/// Reads the vendor signature hypervisors publish at CPUID leaf
+/// `0x40000000`, once leaf 1 says one is present.
+#[cfg(target_arch = "x86_64")]
+fn cpuid_hypervisor() -> Option<String> {
+    use std::arch::x86_64::__cpuid;
+
+    const HYPERVISOR_PRESENT: u32 = 1 << 31;
+    if __cpuid(1).ecx & HYPERVISOR_PRESENT == 0 {
+        return None;
+    }
+    let leaf = __cpuid(0x4000_0000);
+    let signature: Vec<u8> = [leaf.ebx, leaf.ecx, leaf.edx]
+        .iter()
+        .flat_map(|register| register.to_le_bytes())
+        .collect();
+    let signature = String::from_utf8_lossy(&signature);
+    let signature = signature.trim_matches(|c: char| c == '\0' || c == ' ');
+
+    let vendor = match signature {
+        "KVMKVMKVM" | "Linux KVM Hv" => "KVM",
+        "Microsoft Hv" => "Microsoft Hyper-V",
+        "VMwareVMware" => "VMware",
+        "XenVMMXenVMM" => "Xen",
+        "VBoxVBoxVBox" => "VirtualBox",
+        "TCGTCGTCGTCG" => "QEMU",
+        "lrpepyh  vr" | "prl hyperv" => "Parallels",
+        "bhyve bhyve" => "bhyve",
+        "ACRNACRNACRN" => "ACRN",
+        "QNXQVMBSQG" => "QNX",
+        "" => "Unknown",
+        signature => return Some(signature.to_string()),
+    };
+    Some(vendor.into())
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn cpuid_hypervisor() -> Option<String> {
+    None
+}
+
+/// Falls back to the firmware vendor strings on Linux, which also covers
+/// ARM guests without CPUID.
+fn dmi_hypervisor() -> Option<String> {
+    let dmi = Path::new("/sys/class/dmi/id");
+    let vendor = ["sys_vendor", "product_name", "board_vendor", "bios_vendor"]
+        .iter()
+        .filter_map(|field| read_trimmed(dmi.join(field)))
+        .find_map(|value| {
+            [
+                ("QEMU", "QEMU"),
+                ("KVM", "KVM"),
+                ("VMware", "VMware"),
+                ("VirtualBox", "VirtualBox"),
+                ("innotek", "VirtualBox"),
+                ("Xen", "Xen"),
+                ("Microsoft Corporation", "Microsoft Hyper-V"),
+                ("Parallels", "Parallels"),
+                ("Amazon EC2", "Amazon EC2"),
+                ("Google Compute Engine", "Google Compute Engine"),
+                ("BHYVE", "bhyve"),
+            ]
+            .iter()
+            .find(|(marker, _)| value.contains(marker))
+            .map(|(_, vendor)| vendor.to_string())
+        });
+
+    // Xen guests without DMI still expose the hypervisor type.
+    vendor.or_else(|| read_trimmed("/sys/hypervisor/type").map(|kind| kind.replace("xen", "Xen")))
+}
+
+fn macos_hypervisor() -> Option<String> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    (command_output("sysctl", &["-n", "kern.hv_vmm_present"])? == "1").then(|| "Unknown".into())
+}
+
+/// FreeBSD names the hypervisor it detected in `kern.vm_guest`, such as
+/// `kvm` or `bhyve`, which also covers ARM guests.
+fn bsd_hypervisor() -> Option<String> {
+    if !is_bsd() {
+        return None;
+    }
+    let guest = sysctl("kern.vm_guest")?;
+    Some(match guest.as_str() {
+        "none" => return None,
+        "kvm" => "KVM".into(),
+        "vmware" => "VMware".into(),
+        "xen" => "Xen".into(),
+        "hv" => "Microsoft Hyper-V".into(),
+        "vbox" => "VirtualBox".into(),
+        "parallels" => "Parallels".into(),
+        guest => guest.to_string(),
+    })
+}
+
+/// Recognizes the container runtime from the marker files and environment
+/// they leave for the processes they start.
+fn container() -> Option<String> {
+    if is_bsd() {
+        return (sysctl("security.jail.jailed").as_deref() == Some("1")).then(|| "Jail".into());
+    }
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some("Podman".into());
+    }
+    if Path::new("/.dockerenv").exists() {
+        return Some("Docker".into());
+    }
+
+    // systemd-nspawn, LXC and others set `container` for the init process.
+    let environment = std::fs::read("/proc/1/environ").unwrap_or_default();
+    let declared = read_trimmed("/run/systemd/container").or_else(|| {
+        environment
+            .split(|&byte| byte == 0)
+            .find_map(|variable| variable.strip_prefix(b"container="))
+            .map(|value| String::from_utf8_lossy(value).into_owned())
+    });
+    if let Some(runtime) = declared {
+        return Some(runtime_name(&runtime));
+    }
+
+    let cgroup = std::fs::read_to_string("/proc/self/cgroup").unwrap_or_default();
+    [
+        ("docker", "Docker"),
+        ("libpod", "Podman"),
+        ("lxc", "LXC"),
+        ("kubepods", "Kubernetes"),
+    ]
+    .iter()
+    .find(|(marker, _)| cgroup.contains(marker))
+    .map(|(_, runtime)| runtime.to_string())
+}
+
+fn runtime_name(runtime: &str) -> String {
+    match runtime {
+        "docker" => "Docker".into(),
+        "podman" => "Podman".into(),
+        "lxc" | "lxc-libvirt" => "LXC".into(),
+        "systemd-nspawn" => "systemd-nspawn".into(),
+        "wsl" => "WSL".into(),
+        runtime => runtime.to_string(),
+    }
+}
+
+/// WSL kernels name Microsoft in their release: WSL1 fakes one such as
+/// `4.4.0-19041-Microsoft`, with the Windows build in it, and WSL2 runs a
+/// real one such as `5.15.146.1-microsoft-standard-WSL2`.
+fn wsl() -> Option<WslInfo> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let release = read_trimmed("/proc/sys/kernel/osrelease")?;
+    if !release.to_ascii_lowercase().contains("microsoft") {
+        return None;
+    }
+    let version = if release.contains("-Microsoft") { 1 } else { 2 };
+
+    // Interop registers Windows executables as a binfmt_misc format.
+    let interop = ["WSLInterop", "WSLInterop-late"].iter().any(|name| {
+        read_trimmed(Path::new("/proc/sys/fs/binfmt_misc").join(name))
+            .is_some_and(|format| format.lines().next() == Some("enabled"))
+    });
+    let windows_build = if version == 1 {
+        release
+            .split('-')
+            .nth(1)
+            .map(|build| format!("10.0.{}", build))
+    } else if interop {
+        // `ver` prints `Microsoft Windows [Version 10.0.22631.3447]`.
+        command_output_timeout("cmd.exe", &["/c", "ver"], Duration::from_secs(3)).and_then(
+            |output| {
+                let (_, version) = output.split_once("[Version ")?;
+                Some(version.split(']').next()?.trim().to_string())
+            },
+        )
+    } else {
+        None
+    };
+
+    Some(WslInfo {
+        version,
+        distribution: env::var("WSL_DISTRO_NAME")
+            .ok()
+            .filter(|name| !name.is_empty()),
+        windows_build,
+        interop,
+    })
+}
@@ -0,0 +1,246 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_objects_in, json_string},
+    },
+    serde::{Deserialize, Serialize},
+};
+
+/// The wireless networks the machine is connected to. The SSID and BSSID
+/// can tell where the machine is, so the command line redacts them unless
+/// asked not to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WifiInfo {
+    pub connections: Vec<WifiConnection>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WifiConnection {
+    pub interface: String,
+    pub ssid: Option<String>,
+    /// The MAC address of the access point.
+    pub bssid: Option<String>,
+    /// On Windows, converted from the signal quality it reports.
+    pub signal_dbm: Option<i32>,
+    /// `2.4 GHz`, `5 GHz` or `6 GHz`.
+    pub band: Option<String>,
+    pub channel: Option<u32>,
+    /// The rate frames are sent at.
+    pub link_rate_mbps: Option<f64>,
+}
+
+impl WifiInfo {
+    pub fn collect() -> Self {
+        let connections = if cfg!(target_os = "macos") {
+            macos_connections()
+        } else if cfg!(target_os = "windows") {
+            windows_connections()
+        } else {
+            linux_connections()
+        };
+
+        Self { connections }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Wi-Fi Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        if self.connections.is_empty() {
+            options.line("  Connections: None");
+            return;
+        }
+
+        for connection in &self.connections {
+            options.line(format_args!("  {}:", connection.interface));
+            options.line(format_args!(
+                "    SSID      : {}",
+                connection.ssid.as_deref().unwrap_or("Unknown")
+            ));
+            match connection.signal_dbm {
+                // Below -80 dBm connections tend to drop.
+                Some(signal) => options
+                    .line(options.warn_if(signal < -80, format!("    Signal    : {} dBm", signal))),
+                None => options.line("    Signal    : Unknown"),
+            }
+            let channel = match (&connection.band, connection.channel) {
+                (Some(band), Some(channel)) => format!("{} ({})", channel, band),
+                (Some(band), None) => band.clone(),
+                (None, Some(channel)) => channel.to_string(),
+                (None, None) => "Unknown".into(),
+            };
+            options.line(format_args!("    Channel   : {}", channel));
+            match connection.link_rate_mbps {
+                Some(rate) => options.line(format_args!("    Link Rate : {} Mbps", rate)),
+                None => options.line("    Link Rate : Unknown"),
+            }
+        }
+    }
+}
+
+/// Asks `iw` about each wireless interface, whose link reads:
+///
+/// ```text
+/// Connected to 7c:10:c9:aa:bb:cc (on wlan0)
+///         SSID: home
+///         freq: 5180
+///         signal: -52 dBm
+///         tx bitrate: 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2
+/// ```
+fn linux_connections() -> Vec<WifiConnection> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/net") else {
+        return Vec::new();
+    };
+
+    let mut connections: Vec<WifiConnection> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().join("wireless").is_dir())
+        .filter_map(|entry| {
+            let interface = entry.file_name().to_string_lossy().into_owned();
+            let link = command_output("iw", &["dev", &interface, "link"])?;
+            let bssid = link
+                .lines()
+                .next()?
+                .strip_prefix("Connected to ")?
+                .split_whitespace()
+                .next()
+                .map(str::to_string);
+            let field = |name: &str| {
+                link.lines()
+                    .find_map(|line| line.trim().strip_prefix(name)?.strip_prefix(':'))
+                    .map(str::trim)
+            };
+            let frequency =
+                field("freq").and_then(|freq| freq.split('.').next()?.parse::<u32>().ok());
+
+            Some(WifiConnection {
+                interface,
+                ssid: field("SSID").map(str::to_string),
+                bssid,
+                signal_dbm: field("signal")
+                    .and_then(|signal| signal.split_whitespace().next()?.parse().ok()),
+                band: frequency.and_then(band),
+                channel: frequency.and_then(channel),
+                link_rate_mbps: field("tx bitrate")
+                    .and_then(|rate| rate.split_whitespace().next()?.parse().ok()),
+            })
+        })
+        .collect();
+    connections.sort_by(|a, b| a.interface.cmp(&b.interface));
+    connections
+}
+
+/// Reads `system_profiler SPAirPortDataType`, whose channel reads
+/// `149 (5GHz, 80MHz)` and signal `-52 dBm / -92 dBm`.
+fn macos_connections() -> Vec<WifiConnection> {
+    let Some(output) = command_output("system_profiler", &["SPAirPortDataType", "-json"]) else {
+        return Vec::new();
+    };
+    let Ok(value) = output.parse::<serde_json::Value>() else {
+        return Vec::new();
+    };
+
+    json_objects_in(&value, "SPAirPortDataType")
+        .into_iter()
+        .flat_map(|airport| json_objects_in(airport, "spairport_airport_interfaces"))
+        .filter_map(|interface| {
+            let network = interface.get("spairport_current_network_information")?;
+            let channel = json_string(network, "spairport_network_channel");
+            let band = channel
+                .as_deref()
+                .and_then(|channel| channel.split_once('(')?.1.split([',', ')']).next())
+                .map(|band| band.trim().replace("GHz", " GHz"));
+
+            Some(WifiConnection {
+                interface: json_string(interface, "_name")?,
+                ssid: json_string(network, "_name"),
+                bssid: None,
+                signal_dbm: json_string(network, "spairport_signal_noise")
+                    .and_then(|signal| signal.split_whitespace().next()?.parse().ok()),
+                band,
+                channel: channel
+                    .as_deref()
+                    .and_then(|channel| channel.split_whitespace().next()?.parse().ok()),
+                link_rate_mbps: network
+                    .get("spairport_network_rate")
+                    .and_then(|rate| rate.as_f64()),
+            })
+        })
+        .collect()
+}
+
+/// Parses `netsh wlan show interfaces`, whose fields read `    SSID    : home`.
+fn windows_connections() -> Vec<WifiConnection> {
+    let Some(output) = command_output("netsh", &["wlan", "show", "interfaces"]) else {
+        return Vec::new();
+    };
+
+    // Each interface starts with its `Name` field.
+    let mut blocks: Vec<Vec<(&str, &str)>> = Vec::new();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(" : ") else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if key == "Name" {
+            blocks.push(Vec::new());
+        }
+        if let Some(block) = blocks.last_mut() {
+            block.push((key, value));
+        }
+    }
+
+    blocks
+        .iter()
+        .filter_map(|block| {
+            let field = |name: &str| {
+                block
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| *value)
+            };
+            if field("State") != Some("connected") {
+                return None;
+            }
+            // Quality runs linearly from 0% at -100 dBm to 100% at -50 dBm.
+            let signal_dbm = field("Signal")
+                .and_then(|signal| signal.trim_end_matches('%').parse::<i32>().ok())
+                .map(|quality| quality / 2 - 100);
+
+            Some(WifiConnection {
+                interface: field("Name")?.to_string(),
+                ssid: field("SSID").map(str::to_string),
+                bssid: field("BSSID")
+                    .or_else(|| field("AP BSSID"))
+                    .map(str::to_string),
+                signal_dbm,
+                band: field("Band").map(str::to_string),
+                channel: field("Channel").and_then(|channel| channel.parse().ok()),
+                link_rate_mbps: field("Transmit rate (Mbps)").and_then(|rate| rate.parse().ok()),
+            })
+        })
+        .collect()
+}
+
+fn band(frequency_mhz: u32) -> Option<String> {
+    let band = match frequency_mhz {
+        2400..=2500 => "2.4 GHz",
+        5150..=5925 => "5 GHz",
+        5926..=7125 => "6 GHz",
+        _ => return None,
+    };
+    Some(band.into())
+}
+
+fn channel(frequency_mhz: u32) -> Option<u32> {
+    match frequency_mhz {
+        2484 => Some(14),
+        2412..=2472 => Some((frequency_mhz - 2407) / 5),
+        5150..=5925 => Some((frequency_mhz - 5000) / 5),
+        5926..=7125 => Some((frequency_mhz - 5950) / 5),
+        _ => None,
+    }
+}
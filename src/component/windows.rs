@@ -0,0 +1,141 @@
+use {
+    crate::{
+        format::DisplayOptions,
+        util::{command_output, json_objects, json_string},
+    },
+    serde::{Deserialize, Serialize},
+};
+
+/// Windows release details from the registry and WMI, beyond the version
+/// sysinfo reports. Empty on other systems.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WindowsInfo {
+    /// Such as `Windows 11 Pro`.
+    pub product_name: Option<String>,
+    /// Such as `Home`, `Pro` or `Enterprise`.
+    pub edition: Option<String>,
+    /// The feature update, such as `23H2`.
+    pub display_version: Option<String>,
+    /// The build and update revision, such as `22631.3447`.
+    pub build: Option<String>,
+    /// As `yyyy-MM-dd`.
+    pub install_date: Option<String>,
+    /// Whether `envin` runs with administrator rights.
+    pub elevated: Option<bool>,
+}
+
+/// The first build of Windows 11, which still calls itself Windows 10 in
+/// the registry.
+const WINDOWS_11_BUILD: u32 = 22000;
+
+impl WindowsInfo {
+    pub fn collect() -> Self {
+        let empty = Self {
+            product_name: None,
+            edition: None,
+            display_version: None,
+            build: None,
+            install_date: None,
+            elevated: None,
+        };
+        if !cfg!(target_os = "windows") {
+            return empty;
+        }
+        // Releases before 20H2 only have the older `ReleaseId`.
+        let Some(output) = command_output(
+            "powershell",
+            &[
+                "-NoProfile",
+                "-Command",
+                "$version = Get-ItemProperty 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion'; \
+                 $os = Get-CimInstance Win32_OperatingSystem; \
+                 $identity = [Security.Principal.WindowsPrincipal][Security.Principal.WindowsIdentity]::GetCurrent(); \
+                 [pscustomobject]@{ \
+                   ProductName = $version.ProductName; EditionID = $version.EditionID; \
+                   DisplayVersion = $version.DisplayVersion; ReleaseId = $version.ReleaseId; \
+                   Build = $version.CurrentBuildNumber; Revision = $version.UBR; \
+                   InstallDate = $os.InstallDate.ToString('yyyy-MM-dd'); \
+                   Elevated = $identity.IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator) \
+                 } | ConvertTo-Json",
+            ],
+        ) else {
+            return empty;
+        };
+        let Some(version) = json_objects(&output).into_iter().next() else {
+            return empty;
+        };
+
+        let build = json_string(&version, "Build");
+        let number = build.as_deref().and_then(|build| build.parse::<u32>().ok());
+        let product_name = json_string(&version, "ProductName").map(|name| {
+            if number.is_some_and(|number| number >= WINDOWS_11_BUILD) {
+                name.replacen("Windows 10", "Windows 11", 1)
+            } else {
+                name
+            }
+        });
+
+        Self {
+            product_name,
+            edition: json_string(&version, "EditionID").map(|edition| edition_name(&edition)),
+            display_version: json_string(&version, "DisplayVersion")
+                .or_else(|| json_string(&version, "ReleaseId")),
+            build: build.map(|build| match version["Revision"].as_u64() {
+                Some(revision) => format!("{}.{}", build, revision),
+                None => build,
+            }),
+            install_date: json_string(&version, "InstallDate"),
+            elevated: version["Elevated"].as_bool(),
+        }
+    }
+
+    pub fn display(&self, options: &DisplayOptions) {
+        options.line(format_args!(
+            "{}Windows Information{}",
+            "=".repeat(10),
+            "=".repeat(10)
+        ));
+
+        let Some(product_name) = &self.product_name else {
+            options.line("  Product   : None");
+            return;
+        };
+        options.line(format_args!("  Product   : {}", product_name));
+        for (key, value) in [
+            ("Edition  ", &self.edition),
+            ("Version  ", &self.display_version),
+            ("Build    ", &self.build),
+            ("Installed", &self.install_date),
+        ] {
+            options.line(format_args!(
+                "  {} : {}",
+                key,
+                value.as_deref().unwrap_or("Unknown")
+            ));
+        }
+        options.line(format_args!(
+            "  Elevated  : {}",
+            match self.elevated {
+                Some(true) => "Yes",
+                Some(false) => "No",
+                None => "Unknown",
+            }
+        ));
+    }
+}
+
+/// Turns the registry's `EditionID` into the name Windows sells it under.
+fn edition_name(edition: &str) -> String {
+    match edition {
+        "Core" | "CoreSingleLanguage" | "CoreCountrySpecific" => "Home".into(),
+        "CoreN" => "Home N".into(),
+        "Professional" => "Pro".into(),
+        "ProfessionalN" => "Pro N".into(),
+        "ProfessionalWorkstation" => "Pro for Workstations".into(),
+        "ProfessionalEducation" => "Pro Education".into(),
+        "ServerStandard" => "Server Standard".into(),
+        "ServerDatacenter" => "Server Datacenter".into(),
+        "IoTEnterprise" => "IoT Enterprise".into(),
+        edition => edition.to_string(),
+    }
+}
@@ -0,0 +1,87 @@
+//! The optional `envin.toml` holding defaults for the command line flags.
+//!
+//! ```toml
+//! sections = ["system", "disk", "gpu"]
+//! output = "text"
+//! human = true
+//! units = "si"
+//! color = "never"
+//! redact = ["host.hostname", "network.interfaces.mac_address"]
+//! public-ip-endpoint = "https://ifconfig.me/ip"
+//! show-serials = false
+//! cloud-metadata = true
+//! ```
+
+use {
+    crate::{
+        format::{ByteUnits, ColorChoice},
+        Sections,
+    },
+    serde::Deserialize,
+    std::{fs, io, path::PathBuf},
+};
+
+/// Defaults read from the configuration file. Flags given on the command
+/// line take precedence.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// Sections to collect when none are selected on the command line.
+    pub sections: Option<Vec<String>>,
+    /// Output format, by the name `--output` takes.
+    pub output: Option<String>,
+    pub human: Option<bool>,
+    pub units: Option<ByteUnits>,
+    pub color: Option<ColorChoice>,
+    pub cpu_cores: Option<bool>,
+    /// Dotted paths of text fields to blank out, such as `host.hostname`.
+    pub redact: Vec<String>,
+    /// The service `--public-ip` asks for the public addresses.
+    pub public_ip_endpoint: Option<String>,
+    /// Whether `--board` shows the machine's UUID and serial numbers.
+    pub show_serials: Option<bool>,
+    /// Whether `--cloud` asks the provider's metadata service.
+    pub cloud_metadata: Option<bool>,
+}
+
+impl Config {
+    /// Loads the configuration from `path`, or from [`default_path`] if none
+    /// is given. A missing default file is the same as an empty one.
+    pub fn load(path: Option<PathBuf>) -> Result<Self, String> {
+        let explicit = path.is_some();
+        let Some(path) = path.or_else(default_path) else {
+            return Ok(Self::default());
+        };
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !explicit => {
+                return Ok(Self::default())
+            }
+            Err(err) => return Err(format!("{}: {}", path.display(), err)),
+        };
+        toml::from_str(&content).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// The configured default sections, if any.
+    pub fn sections(&self) -> Result<Option<Sections>, String> {
+        self.sections
+            .as_deref()
+            .map(Sections::from_names)
+            .transpose()
+    }
+}
+
+/// `envin/envin.toml` in `$XDG_CONFIG_HOME`, falling back to `~/.config`,
+/// or in `%APPDATA%` on Windows.
+pub fn default_path() -> Option<PathBuf> {
+    let directory = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|directory| !directory.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    }?;
+    Some(directory.join("envin").join("envin.toml"))
+}
@@ -0,0 +1,35 @@
+//! Renders a report as `path,value` rows for spreadsheets and awk.
+
+use {
+    crate::{flatten, format::DisplayOptions},
+    serde_json::Value,
+};
+
+/// Renders every field as a row of its dotted path and its value, separated
+/// by `delimiter`. A comma gives CSV with values quoted where needed, a tab
+/// gives TSV with tabs and newlines in values replaced by spaces. `report`
+/// is the JSON form of a report.
+pub fn render(report: &Value, delimiter: char, options: &DisplayOptions) -> String {
+    flatten::leaves("", report, options)
+        .into_iter()
+        .map(|(path, value)| {
+            format!(
+                "{}{}{}\n",
+                field(&path, delimiter),
+                delimiter,
+                field(&value, delimiter)
+            )
+        })
+        .collect()
+}
+
+/// Quotes `value` for CSV, or flattens it for TSV.
+pub(crate) fn field(value: &str, delimiter: char) -> String {
+    if delimiter == '\t' {
+        value.replace(['\t', '\n', '\r'], " ")
+    } else if value.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,40 @@
+//! Listing the process environment without leaking credentials.
+
+use {
+    crate::{redact::REDACTED, util::glob},
+    std::{collections::BTreeMap, env},
+};
+
+/// Parts of a variable name that mark its value as a secret.
+pub const SECRET_MARKERS: &[&str] = &["TOKEN", "KEY", "PASSWORD", "PASSWD", "SECRET", "CREDENTIAL"];
+
+/// Whether the value of the variable called `name` looks like a secret.
+pub fn is_secret(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    SECRET_MARKERS.iter().any(|marker| name.contains(marker))
+}
+
+/// The variables whose names match any of `patterns`, or all of them if
+/// there are none, sorted by name. A `*` in a pattern matches any run of
+/// characters. Secret-looking values are replaced with [`REDACTED`] unless
+/// `show_secrets` is set.
+pub fn variables<S: AsRef<str>>(patterns: &[S], show_secrets: bool) -> BTreeMap<String, String> {
+    env::vars_os()
+        .map(|(name, value)| {
+            (
+                name.to_string_lossy().into_owned(),
+                value.to_string_lossy().into_owned(),
+            )
+        })
+        .filter(|(name, _)| {
+            patterns.is_empty() || patterns.iter().any(|pattern| glob(pattern.as_ref(), name))
+        })
+        .map(|(name, value)| {
+            if !show_secrets && is_secret(&name) {
+                (name, REDACTED.to_string())
+            } else {
+                (name, value)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,179 @@
+//! Keeping or dropping individual fields of a serialized report.
+//!
+//! A pattern is a short alias from [`template::ALIASES`] or a dotted path
+//! like the ones `--output csv` prints, such as `disk.disks.0.mount_point`.
+//! A `*` matches any part of a single segment, so `disk.disks.*.name` covers
+//! every disk, and `**` matches any number of segments.
+//!
+//! [`template::ALIASES`]: crate::template::ALIASES
+
+use {
+    crate::{template::ALIASES, util::glob, Sections},
+    serde_json::Value,
+};
+
+/// Fields to keep and fields to drop.
+#[derive(Clone, Debug, Default)]
+pub struct FieldFilter {
+    only: Vec<Vec<String>>,
+    exclude: Vec<Vec<String>>,
+}
+
+impl FieldFilter {
+    /// Keeps just the fields matching `only`, or every field if it is empty,
+    /// then drops the fields matching `exclude`.
+    pub fn new<S: AsRef<str>>(only: &[S], exclude: &[S]) -> Result<Self, String> {
+        Ok(Self {
+            only: only.iter().map(parse).collect::<Result<_, _>>()?,
+            exclude: exclude.iter().map(parse).collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.only.is_empty() && self.exclude.is_empty()
+    }
+
+    /// The sections `only` refers to, unless it is empty or starts a pattern
+    /// with a wildcard.
+    pub fn sections(&self) -> Option<Sections> {
+        if self.only.is_empty() {
+            return None;
+        }
+        let names: Option<Vec<&str>> = self
+            .only
+            .iter()
+            .map(|pattern| Some(pattern[0].as_str()).filter(|name| !name.contains('*')))
+            .collect();
+        Sections::from_names(&names?).ok()
+    }
+
+    /// Removes the fields of `report`, the JSON form of a report, that the
+    /// filter leaves out. Sections and entries left without any fields are
+    /// removed too.
+    pub fn apply(&self, report: &mut Value) {
+        if let Value::Object(sections) = report {
+            sections.retain(|name, value| self.keep(&mut vec![name.clone()], value, false));
+        }
+    }
+
+    fn keep(&self, path: &mut Vec<String>, value: &mut Value, included: bool) -> bool {
+        if self.exclude.iter().any(|pattern| matches(pattern, path)) {
+            return false;
+        }
+        let included = included
+            || self.only.is_empty()
+            || self.only.iter().any(|pattern| matches(pattern, path));
+
+        let emptied = match value {
+            Value::Object(fields) => {
+                fields.retain(|key, value| {
+                    path.push(key.clone());
+                    let keep = self.keep(path, value, included);
+                    path.pop();
+                    keep
+                });
+                fields.is_empty()
+            }
+            Value::Array(entries) => {
+                let mut index = 0;
+                entries.retain_mut(|value| {
+                    path.push(index.to_string());
+                    let keep = self.keep(path, value, included);
+                    path.pop();
+                    index += 1;
+                    keep
+                });
+                entries.is_empty()
+            }
+            _ => return included,
+        };
+        included || !emptied
+    }
+}
+
+fn parse(pattern: impl AsRef<str>) -> Result<Vec<String>, String> {
+    let pattern = pattern.as_ref().trim();
+    let path = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == pattern)
+        .map_or(pattern, |(_, path)| path);
+    let segments: Vec<String> = path.split('.').map(str::to_string).collect();
+    if segments.iter().any(String::is_empty) {
+        return Err(format!("invalid field pattern `{}`", pattern));
+    }
+    if !segments[0].contains('*') && Sections::from_names(&segments[..1]).is_err() {
+        return Err(format!("unknown field `{}`", pattern));
+    }
+    Ok(segments)
+}
+
+/// Whether `pattern` matches the whole of `path`.
+fn matches(pattern: &[String], path: &[String]) -> bool {
+    match (pattern.split_first(), path.split_first()) {
+        (None, None) => true,
+        (Some((first, rest)), _) if first == "**" => {
+            (0..=path.len()).any(|skip| matches(rest, &path[skip..]))
+        }
+        (Some((first, rest)), Some((segment, path))) => glob(first, segment) && matches(rest, path),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn resolves_aliases() {
+        assert_eq!(
+            parse("mem_used_bytes").unwrap(),
+            ["system", "memory_used_bytes"]
+        );
+        assert_eq!(parse(" hostname ").unwrap(), ["host", "hostname"]);
+        assert_eq!(
+            parse("disk.disks.*.name").unwrap(),
+            ["disk", "disks", "*", "name"]
+        );
+        assert!(parse("mem_used").is_err());
+        assert!(parse("system..kernel").is_err());
+    }
+
+    #[test]
+    fn keeps_only_and_drops_exclude() {
+        let mut report = json!({
+            "host": { "hostname": "box", "domain": "lan" },
+            "system": { "kernel": "6.1", "memory_used_bytes": 1 },
+            "disk": { "disks": [{ "name": "sda", "total_bytes": 2 }, { "name": "sdb" }] },
+        });
+        let filter = FieldFilter::new(
+            &["hostname", "mem_used_bytes", "disk.disks.*.name"],
+            &["disk.disks.1"],
+        )
+        .unwrap();
+        filter.apply(&mut report);
+        assert_eq!(
+            report,
+            json!({
+                "host": { "hostname": "box" },
+                "system": { "memory_used_bytes": 1 },
+                "disk": { "disks": [{ "name": "sda" }] },
+            })
+        );
+
+        let sections = filter.sections().unwrap();
+        assert!(sections.host && sections.system && sections.disk);
+        assert!(FieldFilter::new(&["**.name"], &[])
+            .unwrap()
+            .sections()
+            .is_none());
+    }
+
+    #[test]
+    fn double_star_spans_segments() {
+        let mut report = json!({ "disk": { "disks": [{ "name": "sda", "kind": "ssd" }] } });
+        FieldFilter::new(&["**.name"], &[])
+            .unwrap()
+            .apply(&mut report);
+        assert_eq!(report, json!({ "disk": { "disks": [{ "name": "sda" }] } }));
+    }
+}
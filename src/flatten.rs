@@ -0,0 +1,108 @@
+//! Splitting the JSON form of a report into the tables and rows the
+//! Markdown, HTML and CSV outputs render.
+
+use {
+    crate::format::{format_bytes, DisplayOptions},
+    serde_json::{Map, Value},
+};
+
+/// One section: its plain fields and its lists of entries, keyed by their
+/// dotted path.
+pub(crate) struct Section<'a> {
+    pub(crate) fields: Vec<(String, String)>,
+    pub(crate) lists: Vec<(String, &'a [Value])>,
+}
+
+impl<'a> Section<'a> {
+    pub(crate) fn new(value: &'a Value, options: &DisplayOptions) -> Self {
+        let mut section = Self {
+            fields: Vec::new(),
+            lists: Vec::new(),
+        };
+        section.collect("", value, options);
+        section
+    }
+
+    fn collect(&mut self, path: &str, value: &'a Value, options: &DisplayOptions) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    self.collect(&join(path, key), value, options);
+                }
+            }
+            Value::Array(entries) if entries.iter().any(Value::is_object) => {
+                self.lists.push((path.to_string(), entries));
+            }
+            value => self
+                .fields
+                .push((path.to_string(), text(path, value, options))),
+        }
+    }
+}
+
+/// The keys of every entry, in order of first appearance.
+pub(crate) fn columns(entries: &[Value]) -> Vec<&String> {
+    let mut columns: Vec<&String> = Vec::new();
+    for entry in entries {
+        for key in entry.as_object().into_iter().flat_map(Map::keys) {
+            if !columns.contains(&key) {
+                columns.push(key);
+            }
+        }
+    }
+    columns
+}
+
+/// Renders a plain value, scaling `*_bytes` fields with `--human` and
+/// joining lists with commas.
+pub(crate) fn text(field: &str, value: &Value, options: &DisplayOptions) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Number(number) if options.human && field.ends_with("_bytes") => number
+            .as_u64()
+            .map(|bytes| format_bytes(bytes, options.units))
+            .unwrap_or_else(|| number.to_string()),
+        Value::Array(values) => values
+            .iter()
+            .map(|value| text(field, value, options))
+            .collect::<Vec<_>>()
+            .join(", "),
+        value => value.to_string(),
+    }
+}
+
+/// `memory_total_bytes` becomes `Memory total bytes`.
+pub(crate) fn title(name: &str) -> String {
+    let name = name.rsplit('.').next().unwrap_or(name).replace('_', " ");
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+pub(crate) fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Every plain value under `value` with its dotted path, counting list
+/// entries by position like `disk.disks.0.mount_point`.
+pub(crate) fn leaves(path: &str, value: &Value, options: &DisplayOptions) -> Vec<(String, String)> {
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .flat_map(|(key, value)| leaves(&join(path, key), value, options))
+            .collect(),
+        Value::Array(entries) => entries
+            .iter()
+            .enumerate()
+            .flat_map(|(index, value)| leaves(&join(path, &index.to_string()), value, options))
+            .collect(),
+        value => vec![(path.to_string(), text(path, value, options))],
+    }
+}
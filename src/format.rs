@@ -0,0 +1,243 @@
+//! Helpers for rendering collected values as text and parsing them back
+//! from command line arguments.
+
+use std::{
+    fmt::Display,
+    io::{self, IsTerminal},
+    time::Duration,
+};
+
+/// Unit system used when scaling byte counts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ByteUnits {
+    /// Powers of 1000: kB, MB, GB, ...
+    Si,
+    /// Powers of 1024: KiB, MiB, GiB, ...
+    #[default]
+    Iec,
+}
+
+/// When to color the text output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether output should be colored, detecting the terminal for `Auto`.
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                io::stdout().is_terminal()
+                    && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                    && std::env::var("TERM").map_or(true, |term| term != "dumb")
+            }
+        }
+    }
+}
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Options for the plain text output.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DisplayOptions {
+    /// Scale byte counts to the largest fitting unit instead of printing
+    /// them exactly.
+    pub human: bool,
+    pub units: ByteUnits,
+    /// List every CPU core in the system section.
+    pub cores: bool,
+    /// Color headings, keys and warnings with ANSI escapes.
+    pub color: bool,
+}
+
+impl DisplayOptions {
+    /// Renders a byte count according to these options.
+    pub fn bytes(&self, bytes: u64) -> String {
+        if self.human {
+            format_bytes(bytes, self.units)
+        } else {
+            format!("{} bytes", bytes)
+        }
+    }
+
+    /// Renders a `used / total` pair of byte counts, flagged as a warning
+    /// once more than 90% is used.
+    pub fn usage(&self, used: u64, total: u64) -> String {
+        let usage = format!("{} / {}", self.bytes(used), self.bytes(total));
+        self.warn_if(used as f64 > total as f64 * 0.9, usage)
+    }
+
+    /// Prints one line of a section. Headings are set in bold, the key in
+    /// front of the first `:` in cyan and missing values dimmed.
+    pub fn line(&self, line: impl Display) {
+        let line = line.to_string();
+        if !self.color {
+            println!("{}", line);
+            return;
+        }
+
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("==========") || trimmed.starts_with("----------") {
+            println!("{}", self.paint(BOLD, &line));
+        } else if let Some((key, value)) = line.split_once(':') {
+            let value = match value.trim() {
+                "Unknown" | "None" => self.paint(DIM, value),
+                _ => value.to_string(),
+            };
+            println!("{}:{}", self.key(key), value);
+        } else {
+            println!("{}", line);
+        }
+    }
+
+    /// Colors `text` as a key.
+    pub fn key(&self, text: &str) -> String {
+        self.paint(CYAN, text)
+    }
+
+    /// Colors `text` as a warning if `condition` holds.
+    pub fn warn_if(&self, condition: bool, text: impl Display) -> String {
+        if condition {
+            self.paint(RED, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn paint(&self, style: &str, text: impl Display) -> String {
+        if self.color {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Formats a byte count with one decimal in the largest fitting unit, e.g.
+/// `15.6 GiB` or `16.8 GB`.
+pub fn format_bytes(bytes: u64, units: ByteUnits) -> String {
+    let (base, suffixes) = match units {
+        ByteUnits::Si => (1000.0, ["kB", "MB", "GB", "TB", "PB", "EB"]),
+        ByteUnits::Iec => (1024.0, ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"]),
+    };
+
+    if (bytes as f64) < base {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64;
+    let mut suffix = suffixes[0];
+    for candidate in suffixes {
+        value /= base;
+        suffix = candidate;
+        if value < base {
+            break;
+        }
+    }
+
+    format!("{:.1} {}", value, suffix)
+}
+
+/// Parses durations such as `500ms`, `2s`, `1.5m` or `1h`. A bare number is
+/// taken as seconds.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(input.len());
+    let (value, unit) = input.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration `{}`", input))?;
+    let seconds = match unit.trim() {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        unit => return Err(format!("unknown duration unit `{}`", unit)),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|err| err.to_string())
+}
+
+/// Parses sizes such as `512`, `64KiB`, `10MB` or `1.5G`. `KB`, `MB` and so
+/// on are powers of 1000, `KiB`, `MiB` and the bare `K`, `M` powers of
+/// 1024; a bare number is bytes.
+pub fn parse_byte_size(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(input.len());
+    let (value, unit) = input.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid size `{}`", input))?;
+    let unit = unit.trim();
+    let exponent = match unit.chars().next().map(|c| c.to_ascii_uppercase()) {
+        None => 0,
+        Some('B') if unit.len() == 1 => 0,
+        Some('K') => 1,
+        Some('M') => 2,
+        Some('G') => 3,
+        Some('T') => 4,
+        Some('P') => 5,
+        _ => return Err(format!("unknown size unit `{}`", unit)),
+    };
+    let base: f64 = match &unit.get(1..).unwrap_or_default().to_ascii_lowercase()[..] {
+        "" | "i" | "ib" => 1024.0,
+        "b" => 1000.0,
+        _ => return Err(format!("unknown size unit `{}`", unit)),
+    };
+
+    Ok((value * base.powi(exponent)) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("2"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration(" 1.5 s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("-1s").is_err());
+        assert!(parse_duration("3d").is_err());
+        assert!(parse_duration("1.2.3s").is_err());
+    }
+
+    #[test]
+    fn parses_byte_sizes() {
+        assert_eq!(parse_byte_size("512"), Ok(512));
+        assert_eq!(parse_byte_size("512B"), Ok(512));
+        assert_eq!(parse_byte_size("64KiB"), Ok(64 * 1024));
+        assert_eq!(parse_byte_size("64k"), Ok(64 * 1024));
+        assert_eq!(parse_byte_size("10MB"), Ok(10_000_000));
+        assert_eq!(parse_byte_size("1.5G"), Ok(1536 * 1024 * 1024));
+        assert_eq!(parse_byte_size(" 2 TiB "), Ok(2 << 40));
+        assert!(parse_byte_size("").is_err());
+        assert!(parse_byte_size("MB").is_err());
+        assert!(parse_byte_size("5XB").is_err());
+        assert!(parse_byte_size("5Mbit").is_err());
+        assert!(parse_byte_size("5BB").is_err());
+    }
+}
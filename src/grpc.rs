@@ -0,0 +1,322 @@
+//! The gRPC service of `envin serve --grpc`, defined in `proto/envin.proto`,
+//! answering from the same latest report as the HTTP listeners.
+
+// tonic's `Status` is large, but it is what every handler has to return.
+#![allow(clippy::result_large_err)]
+
+use {
+    crate::{
+        component::{
+            cpu::{CoreInfo, CpuCache, CpuInfo, CpuUsage},
+            disk::{DiskEntry, DiskInfo},
+            host::HostInfo,
+            load::LoadInfo,
+            memory::MemoryBreakdown,
+            network::{InterfaceInfo, NetworkInfo},
+            process::{ProcessEntry, ProcessInfo},
+            system::SystemInfo,
+            uptime::UptimeInfo,
+        },
+        serve::{is_bearer_token, Latest},
+        Report,
+    },
+    proto::envin_server::{Envin, EnvinServer},
+    std::{io, net::TcpListener, time::Duration},
+    tokio::sync::mpsc,
+    tokio_stream::wrappers::{ReceiverStream, TcpListenerStream},
+    tonic::{transport::Server, Request, Response, Status},
+};
+
+/// The messages and service generated from `proto/envin.proto`.
+pub mod proto {
+    tonic::include_proto!("envin.v1");
+}
+
+/// How many reports a stream buffers for a client that reads slowly.
+const STREAM_BUFFER: usize = 4;
+
+/// Answers gRPC calls on `listener` from `latest` until the process is
+/// stopped. With a `token`, every call must carry
+/// `authorization: Bearer <token>` metadata.
+pub fn serve(listener: TcpListener, latest: Latest, token: Option<String>) -> io::Result<()> {
+    listener.set_nonblocking(true)?;
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+
+    runtime.block_on(async move {
+        let incoming = TcpListenerStream::new(tokio::net::TcpListener::from_std(listener)?);
+        let service = EnvinServer::with_interceptor(Service { latest }, move |request| {
+            authorize(request, token.as_deref())
+        });
+        Server::builder()
+            .add_service(service)
+            .serve_with_incoming(incoming)
+            .await
+            .map_err(io::Error::other)
+    })
+}
+
+fn authorize(request: Request<()>, token: Option<&str>) -> Result<Request<()>, Status> {
+    let Some(token) = token else {
+        return Ok(request);
+    };
+    let authorization = request
+        .metadata()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok());
+    if is_bearer_token(authorization, token) {
+        Ok(request)
+    } else {
+        Err(Status::unauthenticated("missing or wrong bearer token"))
+    }
+}
+
+struct Service {
+    latest: Latest,
+}
+
+impl Service {
+    fn report(&self) -> Result<proto::Report, Status> {
+        self.latest
+            .read()
+            .map(|report| proto::Report::from(&*report))
+            .ok_or_else(|| Status::internal("the latest report is unavailable"))
+    }
+}
+
+#[tonic::async_trait]
+impl Envin for Service {
+    async fn get_report(
+        &self,
+        _: Request<proto::ReportRequest>,
+    ) -> Result<Response<proto::Report>, Status> {
+        self.report().map(Response::new)
+    }
+
+    type StreamReportsStream = ReceiverStream<Result<proto::Report, Status>>;
+
+    async fn stream_reports(
+        &self,
+        request: Request<proto::StreamRequest>,
+    ) -> Result<Response<Self::StreamReportsStream>, Status> {
+        let interval = match request.into_inner().interval_ms {
+            0 => self.latest.interval(),
+            milliseconds => Duration::from_millis(milliseconds),
+        };
+        let service = Service {
+            latest: self.latest.clone(),
+        };
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            loop {
+                ticks.tick().await;
+                // Stops once the client hangs up.
+                if sender.send(service.report()).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+impl From<&Report> for proto::Report {
+    fn from(report: &Report) -> Self {
+        Self {
+            system: report.system.as_ref().map(Into::into),
+            disk: report.disk.as_ref().map(Into::into),
+            network: report.network.as_ref().map(Into::into),
+            load: report.load.as_ref().map(Into::into),
+            uptime: report.uptime.as_ref().map(Into::into),
+            host: report.host.as_ref().map(Into::into),
+            processes: report.processes.as_ref().map(Into::into),
+            json: serde_json::to_string(report).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&SystemInfo> for proto::System {
+    fn from(system: &SystemInfo) -> Self {
+        Self {
+            os: system.os.clone(),
+            os_name: system.os_name.clone(),
+            os_version: system.os_version.clone(),
+            distribution_id: system.distribution_id.clone(),
+            kernel: system.kernel.clone(),
+            cpu: Some((&system.cpu).into()),
+            memory_used_bytes: system.memory_used_bytes,
+            memory_total_bytes: system.memory_total_bytes,
+            memory: system.memory.as_ref().map(Into::into),
+            swap_used_bytes: system.swap_used_bytes,
+            swap_total_bytes: system.swap_total_bytes,
+            shell: system.shell.clone(),
+        }
+    }
+}
+
+impl From<&CpuInfo> for proto::Cpu {
+    fn from(cpu: &CpuInfo) -> Self {
+        Self {
+            brand: cpu.brand.clone(),
+            vendor_id: cpu.vendor_id.clone(),
+            sockets: cpu.sockets.map(|sockets| sockets as u32),
+            physical_cores: cpu.physical_cores as u32,
+            logical_cores: cpu.logical_cores as u32,
+            caches: cpu.caches.iter().map(Into::into).collect(),
+            cores: cpu.cores.iter().map(Into::into).collect(),
+            usage: cpu.usage.as_ref().map(Into::into),
+        }
+    }
+}
+
+impl From<&CpuCache> for proto::CpuCache {
+    fn from(cache: &CpuCache) -> Self {
+        Self {
+            level: cache.level.into(),
+            kind: cache.kind.clone(),
+            size_bytes: cache.size_bytes,
+            instances: cache.instances.map(|instances| instances as u32),
+        }
+    }
+}
+
+impl From<&CoreInfo> for proto::Core {
+    fn from(core: &CoreInfo) -> Self {
+        Self {
+            name: core.name.clone(),
+            frequency_mhz: core.frequency_mhz,
+            min_frequency_mhz: core.min_frequency_mhz,
+            max_frequency_mhz: core.max_frequency_mhz,
+        }
+    }
+}
+
+impl From<&CpuUsage> for proto::CpuUsage {
+    fn from(usage: &CpuUsage) -> Self {
+        Self {
+            interval_ms: usage.interval_ms,
+            global_percent: usage.global_percent,
+            per_core_percent: usage.per_core_percent.clone(),
+        }
+    }
+}
+
+impl From<&MemoryBreakdown> for proto::MemoryBreakdown {
+    fn from(memory: &MemoryBreakdown) -> Self {
+        Self {
+            available_bytes: memory.available_bytes,
+            free_bytes: memory.free_bytes,
+            buffers_bytes: memory.buffers_bytes,
+            cached_bytes: memory.cached_bytes,
+            shared_bytes: memory.shared_bytes,
+            dirty_bytes: memory.dirty_bytes,
+            wired_bytes: memory.wired_bytes,
+            compressed_bytes: memory.compressed_bytes,
+        }
+    }
+}
+
+impl From<&DiskInfo> for proto::Disk {
+    fn from(disk: &DiskInfo) -> Self {
+        Self {
+            disks: disk.disks.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&DiskEntry> for proto::DiskEntry {
+    fn from(disk: &DiskEntry) -> Self {
+        Self {
+            name: disk.name.clone(),
+            file_system: disk.file_system.clone(),
+            mount_point: disk.mount_point.clone(),
+            total_bytes: disk.total_bytes,
+            used_bytes: disk.used_bytes,
+            available_bytes: disk.available_bytes,
+            removable: disk.removable,
+        }
+    }
+}
+
+impl From<&NetworkInfo> for proto::Network {
+    fn from(network: &NetworkInfo) -> Self {
+        Self {
+            interfaces: network.interfaces.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&InterfaceInfo> for proto::Interface {
+    fn from(interface: &InterfaceInfo) -> Self {
+        Self {
+            name: interface.name.clone(),
+            mac_address: interface.mac_address.clone(),
+            ip_addresses: interface.ip_addresses.clone(),
+            received_bytes: interface.received_bytes,
+            transmitted_bytes: interface.transmitted_bytes,
+            received_packets: interface.received_packets,
+            transmitted_packets: interface.transmitted_packets,
+            receive_errors: interface.receive_errors,
+            transmit_errors: interface.transmit_errors,
+        }
+    }
+}
+
+impl From<&LoadInfo> for proto::Load {
+    fn from(load: &LoadInfo) -> Self {
+        Self {
+            one: load.one,
+            five: load.five,
+            fifteen: load.fifteen,
+        }
+    }
+}
+
+impl From<&UptimeInfo> for proto::Uptime {
+    fn from(uptime: &UptimeInfo) -> Self {
+        Self {
+            uptime_secs: uptime.uptime_secs,
+            boot_time_unix: uptime.boot_time_unix,
+            boot_time: uptime.boot_time.clone(),
+        }
+    }
+}
+
+impl From<&HostInfo> for proto::Host {
+    fn from(host: &HostInfo) -> Self {
+        Self {
+            hostname: host.hostname.clone(),
+            username: host.username.clone(),
+            uid: host.uid,
+            gid: host.gid,
+            home: host.home.clone(),
+        }
+    }
+}
+
+impl From<&ProcessInfo> for proto::Processes {
+    fn from(processes: &ProcessInfo) -> Self {
+        Self {
+            processes: processes.processes.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&ProcessEntry> for proto::Process {
+    fn from(process: &ProcessEntry) -> Self {
+        Self {
+            pid: process.pid,
+            parent_pid: process.parent_pid,
+            name: process.name.clone(),
+            user: process.user.clone(),
+            command: process.command.clone(),
+            cpu_usage: process.cpu_usage,
+            memory_bytes: process.memory_bytes,
+            status: process.status.clone(),
+        }
+    }
+}
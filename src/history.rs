@@ -0,0 +1,198 @@
+//! The SQLite history of `envin record --db`, and the summaries of
+//! `envin history` over it.
+
+use {
+    crate::{
+        flatten,
+        format::{format_bytes, DisplayOptions},
+        template::ALIASES,
+    },
+    chrono::{DateTime, Local, SecondsFormat},
+    rusqlite::{params, Connection},
+    serde::Serialize,
+    serde_json::Value,
+    std::path::Path,
+};
+
+/// Every report is kept whole, and its numbers once more as samples so they
+/// can be summarized without parsing the JSON again.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS snapshots (
+        id INTEGER PRIMARY KEY,
+        taken_at INTEGER NOT NULL,
+        report TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS snapshots_taken_at ON snapshots (taken_at);
+    CREATE TABLE IF NOT EXISTS samples (
+        snapshot INTEGER NOT NULL REFERENCES snapshots (id),
+        path TEXT NOT NULL,
+        value REAL NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS samples_path ON samples (path, snapshot);
+";
+
+/// A history database, created on first use.
+pub struct Store {
+    connection: Connection,
+}
+
+/// The values one field took over a stretch of history.
+#[derive(Clone, Debug, Serialize)]
+pub struct Summary {
+    pub path: String,
+    pub count: u64,
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+    /// When the first and last of the values were recorded, in RFC 3339.
+    pub first: String,
+    pub last: String,
+}
+
+impl Store {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let error = |err: rusqlite::Error| format!("{}: {}", path.display(), err);
+        let connection = Connection::open(path).map_err(error)?;
+        connection.execute_batch(SCHEMA).map_err(error)?;
+        Ok(Self { connection })
+    }
+
+    /// Stores `report`, the JSON form of a report, as taken at `time`.
+    pub fn insert(&mut self, report: &Value, time: DateTime<Local>) -> Result<(), String> {
+        let mut samples = Vec::new();
+        numbers("", report, &mut samples);
+
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| err.to_string())?;
+        transaction
+            .execute(
+                "INSERT INTO snapshots (taken_at, report) VALUES (?1, ?2)",
+                params![time.timestamp(), report.to_string()],
+            )
+            .map_err(|err| err.to_string())?;
+        let snapshot = transaction.last_insert_rowid();
+        {
+            let mut insert = transaction
+                .prepare("INSERT INTO samples (snapshot, path, value) VALUES (?1, ?2, ?3)")
+                .map_err(|err| err.to_string())?;
+            for (path, value) in &samples {
+                insert
+                    .execute(params![snapshot, path, value])
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+        transaction.commit().map_err(|err| err.to_string())
+    }
+
+    /// Summarizes every field named `field` recorded at or after `since`, or
+    /// over all of history without it. `field` is a short alias from
+    /// [`ALIASES`] such as `mem_used_bytes`, a dotted path such as
+    /// `system.memory_used_bytes`, or its end such as `used_bytes`, which can
+    /// match several fields like `disk.disks.0.used_bytes` and
+    /// `disk.disks.1.used_bytes`.
+    pub fn summarize(
+        &self,
+        field: &str,
+        since: Option<DateTime<Local>>,
+    ) -> Result<Vec<Summary>, String> {
+        let field = ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == field)
+            .map_or(field, |(_, path)| path);
+        let mut query = self
+            .connection
+            .prepare(
+                "SELECT path, COUNT(*), MIN(value), MAX(value), AVG(value),
+                        MIN(taken_at), MAX(taken_at)
+                 FROM samples JOIN snapshots ON snapshots.id = samples.snapshot
+                 WHERE (path = ?1 OR substr(path, -length(?1) - 1) = '.' || ?1)
+                   AND taken_at >= ?2
+                 GROUP BY path
+                 ORDER BY path",
+            )
+            .map_err(|err| err.to_string())?;
+        let since = since.map_or(i64::MIN, |since| since.timestamp());
+        let rows = query
+            .query_map(params![field, since], |row| {
+                Ok(Summary {
+                    path: row.get(0)?,
+                    count: row.get(1)?,
+                    min: row.get(2)?,
+                    max: row.get(3)?,
+                    avg: row.get(4)?,
+                    first: timestamp(row.get(5)?),
+                    last: timestamp(row.get(6)?),
+                })
+            })
+            .map_err(|err| err.to_string())?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// Prints one row a field, scaling `*_bytes` fields with `--human`.
+pub fn display(summaries: &[Summary], options: &DisplayOptions) {
+    options.line(format_args!("{}History{}", "=".repeat(10), "=".repeat(10)));
+
+    if summaries.is_empty() {
+        options.line("  Samples: None");
+        return;
+    }
+
+    let width = summaries
+        .iter()
+        .map(|summary| summary.path.len())
+        .max()
+        .unwrap_or(0);
+    println!(
+        "  {:<width$}  {:>7}  {:>14}  {:>14}  {:>14}",
+        "Field", "Count", "Min", "Max", "Avg"
+    );
+    for summary in summaries {
+        let value = |value: f64| {
+            if options.human && summary.path.ends_with("_bytes") {
+                format_bytes(value.round() as u64, options.units)
+            } else {
+                format!("{}", (value * 100.0).round() / 100.0)
+            }
+        };
+        println!(
+            "  {:<width$}  {:>7}  {:>14}  {:>14}  {:>14}",
+            summary.path,
+            summary.count,
+            value(summary.min),
+            value(summary.max),
+            value(summary.avg)
+        );
+    }
+}
+
+/// Every number under `value` with its dotted path, counting list entries
+/// by position like `disk.disks.0.used_bytes`.
+fn numbers(path: &str, value: &Value, samples: &mut Vec<(String, f64)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                numbers(&flatten::join(path, key), value, samples);
+            }
+        }
+        Value::Array(entries) => {
+            for (index, value) in entries.iter().enumerate() {
+                numbers(&flatten::join(path, &index.to_string()), value, samples);
+            }
+        }
+        Value::Number(number) => samples.extend(number.as_f64().map(|n| (path.to_string(), n))),
+        _ => {}
+    }
+}
+
+fn timestamp(unix: i64) -> String {
+    DateTime::from_timestamp(unix, 0)
+        .map(|time| {
+            time.with_timezone(&Local)
+                .to_rfc3339_opts(SecondsFormat::Secs, false)
+        })
+        .unwrap_or_default()
+}
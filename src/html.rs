@@ -0,0 +1,169 @@
+//! Renders a report as a single self-contained HTML page.
+
+use {
+    crate::{
+        flatten::{self, Section},
+        format::{format_bytes, DisplayOptions},
+    },
+    chrono::{SecondsFormat, Utc},
+    serde_json::Value,
+    std::fmt::Write,
+};
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 60rem; color: #222; }
+h1 { margin-bottom: 0; }
+.timestamp { color: #666; margin-top: 0.25rem; }
+details { border: 1px solid #ddd; border-radius: 6px; margin: 1rem 0; padding: 0.5rem 1rem; }
+summary { cursor: pointer; font-size: 1.2rem; font-weight: bold; }
+table { border-collapse: collapse; margin: 0.75rem 0; width: 100%; }
+th, td { border-bottom: 1px solid #eee; padding: 0.25rem 0.5rem; text-align: left; vertical-align: top; }
+th { background: #f6f6f6; }
+.chart { margin: 0.75rem 0; }
+.label { display: flex; justify-content: space-between; font-size: 0.9rem; }
+.bar { background: #eee; border-radius: 4px; height: 1rem; overflow: hidden; }
+.fill { background: #4a90d9; height: 100%; }
+.fill.warn { background: #d94a4a; }
+";
+
+/// Renders every collected section as a collapsible block of field tables,
+/// with bar charts for memory, swap and disk usage and the time the page was
+/// generated. Byte counts in the tables are scaled with `--human`. `report`
+/// is the JSON form of a report.
+pub fn render(report: &Value, options: &DisplayOptions) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>envin report</title>\n<style>\n{}</style>\n</head>\n<body>\n\
+         <h1>System Information</h1>\n<p class=\"timestamp\">Generated {}</p>\n",
+        STYLE,
+        Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+
+    let Value::Object(sections) = report else {
+        out.push_str("</body>\n</html>\n");
+        return out;
+    };
+
+    for (name, value) in sections {
+        let _ = writeln!(
+            out,
+            "<details open>\n<summary>{}</summary>",
+            escape(&flatten::title(name))
+        );
+        write_charts(&mut out, name, value, options);
+
+        let section = Section::new(value, options);
+        if !section.fields.is_empty() {
+            out.push_str("<table>\n<tr><th>Field</th><th>Value</th></tr>\n");
+            for (field, value) in &section.fields {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}</td></tr>",
+                    escape(field),
+                    escape(value)
+                );
+            }
+            out.push_str("</table>\n");
+        }
+        for (field, entries) in section.lists {
+            write_list(&mut out, &field, entries, options);
+        }
+        out.push_str("</details>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Writes the usage bars belonging to the section called `name`.
+fn write_charts(out: &mut String, name: &str, section: &Value, options: &DisplayOptions) {
+    let bytes = |value: &Value, field: &str| value.get(field).and_then(Value::as_u64);
+    match name {
+        "system" => {
+            for (label, used, total) in [
+                ("Memory", "memory_used_bytes", "memory_total_bytes"),
+                ("Swap", "swap_used_bytes", "swap_total_bytes"),
+            ] {
+                // Machines without swap report a total of zero.
+                if let (Some(used), Some(total @ 1..)) =
+                    (bytes(section, used), bytes(section, total))
+                {
+                    write_bar(out, label, used, total, options);
+                }
+            }
+        }
+        "disk" => {
+            let disks = section.get("disks").and_then(Value::as_array);
+            for disk in disks.into_iter().flatten() {
+                let label = disk
+                    .get("mount_point")
+                    .and_then(Value::as_str)
+                    .unwrap_or("Disk");
+                if let (Some(used), Some(total)) =
+                    (bytes(disk, "used_bytes"), bytes(disk, "total_bytes"))
+                {
+                    write_bar(out, label, used, total, options);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Writes a bar filled to `used / total`, drawn as a warning once more than
+/// 90% is used.
+fn write_bar(out: &mut String, label: &str, used: u64, total: u64, options: &DisplayOptions) {
+    let percent = if total == 0 {
+        0.0
+    } else {
+        (used as f64 / total as f64 * 100.0).min(100.0)
+    };
+    let _ = writeln!(
+        out,
+        "<div class=\"chart\"><div class=\"label\"><span>{}</span><span>{} / {} ({:.1}%)</span></div>\
+         <div class=\"bar\"><div class=\"fill{}\" style=\"width: {:.1}%\"></div></div></div>",
+        escape(label),
+        format_bytes(used, options.units),
+        format_bytes(total, options.units),
+        percent,
+        if percent > 90.0 { " warn" } else { "" },
+        percent
+    );
+}
+
+fn write_list(out: &mut String, field: &str, entries: &[Value], options: &DisplayOptions) {
+    let _ = writeln!(out, "<h3>{}</h3>", escape(&flatten::title(field)));
+    if entries.is_empty() {
+        out.push_str("<p>None</p>\n");
+        return;
+    }
+
+    let columns = flatten::columns(entries);
+    out.push_str("<table>\n<tr>");
+    for column in &columns {
+        let _ = write!(out, "<th>{}</th>", escape(column));
+    }
+    out.push_str("</tr>\n");
+    for entry in entries {
+        out.push_str("<tr>");
+        for column in &columns {
+            let value = entry
+                .get(column.as_str())
+                .map(|value| flatten::text(column, value, options))
+                .unwrap_or_default();
+            let _ = write!(out, "<td>{}</td>", escape(&value));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+}
+
+/// Escapes text for use in element content and attribute values.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
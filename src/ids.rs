@@ -0,0 +1,74 @@
+//! The PCI and USB ID databases shipped by `hwdata`, `pciutils` and
+//! `usbutils`, used to turn numeric vendor and device IDs into names.
+
+pub(crate) struct IdDatabase {
+    content: String,
+}
+
+impl IdDatabase {
+    pub(crate) fn pci() -> Option<Self> {
+        Self::load(&[
+            "/usr/share/hwdata/pci.ids",
+            "/usr/share/misc/pci.ids",
+            "/usr/share/pci.ids",
+        ])
+    }
+
+    pub(crate) fn usb() -> Option<Self> {
+        Self::load(&[
+            "/usr/share/hwdata/usb.ids",
+            "/usr/share/misc/usb.ids",
+            "/usr/share/usb.ids",
+            "/var/lib/usbutils/usb.ids",
+        ])
+    }
+
+    fn load(paths: &[&str]) -> Option<Self> {
+        paths
+            .iter()
+            .find_map(|path| std::fs::read_to_string(path).ok())
+            .map(|content| Self { content })
+    }
+
+    pub(crate) fn names(&self, vendor: u16, device: u16) -> (Option<String>, Option<String>) {
+        let vendor_prefix = format!("{:04x}  ", vendor);
+        let device_prefix = format!("\t{:04x}  ", device);
+
+        let mut lines = self
+            .content
+            .lines()
+            .skip_while(|line| !line.starts_with(&vendor_prefix));
+        let Some(vendor_line) = lines.next() else {
+            return (None, None);
+        };
+
+        let device_name = lines
+            .take_while(|line| line.starts_with('\t') || line.starts_with('#'))
+            .find_map(|line| line.strip_prefix(&device_prefix))
+            .map(str::to_string);
+
+        (
+            Some(vendor_line[vendor_prefix.len()..].to_string()),
+            device_name,
+        )
+    }
+
+    /// Looks up a PCI device class in the `C` section at the end of
+    /// `pci.ids`, preferring the subclass name.
+    pub(crate) fn class_name(&self, class: u8, subclass: u8) -> Option<String> {
+        let class_prefix = format!("C {:02x}  ", class);
+        let subclass_prefix = format!("\t{:02x}  ", subclass);
+
+        let mut lines = self
+            .content
+            .lines()
+            .skip_while(|line| !line.starts_with(&class_prefix));
+        let class_line = lines.next()?;
+
+        lines
+            .take_while(|line| line.starts_with('\t') || line.starts_with('#'))
+            .find_map(|line| line.strip_prefix(&subclass_prefix))
+            .or(class_line.strip_prefix(&class_prefix))
+            .map(str::to_string)
+    }
+}
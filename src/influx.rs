@@ -0,0 +1,226 @@
+//! Renders a report in the InfluxDB line protocol, one point a measurement
+//! tagged with the host, ready to pipe to `/api/v2/write` or Telegraf.
+
+use {
+    crate::{Report, Sections},
+    std::fmt::Write,
+};
+
+/// The sections rendered when none are selected explicitly: those the
+/// Prometheus exposition covers, and the host for the `host` tag.
+pub fn sections() -> Sections {
+    Sections {
+        host: true,
+        ..crate::prometheus::sections()
+    }
+}
+
+/// Renders the memory, CPU, load, uptime, disk and network figures of the
+/// report, stamped with `timestamp` in nanoseconds since the Unix epoch.
+pub fn render(report: &Report, timestamp: i64) -> String {
+    let mut host = Vec::new();
+    if let Some(info) = &report.host {
+        host.push(("host", info.hostname.as_str()));
+    }
+    let mut lines = Lines {
+        out: String::new(),
+        timestamp,
+    };
+
+    if let Some(system) = &report.system {
+        let mut memory = vec![
+            ("used_bytes", Field::Integer(system.memory_used_bytes)),
+            ("total_bytes", Field::Integer(system.memory_total_bytes)),
+        ];
+        if let Some(breakdown) = &system.memory {
+            memory.push(("available_bytes", Field::Integer(breakdown.available_bytes)));
+        }
+        lines.point("memory", &host, &memory);
+        lines.point(
+            "swap",
+            &host,
+            &[
+                ("used_bytes", Field::Integer(system.swap_used_bytes)),
+                ("total_bytes", Field::Integer(system.swap_total_bytes)),
+            ],
+        );
+        if let Some(usage) = &system.cpu.usage {
+            lines.point(
+                "cpu",
+                &tags(&host, [("cpu", "total")]),
+                &[("usage_percent", Field::Float(usage.global_percent.into()))],
+            );
+            for (index, core) in usage.per_core_percent.iter().enumerate() {
+                lines.point(
+                    "cpu",
+                    &tags(&host, [("cpu", index.to_string().as_str())]),
+                    &[("usage_percent", Field::Float((*core).into()))],
+                );
+            }
+        }
+    }
+    if let Some(load) = &report.load {
+        lines.point(
+            "load",
+            &host,
+            &[
+                ("one", Field::Float(load.one)),
+                ("five", Field::Float(load.five)),
+                ("fifteen", Field::Float(load.fifteen)),
+            ],
+        );
+    }
+    if let Some(uptime) = &report.uptime {
+        lines.point(
+            "uptime",
+            &host,
+            &[("seconds", Field::Integer(uptime.uptime_secs))],
+        );
+    }
+    if let Some(disk) = &report.disk {
+        for entry in &disk.disks {
+            lines.point(
+                "disk",
+                &tags(
+                    &host,
+                    [
+                        ("mount", entry.mount_point.as_str()),
+                        ("device", entry.name.as_str()),
+                        ("fstype", entry.file_system.as_str()),
+                    ],
+                ),
+                &[
+                    ("used_bytes", Field::Integer(entry.used_bytes)),
+                    ("total_bytes", Field::Integer(entry.total_bytes)),
+                    ("available_bytes", Field::Integer(entry.available_bytes)),
+                ],
+            );
+        }
+    }
+    if let Some(network) = &report.network {
+        for interface in &network.interfaces {
+            lines.point(
+                "network",
+                &tags(&host, [("interface", interface.name.as_str())]),
+                &[
+                    ("received_bytes", Field::Integer(interface.received_bytes)),
+                    (
+                        "transmitted_bytes",
+                        Field::Integer(interface.transmitted_bytes),
+                    ),
+                    (
+                        "received_packets",
+                        Field::Integer(interface.received_packets),
+                    ),
+                    (
+                        "transmitted_packets",
+                        Field::Integer(interface.transmitted_packets),
+                    ),
+                    ("receive_errors", Field::Integer(interface.receive_errors)),
+                    ("transmit_errors", Field::Integer(interface.transmit_errors)),
+                ],
+            );
+        }
+    }
+
+    lines.out
+}
+
+enum Field {
+    Integer(u64),
+    Float(f64),
+}
+
+type Tags<'a> = [(&'static str, &'a str)];
+
+fn tags<'a, const N: usize>(
+    host: &Tags<'a>,
+    more: [(&'static str, &'a str); N],
+) -> Vec<(&'static str, &'a str)> {
+    host.iter().copied().chain(more).collect()
+}
+
+struct Lines {
+    out: String,
+    timestamp: i64,
+}
+
+impl Lines {
+    /// Writes `measurement,tag=value field=value timestamp`. Tags are left
+    /// out when empty, which the protocol does not allow.
+    fn point(&mut self, measurement: &str, tags: &Tags, fields: &[(&str, Field)]) {
+        self.out.push_str(&escape(measurement, &[',', ' ']));
+        for (key, value) in tags {
+            if !value.is_empty() {
+                let _ = write!(self.out, ",{}={}", key, escape(value, &[',', '=', ' ']));
+            }
+        }
+        for (index, (key, value)) in fields.iter().enumerate() {
+            self.out.push(if index == 0 { ' ' } else { ',' });
+            let _ = match value {
+                Field::Integer(value) => write!(self.out, "{}={}i", key, value),
+                Field::Float(value) => write!(self.out, "{}={}", key, float(*value)),
+            };
+        }
+        let _ = writeln!(self.out, " {}", self.timestamp);
+    }
+}
+
+/// Rounds to two decimals, since widening an `f32` would write digits it
+/// never had. `NaN` and infinities have no line protocol form and become 0.
+fn float(value: f64) -> f64 {
+    if value.is_finite() {
+        (value * 100.0).round() / 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Backslash-escapes the `special` characters.
+fn escape(text: &str, special: &[char]) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if special.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_measurements_and_tag_values() {
+        let mut lines = Lines {
+            out: String::new(),
+            timestamp: 7,
+        };
+        lines.point(
+            "disk io,total",
+            &[
+                ("mount", "C:\\Program Files"),
+                ("device", "a=b,c"),
+                ("empty", ""),
+            ],
+            &[
+                ("used_bytes", Field::Integer(1)),
+                ("ratio", Field::Float(0.125)),
+            ],
+        );
+        assert_eq!(
+            lines.out,
+            "disk\\ io\\,total,mount=C:\\Program\\ Files,device=a\\=b\\,c \
+             used_bytes=1i,ratio=0.13 7\n"
+        );
+    }
+
+    #[test]
+    fn escape_leaves_other_characters() {
+        assert_eq!(escape("/mnt/a b", &[',', ' ']), "/mnt/a\\ b");
+        assert_eq!(escape("x=y", &[',', ' ']), "x=y");
+        assert_eq!(float(f64::NAN), 0.0);
+    }
+}
@@ -0,0 +1,1391 @@
+//! Collects information about the machine and the Rust environment it runs.
+//!
+//! This is the library behind the `envin` binary. Pick the sections to
+//! collect, then print the report or serialize it with any serde format:
+//!
+//! ```no_run
+//! use envin::{format::DisplayOptions, Report};
+//!
+//! let report = Report::builder().system().rust().build();
+//! report.display(&DisplayOptions::default());
+//! ```
+
+pub mod api;
+pub mod check;
+pub mod component;
+pub mod config;
+pub mod csv;
+pub mod environment;
+pub mod filter;
+mod flatten;
+pub mod format;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "sqlite")]
+pub mod history;
+pub mod html;
+mod ids;
+pub mod influx;
+pub mod logger;
+pub mod markdown;
+pub mod mqtt;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod pretty;
+pub mod prometheus;
+pub mod push;
+pub mod record;
+pub mod redact;
+pub mod serve;
+pub mod snapshot;
+pub mod statsd;
+pub mod template;
+#[cfg(feature = "tui")]
+pub mod tui;
+mod util;
+
+use {
+    component::{
+        android::AndroidInfo,
+        audio::AudioInfo,
+        battery::BatteryInfo,
+        bluetooth::BluetoothInfo,
+        board::BoardInfo,
+        cgroup::CgroupInfo,
+        cloud::CloudInfo,
+        compute::ComputeInfo,
+        container_engine::ContainerEngineInfo,
+        cpu_flags::CpuFlagsInfo,
+        cpu_vulnerabilities::CpuVulnerabilitiesInfo,
+        desktop::DesktopInfo,
+        disk::DiskInfo,
+        disk_io::DiskIoInfo,
+        display::DisplayInfo,
+        dns::DnsInfo,
+        firmware::FirmwareInfo,
+        gpu::GpuInfo,
+        graphics_api::GraphicsApiInfo,
+        host::HostInfo,
+        hugepages::HugepagesInfo,
+        k8s::KubernetesInfo,
+        kmod::KernelModuleInfo,
+        load::LoadInfo,
+        locale::LocaleInfo,
+        macos::MacosInfo,
+        memory_modules::MemoryModulesInfo,
+        mounts::MountsInfo,
+        net_usage::NetUsageInfo,
+        network::NetworkInfo,
+        numa::NumaInfo,
+        packages::PackagesInfo,
+        pci::PciInfo,
+        ports::PortsInfo,
+        power::PowerInfo,
+        process::ProcessInfo,
+        project::ProjectInfo,
+        public_ip::{self, PublicIpInfo},
+        routes::RoutesInfo,
+        rust::RustInfo,
+        sensor::SensorInfo,
+        services::ServicesInfo,
+        shell::ShellInfo,
+        smart::SmartInfo,
+        system::SystemInfo,
+        toolchain::ToolchainInfo,
+        uptime::UptimeInfo,
+        usb::UsbInfo,
+        virt::VirtInfo,
+        wifi::WifiInfo,
+        windows::WindowsInfo,
+    },
+    format::DisplayOptions,
+    serde::{Deserialize, Serialize},
+    std::time::Duration,
+};
+
+pub use component::process::{ProcessOptions, ProcessSort};
+
+/// The sections the report should contain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sections {
+    pub system: bool,
+    pub disk: bool,
+    pub network: bool,
+    pub load: bool,
+    pub uptime: bool,
+    pub host: bool,
+    pub processes: bool,
+    pub sensors: bool,
+    pub gpu: bool,
+    pub battery: bool,
+    pub virt: bool,
+    pub desktop: bool,
+    pub shell: bool,
+    pub locale: bool,
+    pub toolchain: bool,
+    pub packages: bool,
+    pub kernel_modules: bool,
+    pub services: bool,
+    pub usb: bool,
+    pub pci: bool,
+    pub display: bool,
+    pub audio: bool,
+    pub bluetooth: bool,
+    pub wifi: bool,
+    pub public_ip: bool,
+    pub dns: bool,
+    pub routes: bool,
+    pub ports: bool,
+    pub mounts: bool,
+    pub smart: bool,
+    pub disk_io: bool,
+    pub net_usage: bool,
+    pub cgroup: bool,
+    pub numa: bool,
+    pub cpu_flags: bool,
+    pub cpu_vulnerabilities: bool,
+    pub board: bool,
+    pub firmware: bool,
+    pub power: bool,
+    pub memory_modules: bool,
+    pub hugepages: bool,
+    pub graphics_api: bool,
+    pub compute: bool,
+    pub cloud: bool,
+    pub k8s: bool,
+    pub container_engine: bool,
+    pub macos: bool,
+    pub windows: bool,
+    pub android: bool,
+    pub rust: bool,
+    pub project: bool,
+}
+
+impl Sections {
+    /// No sections at all.
+    pub fn none() -> Self {
+        Self {
+            system: false,
+            disk: false,
+            network: false,
+            load: false,
+            uptime: false,
+            host: false,
+            processes: false,
+            sensors: false,
+            gpu: false,
+            battery: false,
+            virt: false,
+            desktop: false,
+            shell: false,
+            locale: false,
+            toolchain: false,
+            packages: false,
+            kernel_modules: false,
+            services: false,
+            usb: false,
+            pci: false,
+            display: false,
+            audio: false,
+            bluetooth: false,
+            wifi: false,
+            public_ip: false,
+            dns: false,
+            routes: false,
+            ports: false,
+            mounts: false,
+            smart: false,
+            disk_io: false,
+            net_usage: false,
+            cgroup: false,
+            numa: false,
+            cpu_flags: false,
+            cpu_vulnerabilities: false,
+            board: false,
+            firmware: false,
+            power: false,
+            memory_modules: false,
+            hugepages: false,
+            graphics_api: false,
+            compute: false,
+            cloud: false,
+            k8s: false,
+            container_engine: false,
+            macos: false,
+            windows: false,
+            android: false,
+            rust: false,
+            project: false,
+        }
+    }
+
+    /// The sections selected in either.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            system: self.system || other.system,
+            disk: self.disk || other.disk,
+            network: self.network || other.network,
+            load: self.load || other.load,
+            uptime: self.uptime || other.uptime,
+            host: self.host || other.host,
+            processes: self.processes || other.processes,
+            sensors: self.sensors || other.sensors,
+            gpu: self.gpu || other.gpu,
+            battery: self.battery || other.battery,
+            virt: self.virt || other.virt,
+            desktop: self.desktop || other.desktop,
+            shell: self.shell || other.shell,
+            locale: self.locale || other.locale,
+            toolchain: self.toolchain || other.toolchain,
+            packages: self.packages || other.packages,
+            kernel_modules: self.kernel_modules || other.kernel_modules,
+            services: self.services || other.services,
+            usb: self.usb || other.usb,
+            pci: self.pci || other.pci,
+            display: self.display || other.display,
+            audio: self.audio || other.audio,
+            bluetooth: self.bluetooth || other.bluetooth,
+            wifi: self.wifi || other.wifi,
+            public_ip: self.public_ip || other.public_ip,
+            dns: self.dns || other.dns,
+            routes: self.routes || other.routes,
+            ports: self.ports || other.ports,
+            mounts: self.mounts || other.mounts,
+            smart: self.smart || other.smart,
+            disk_io: self.disk_io || other.disk_io,
+            net_usage: self.net_usage || other.net_usage,
+            cgroup: self.cgroup || other.cgroup,
+            numa: self.numa || other.numa,
+            cpu_flags: self.cpu_flags || other.cpu_flags,
+            cpu_vulnerabilities: self.cpu_vulnerabilities || other.cpu_vulnerabilities,
+            board: self.board || other.board,
+            firmware: self.firmware || other.firmware,
+            power: self.power || other.power,
+            memory_modules: self.memory_modules || other.memory_modules,
+            hugepages: self.hugepages || other.hugepages,
+            graphics_api: self.graphics_api || other.graphics_api,
+            compute: self.compute || other.compute,
+            cloud: self.cloud || other.cloud,
+            k8s: self.k8s || other.k8s,
+            container_engine: self.container_engine || other.container_engine,
+            macos: self.macos || other.macos,
+            windows: self.windows || other.windows,
+            android: self.android || other.android,
+            rust: self.rust || other.rust,
+            project: self.project || other.project,
+        }
+    }
+
+    /// Parses section names as the command line flags spell them, e.g.
+    /// `system` or `net`.
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Result<Self, String> {
+        let mut sections = Self::none();
+        for name in names {
+            let section = match name.as_ref() {
+                "system" => &mut sections.system,
+                "disk" => &mut sections.disk,
+                "net" | "network" => &mut sections.network,
+                "load" => &mut sections.load,
+                "uptime" => &mut sections.uptime,
+                "host" => &mut sections.host,
+                "processes" => &mut sections.processes,
+                "sensors" => &mut sections.sensors,
+                "gpu" => &mut sections.gpu,
+                "battery" => &mut sections.battery,
+                "virt" | "virtualization" => &mut sections.virt,
+                "desktop" => &mut sections.desktop,
+                "shell" => &mut sections.shell,
+                "locale" => &mut sections.locale,
+                "toolchain" => &mut sections.toolchain,
+                "packages" => &mut sections.packages,
+                "kernel-modules" | "kernel_modules" | "kmod" => &mut sections.kernel_modules,
+                "services" => &mut sections.services,
+                "usb" => &mut sections.usb,
+                "pci" => &mut sections.pci,
+                "display" => &mut sections.display,
+                "audio" => &mut sections.audio,
+                "bluetooth" => &mut sections.bluetooth,
+                "wifi" => &mut sections.wifi,
+                "public-ip" | "public_ip" => &mut sections.public_ip,
+                "dns" => &mut sections.dns,
+                "routes" => &mut sections.routes,
+                "ports" => &mut sections.ports,
+                "mounts" => &mut sections.mounts,
+                "smart" => &mut sections.smart,
+                "disk-io" | "disk_io" | "diskio" => &mut sections.disk_io,
+                "net-usage" | "net_usage" => &mut sections.net_usage,
+                "cgroup" | "cgroups" | "container-limits" => &mut sections.cgroup,
+                "numa" => &mut sections.numa,
+                "cpu-flags" | "cpu_flags" | "flags" => &mut sections.cpu_flags,
+                "cpu-vulnerabilities" | "cpu_vulnerabilities" | "vulnerabilities" | "vulns" => {
+                    &mut sections.cpu_vulnerabilities
+                }
+                "board" | "bios" | "dmi" | "motherboard" => &mut sections.board,
+                "firmware" | "uefi" | "secure-boot" => &mut sections.firmware,
+                "power" | "governor" => &mut sections.power,
+                "memory-modules" | "memory_modules" | "dimm" | "dimms" => {
+                    &mut sections.memory_modules
+                }
+                "hugepages" | "thp" => &mut sections.hugepages,
+                "graphics-api" | "graphics_api" | "vulkan" | "opengl" => &mut sections.graphics_api,
+                "compute" | "cuda" | "rocm" | "oneapi" => &mut sections.compute,
+                "cloud" => &mut sections.cloud,
+                "k8s" | "kubernetes" => &mut sections.k8s,
+                "container-engine" | "container_engine" | "docker" | "podman" => {
+                    &mut sections.container_engine
+                }
+                "macos" | "mac" => &mut sections.macos,
+                "windows" => &mut sections.windows,
+                "android" => &mut sections.android,
+                "rust" => &mut sections.rust,
+                "project" => &mut sections.project,
+                name => return Err(format!("unknown section `{}`", name)),
+            };
+            *section = true;
+        }
+        Ok(sections)
+    }
+
+    /// The selected sections `envin` has no probe for on the platform it was
+    /// built for, by the names [`Sections::from_names`] takes. They would
+    /// come back empty.
+    pub fn unsupported(&self) -> Vec<String> {
+        let linux = cfg!(any(target_os = "linux", target_os = "android"));
+        let windows = cfg!(target_os = "windows");
+        let macos = cfg!(target_os = "macos");
+        let bsd = util::is_bsd();
+        [
+            ("kernel-modules", self.kernel_modules, linux),
+            ("services", self.services, linux),
+            ("usb", self.usb, linux),
+            ("pci", self.pci, linux || windows),
+            ("audio", self.audio, !bsd),
+            ("bluetooth", self.bluetooth, !bsd),
+            ("wifi", self.wifi, !bsd),
+            ("disk-io", self.disk_io, linux || windows),
+            ("cgroup", self.cgroup, linux),
+            ("numa", self.numa, linux),
+            ("cpu-vulnerabilities", self.cpu_vulnerabilities, linux),
+            (
+                "memory-modules",
+                self.memory_modules,
+                linux || windows || macos,
+            ),
+            ("hugepages", self.hugepages, linux),
+            ("macos", self.macos, macos),
+            ("windows", self.windows, windows),
+            ("android", self.android, cfg!(target_os = "android")),
+        ]
+        .into_iter()
+        .filter(|(_, selected, supported)| *selected && !supported)
+        .map(|(name, _, _)| name.to_string())
+        .collect()
+    }
+
+    /// Every section, including the opt-in ones, except the public IP
+    /// lookup, which asks an outside service and is only made on request.
+    pub fn all() -> Self {
+        Self {
+            system: true,
+            disk: true,
+            network: true,
+            load: true,
+            uptime: true,
+            host: true,
+            processes: true,
+            sensors: true,
+            gpu: true,
+            battery: true,
+            virt: true,
+            desktop: true,
+            shell: true,
+            locale: true,
+            toolchain: true,
+            packages: true,
+            kernel_modules: true,
+            services: true,
+            usb: true,
+            pci: true,
+            display: true,
+            audio: true,
+            bluetooth: true,
+            wifi: true,
+            public_ip: false,
+            dns: true,
+            routes: true,
+            ports: true,
+            mounts: true,
+            smart: true,
+            disk_io: true,
+            net_usage: true,
+            cgroup: true,
+            numa: true,
+            cpu_flags: true,
+            cpu_vulnerabilities: true,
+            board: true,
+            firmware: true,
+            power: true,
+            memory_modules: true,
+            hugepages: true,
+            graphics_api: true,
+            compute: true,
+            cloud: true,
+            k8s: true,
+            container_engine: true,
+            macos: true,
+            windows: true,
+            android: true,
+            rust: true,
+            project: true,
+        }
+    }
+}
+
+impl Default for Sections {
+    /// The sections `envin` prints when none are selected explicitly.
+    fn default() -> Self {
+        Self {
+            system: true,
+            disk: true,
+            network: true,
+            load: true,
+            rust: true,
+            project: true,
+            ..Self::none()
+        }
+    }
+}
+
+/// Everything `envin` collected, one optional entry per section.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Report {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<SystemInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk: Option<DiskInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<NetworkInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub load: Option<LoadInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uptime: Option<UptimeInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<HostInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub processes: Option<ProcessInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensors: Option<SensorInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu: Option<GpuInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub battery: Option<BatteryInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virt: Option<VirtInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desktop: Option<DesktopInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<ShellInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<LocaleInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<ToolchainInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packages: Option<PackagesInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kernel_modules: Option<KernelModuleInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub services: Option<ServicesInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usb: Option<UsbInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pci: Option<PciInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<DisplayInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bluetooth: Option<BluetoothInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wifi: Option<WifiInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_ip: Option<PublicIpInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns: Option<DnsInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routes: Option<RoutesInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ports: Option<PortsInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mounts: Option<MountsInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart: Option<SmartInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_io: Option<DiskIoInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_usage: Option<NetUsageInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cgroup: Option<CgroupInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numa: Option<NumaInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_flags: Option<CpuFlagsInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_vulnerabilities: Option<CpuVulnerabilitiesInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub board: Option<BoardInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub firmware: Option<FirmwareInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power: Option<PowerInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_modules: Option<MemoryModulesInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hugepages: Option<HugepagesInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graphics_api: Option<GraphicsApiInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute: Option<ComputeInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloud: Option<CloudInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub k8s: Option<KubernetesInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_engine: Option<ContainerEngineInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub macos: Option<MacosInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub windows: Option<WindowsInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub android: Option<AndroidInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rust: Option<RustInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<ProjectInfo>,
+    /// The selected sections the collecting platform has no probe for.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unsupported: Vec<String>,
+}
+
+impl Report {
+    /// Starts selecting the sections to collect.
+    pub fn builder() -> ReportBuilder {
+        ReportBuilder::new()
+    }
+
+    /// The sections this report contains.
+    pub fn sections(&self) -> Sections {
+        Sections {
+            system: self.system.is_some(),
+            disk: self.disk.is_some(),
+            network: self.network.is_some(),
+            load: self.load.is_some(),
+            uptime: self.uptime.is_some(),
+            host: self.host.is_some(),
+            processes: self.processes.is_some(),
+            sensors: self.sensors.is_some(),
+            gpu: self.gpu.is_some(),
+            battery: self.battery.is_some(),
+            virt: self.virt.is_some(),
+            desktop: self.desktop.is_some(),
+            shell: self.shell.is_some(),
+            locale: self.locale.is_some(),
+            toolchain: self.toolchain.is_some(),
+            packages: self.packages.is_some(),
+            kernel_modules: self.kernel_modules.is_some(),
+            services: self.services.is_some(),
+            usb: self.usb.is_some(),
+            pci: self.pci.is_some(),
+            display: self.display.is_some(),
+            audio: self.audio.is_some(),
+            bluetooth: self.bluetooth.is_some(),
+            wifi: self.wifi.is_some(),
+            public_ip: self.public_ip.is_some(),
+            dns: self.dns.is_some(),
+            routes: self.routes.is_some(),
+            ports: self.ports.is_some(),
+            mounts: self.mounts.is_some(),
+            smart: self.smart.is_some(),
+            disk_io: self.disk_io.is_some(),
+            net_usage: self.net_usage.is_some(),
+            cgroup: self.cgroup.is_some(),
+            numa: self.numa.is_some(),
+            cpu_flags: self.cpu_flags.is_some(),
+            cpu_vulnerabilities: self.cpu_vulnerabilities.is_some(),
+            board: self.board.is_some(),
+            firmware: self.firmware.is_some(),
+            power: self.power.is_some(),
+            memory_modules: self.memory_modules.is_some(),
+            hugepages: self.hugepages.is_some(),
+            graphics_api: self.graphics_api.is_some(),
+            compute: self.compute.is_some(),
+            cloud: self.cloud.is_some(),
+            k8s: self.k8s.is_some(),
+            container_engine: self.container_engine.is_some(),
+            macos: self.macos.is_some(),
+            windows: self.windows.is_some(),
+            android: self.android.is_some(),
+            rust: self.rust.is_some(),
+            project: self.project.is_some(),
+        }
+    }
+
+    /// Replaces the text at each dotted path in `fields` with
+    /// [`redact::REDACTED`].
+    pub fn redact(&mut self, fields: &[String]) {
+        if fields.is_empty() {
+            return;
+        }
+        let Ok(mut value) = serde_json::to_value(&*self) else {
+            return;
+        };
+        for field in fields {
+            redact::redact(&mut value, field);
+        }
+        if let Ok(report) = serde_json::from_value(value) {
+            *self = report;
+        }
+    }
+
+    /// Prints the collected sections as plain text.
+    pub fn display(&self, options: &DisplayOptions) {
+        if let Some(system_info) = &self.system {
+            system_info.display(options);
+            println!();
+        }
+
+        if let Some(disk_info) = &self.disk {
+            disk_info.display(options);
+            println!();
+        }
+
+        if let Some(network_info) = &self.network {
+            network_info.display(options);
+            println!();
+        }
+
+        if let Some(load_info) = &self.load {
+            load_info.display(options);
+            println!();
+        }
+
+        if let Some(uptime_info) = &self.uptime {
+            uptime_info.display(options);
+            println!();
+        }
+
+        if let Some(host_info) = &self.host {
+            host_info.display(options);
+            println!();
+        }
+
+        if let Some(process_info) = &self.processes {
+            process_info.display(options);
+            println!();
+        }
+
+        if let Some(sensor_info) = &self.sensors {
+            sensor_info.display(options);
+            println!();
+        }
+
+        if let Some(gpu_info) = &self.gpu {
+            gpu_info.display(options);
+            println!();
+        }
+
+        if let Some(battery_info) = &self.battery {
+            battery_info.display(options);
+            println!();
+        }
+
+        if let Some(virt_info) = &self.virt {
+            virt_info.display(options);
+            println!();
+        }
+
+        if let Some(desktop_info) = &self.desktop {
+            desktop_info.display(options);
+            println!();
+        }
+
+        if let Some(shell_info) = &self.shell {
+            shell_info.display(options);
+            println!();
+        }
+
+        if let Some(locale_info) = &self.locale {
+            locale_info.display(options);
+            println!();
+        }
+
+        if let Some(toolchain_info) = &self.toolchain {
+            toolchain_info.display(options);
+            println!();
+        }
+
+        if let Some(packages_info) = &self.packages {
+            packages_info.display(options);
+            println!();
+        }
+
+        if let Some(kernel_modules_info) = &self.kernel_modules {
+            kernel_modules_info.display(options);
+            println!();
+        }
+
+        if let Some(services_info) = &self.services {
+            services_info.display(options);
+            println!();
+        }
+
+        if let Some(usb_info) = &self.usb {
+            usb_info.display(options);
+            println!();
+        }
+
+        if let Some(pci_info) = &self.pci {
+            pci_info.display(options);
+            println!();
+        }
+
+        if let Some(display_info) = &self.display {
+            display_info.display(options);
+            println!();
+        }
+
+        if let Some(audio_info) = &self.audio {
+            audio_info.display(options);
+            println!();
+        }
+
+        if let Some(bluetooth_info) = &self.bluetooth {
+            bluetooth_info.display(options);
+            println!();
+        }
+
+        if let Some(wifi_info) = &self.wifi {
+            wifi_info.display(options);
+            println!();
+        }
+
+        if let Some(public_ip_info) = &self.public_ip {
+            public_ip_info.display(options);
+            println!();
+        }
+
+        if let Some(dns_info) = &self.dns {
+            dns_info.display(options);
+            println!();
+        }
+
+        if let Some(routes_info) = &self.routes {
+            routes_info.display(options);
+            println!();
+        }
+
+        if let Some(ports_info) = &self.ports {
+            ports_info.display(options);
+            println!();
+        }
+
+        if let Some(mounts_info) = &self.mounts {
+            mounts_info.display(options);
+            println!();
+        }
+
+        if let Some(smart_info) = &self.smart {
+            smart_info.display(options);
+            println!();
+        }
+
+        if let Some(disk_io_info) = &self.disk_io {
+            disk_io_info.display(options);
+            println!();
+        }
+
+        if let Some(net_usage_info) = &self.net_usage {
+            net_usage_info.display(options);
+            println!();
+        }
+
+        if let Some(cgroup_info) = &self.cgroup {
+            cgroup_info.display(options);
+            println!();
+        }
+
+        if let Some(numa_info) = &self.numa {
+            numa_info.display(options);
+            println!();
+        }
+
+        if let Some(cpu_flags_info) = &self.cpu_flags {
+            cpu_flags_info.display(options);
+            println!();
+        }
+
+        if let Some(cpu_vulnerabilities_info) = &self.cpu_vulnerabilities {
+            cpu_vulnerabilities_info.display(options);
+            println!();
+        }
+
+        if let Some(board_info) = &self.board {
+            board_info.display(options);
+            println!();
+        }
+
+        if let Some(firmware_info) = &self.firmware {
+            firmware_info.display(options);
+            println!();
+        }
+
+        if let Some(power_info) = &self.power {
+            power_info.display(options);
+            println!();
+        }
+
+        if let Some(memory_modules_info) = &self.memory_modules {
+            memory_modules_info.display(options);
+            println!();
+        }
+
+        if let Some(hugepages_info) = &self.hugepages {
+            hugepages_info.display(options);
+            println!();
+        }
+
+        if let Some(graphics_api_info) = &self.graphics_api {
+            graphics_api_info.display(options);
+            println!();
+        }
+
+        if let Some(compute_info) = &self.compute {
+            compute_info.display(options);
+            println!();
+        }
+
+        if let Some(cloud_info) = &self.cloud {
+            cloud_info.display(options);
+            println!();
+        }
+
+        if let Some(k8s_info) = &self.k8s {
+            k8s_info.display(options);
+            println!();
+        }
+
+        if let Some(container_engine_info) = &self.container_engine {
+            container_engine_info.display(options);
+            println!();
+        }
+
+        if let Some(macos_info) = &self.macos {
+            macos_info.display(options);
+            println!();
+        }
+
+        if let Some(windows_info) = &self.windows {
+            windows_info.display(options);
+            println!();
+        }
+
+        if let Some(android_info) = &self.android {
+            android_info.display(options);
+            println!();
+        }
+
+        if let Some(rust_info) = &self.rust {
+            rust_info.display(options);
+            println!();
+        }
+
+        if let Some(project_info) = &self.project {
+            project_info.display(options);
+            println!();
+        }
+
+        if !self.unsupported.is_empty() {
+            options.line(format_args!(
+                "{}Unsupported Sections{}",
+                "=".repeat(10),
+                "=".repeat(10)
+            ));
+            options.line(format_args!(
+                "  {} : Unsupported on this platform",
+                self.unsupported.join(", ")
+            ));
+            println!();
+        }
+    }
+}
+
+/// Selects the sections of a [`Report`] and collects them. Starts out with
+/// no sections selected.
+#[derive(Clone, Debug)]
+pub struct ReportBuilder {
+    sections: Sections,
+    process_options: ProcessOptions,
+    service_units: Vec<String>,
+    public_ip_endpoint: String,
+    cloud_metadata: bool,
+    sampling_interval: Duration,
+    cpu_usage_interval: Option<Duration>,
+    redact: Vec<String>,
+}
+
+impl ReportBuilder {
+    pub fn new() -> Self {
+        Self {
+            sections: Sections::none(),
+            process_options: ProcessOptions::default(),
+            service_units: Vec::new(),
+            public_ip_endpoint: public_ip::DEFAULT_ENDPOINT.into(),
+            cloud_metadata: false,
+            sampling_interval: Duration::from_millis(500),
+            cpu_usage_interval: None,
+            redact: Vec::new(),
+        }
+    }
+
+    /// Replaces the current selection.
+    pub fn sections(mut self, sections: Sections) -> Self {
+        self.sections = sections;
+        self
+    }
+
+    pub fn system(mut self) -> Self {
+        self.sections.system = true;
+        self
+    }
+
+    /// Selects the system section and samples CPU usage over `interval`.
+    pub fn cpu_usage(mut self, interval: Duration) -> Self {
+        self.sections.system = true;
+        self.cpu_usage_interval = Some(interval);
+        self
+    }
+
+    pub fn disk(mut self) -> Self {
+        self.sections.disk = true;
+        self
+    }
+
+    pub fn network(mut self) -> Self {
+        self.sections.network = true;
+        self
+    }
+
+    pub fn load(mut self) -> Self {
+        self.sections.load = true;
+        self
+    }
+
+    pub fn uptime(mut self) -> Self {
+        self.sections.uptime = true;
+        self
+    }
+
+    pub fn host(mut self) -> Self {
+        self.sections.host = true;
+        self
+    }
+
+    pub fn processes(mut self) -> Self {
+        self.sections.processes = true;
+        self
+    }
+
+    /// Selects the process listing, sorted and trimmed as given.
+    pub fn processes_with(mut self, options: ProcessOptions) -> Self {
+        self.sections.processes = true;
+        self.process_options = options;
+        self
+    }
+
+    pub fn sensors(mut self) -> Self {
+        self.sections.sensors = true;
+        self
+    }
+
+    pub fn gpu(mut self) -> Self {
+        self.sections.gpu = true;
+        self
+    }
+
+    pub fn battery(mut self) -> Self {
+        self.sections.battery = true;
+        self
+    }
+
+    pub fn virt(mut self) -> Self {
+        self.sections.virt = true;
+        self
+    }
+
+    pub fn desktop(mut self) -> Self {
+        self.sections.desktop = true;
+        self
+    }
+
+    pub fn shell(mut self) -> Self {
+        self.sections.shell = true;
+        self
+    }
+
+    pub fn locale(mut self) -> Self {
+        self.sections.locale = true;
+        self
+    }
+
+    pub fn toolchain(mut self) -> Self {
+        self.sections.toolchain = true;
+        self
+    }
+
+    pub fn packages(mut self) -> Self {
+        self.sections.packages = true;
+        self
+    }
+
+    pub fn kernel_modules(mut self) -> Self {
+        self.sections.kernel_modules = true;
+        self
+    }
+
+    pub fn services(mut self) -> Self {
+        self.sections.services = true;
+        self
+    }
+
+    /// Selects the systemd units, also looking up each of `units`.
+    pub fn services_with<S: Into<String>>(mut self, units: impl IntoIterator<Item = S>) -> Self {
+        self.sections.services = true;
+        self.service_units(units)
+    }
+
+    pub fn usb(mut self) -> Self {
+        self.sections.usb = true;
+        self
+    }
+
+    pub fn pci(mut self) -> Self {
+        self.sections.pci = true;
+        self
+    }
+
+    pub fn display(mut self) -> Self {
+        self.sections.display = true;
+        self
+    }
+
+    pub fn audio(mut self) -> Self {
+        self.sections.audio = true;
+        self
+    }
+
+    pub fn bluetooth(mut self) -> Self {
+        self.sections.bluetooth = true;
+        self
+    }
+
+    pub fn wifi(mut self) -> Self {
+        self.sections.wifi = true;
+        self
+    }
+
+    pub fn public_ip(mut self) -> Self {
+        self.sections.public_ip = true;
+        self
+    }
+
+    /// Selects the public IP section and asks `endpoint` for the addresses.
+    pub fn public_ip_with(mut self, endpoint: impl Into<String>) -> Self {
+        self.sections.public_ip = true;
+        self.public_ip_endpoint = endpoint.into();
+        self
+    }
+
+    pub fn dns(mut self) -> Self {
+        self.sections.dns = true;
+        self
+    }
+
+    pub fn routes(mut self) -> Self {
+        self.sections.routes = true;
+        self
+    }
+
+    pub fn ports(mut self) -> Self {
+        self.sections.ports = true;
+        self
+    }
+
+    pub fn mounts(mut self) -> Self {
+        self.sections.mounts = true;
+        self
+    }
+
+    pub fn smart(mut self) -> Self {
+        self.sections.smart = true;
+        self
+    }
+
+    pub fn disk_io(mut self) -> Self {
+        self.sections.disk_io = true;
+        self
+    }
+
+    /// Selects the disk I/O section and samples it over `interval`.
+    pub fn disk_io_with(mut self, interval: Duration) -> Self {
+        self.sections.disk_io = true;
+        self.sampling_interval = interval;
+        self
+    }
+
+    pub fn net_usage(mut self) -> Self {
+        self.sections.net_usage = true;
+        self
+    }
+
+    /// Selects the network usage section and samples it over `interval`.
+    pub fn net_usage_with(mut self, interval: Duration) -> Self {
+        self.sections.net_usage = true;
+        self.sampling_interval = interval;
+        self
+    }
+
+    pub fn cgroup(mut self) -> Self {
+        self.sections.cgroup = true;
+        self
+    }
+
+    pub fn numa(mut self) -> Self {
+        self.sections.numa = true;
+        self
+    }
+
+    pub fn cpu_flags(mut self) -> Self {
+        self.sections.cpu_flags = true;
+        self
+    }
+
+    pub fn cpu_vulnerabilities(mut self) -> Self {
+        self.sections.cpu_vulnerabilities = true;
+        self
+    }
+
+    pub fn board(mut self) -> Self {
+        self.sections.board = true;
+        self
+    }
+
+    pub fn firmware(mut self) -> Self {
+        self.sections.firmware = true;
+        self
+    }
+
+    pub fn power(mut self) -> Self {
+        self.sections.power = true;
+        self
+    }
+
+    pub fn memory_modules(mut self) -> Self {
+        self.sections.memory_modules = true;
+        self
+    }
+
+    pub fn hugepages(mut self) -> Self {
+        self.sections.hugepages = true;
+        self
+    }
+
+    pub fn graphics_api(mut self) -> Self {
+        self.sections.graphics_api = true;
+        self
+    }
+
+    pub fn compute(mut self) -> Self {
+        self.sections.compute = true;
+        self
+    }
+
+    pub fn cloud(mut self) -> Self {
+        self.sections.cloud = true;
+        self
+    }
+
+    pub fn k8s(mut self) -> Self {
+        self.sections.k8s = true;
+        self
+    }
+
+    pub fn container_engine(mut self) -> Self {
+        self.sections.container_engine = true;
+        self
+    }
+
+    pub fn macos(mut self) -> Self {
+        self.sections.macos = true;
+        self
+    }
+
+    pub fn windows(mut self) -> Self {
+        self.sections.windows = true;
+        self
+    }
+
+    pub fn android(mut self) -> Self {
+        self.sections.android = true;
+        self
+    }
+
+    pub fn rust(mut self) -> Self {
+        self.sections.rust = true;
+        self
+    }
+
+    pub fn project(mut self) -> Self {
+        self.sections.project = true;
+        self
+    }
+
+    /// Sets how the process listing is sorted and trimmed without selecting it.
+    pub fn process_options(mut self, options: ProcessOptions) -> Self {
+        self.process_options = options;
+        self
+    }
+
+    /// Sets the systemd units to look up without selecting them.
+    pub fn service_units<S: Into<String>>(mut self, units: impl IntoIterator<Item = S>) -> Self {
+        self.service_units = units.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the service asked for the public addresses without selecting
+    /// it. The service must answer with the bare address.
+    pub fn public_ip_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.public_ip_endpoint = endpoint.into();
+        self
+    }
+
+    /// Sets whether the cloud section asks the provider's metadata service
+    /// for the instance type, region and zone, without selecting it.
+    pub fn cloud_metadata(mut self, query: bool) -> Self {
+        self.cloud_metadata = query;
+        self
+    }
+
+    /// Sets how long disk I/O and network usage are sampled for without
+    /// selecting them. Defaults to 500ms.
+    pub fn sampling_interval(mut self, interval: Duration) -> Self {
+        self.sampling_interval = interval;
+        self
+    }
+
+    /// Sets the CPU usage sampling interval without selecting the system
+    /// section.
+    pub fn cpu_usage_interval(mut self, interval: Option<Duration>) -> Self {
+        self.cpu_usage_interval = interval;
+        self
+    }
+
+    /// Blanks out the text at each dotted path, such as `host.hostname`, in
+    /// every report collected.
+    pub fn redact<S: Into<String>>(mut self, fields: impl IntoIterator<Item = S>) -> Self {
+        self.redact = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Collects the selected sections.
+    pub fn build(self) -> Report {
+        let sections = self.sections;
+
+        let mut report = Report {
+            host: sections.host.then(HostInfo::collect),
+            virt: sections.virt.then(VirtInfo::collect),
+            desktop: sections.desktop.then(DesktopInfo::collect),
+            shell: sections.shell.then(ShellInfo::collect),
+            toolchain: sections.toolchain.then(ToolchainInfo::collect),
+            packages: sections.packages.then(PackagesInfo::collect),
+            pci: sections.pci.then(PciInfo::collect),
+            display: sections.display.then(DisplayInfo::collect),
+            audio: sections.audio.then(AudioInfo::collect),
+            bluetooth: sections.bluetooth.then(BluetoothInfo::collect),
+            public_ip: sections
+                .public_ip
+                .then(|| PublicIpInfo::collect(&self.public_ip_endpoint)),
+            dns: sections.dns.then(DnsInfo::collect),
+            mounts: sections.mounts.then(MountsInfo::collect),
+            smart: sections.smart.then(SmartInfo::collect),
+            numa: sections.numa.then(NumaInfo::collect),
+            cpu_flags: sections.cpu_flags.then(CpuFlagsInfo::collect),
+            cpu_vulnerabilities: sections
+                .cpu_vulnerabilities
+                .then(CpuVulnerabilitiesInfo::collect),
+            board: sections.board.then(BoardInfo::collect),
+            firmware: sections.firmware.then(FirmwareInfo::collect),
+            power: sections.power.then(PowerInfo::collect),
+            memory_modules: sections.memory_modules.then(MemoryModulesInfo::collect),
+            hugepages: sections.hugepages.then(HugepagesInfo::collect),
+            graphics_api: sections.graphics_api.then(GraphicsApiInfo::collect),
+            compute: sections.compute.then(ComputeInfo::collect),
+            cloud: sections
+                .cloud
+                .then(|| CloudInfo::collect(self.cloud_metadata)),
+            k8s: sections.k8s.then(KubernetesInfo::collect),
+            container_engine: sections.container_engine.then(ContainerEngineInfo::collect),
+            macos: sections.macos.then(MacosInfo::collect),
+            windows: sections.windows.then(WindowsInfo::collect),
+            android: sections.android.then(AndroidInfo::collect),
+            rust: sections.rust.then(RustInfo::collect),
+            project: sections.project.then(ProjectInfo::collect),
+            unsupported: sections.unsupported(),
+            ..Report::default()
+        };
+        self.refresh(&mut report);
+        report
+    }
+
+    /// Collects the selected sections that change while `envin` runs again,
+    /// leaving the host, Rust and project sections of `report` as they are,
+    /// and applies the redaction.
+    pub fn refresh(&self, report: &mut Report) {
+        let sections = self.sections;
+
+        report.system = sections
+            .system
+            .then(|| SystemInfo::collect(self.cpu_usage_interval));
+        report.disk = sections.disk.then(DiskInfo::collect);
+        report.network = sections.network.then(NetworkInfo::collect);
+        report.load = sections.load.then(LoadInfo::collect).flatten();
+        report.uptime = sections.uptime.then(UptimeInfo::collect);
+        report.processes = sections
+            .processes
+            .then(|| ProcessInfo::collect(&self.process_options));
+        report.sensors = sections.sensors.then(SensorInfo::collect);
+        report.gpu = sections.gpu.then(GpuInfo::collect);
+        report.battery = sections.battery.then(BatteryInfo::collect);
+        report.locale = sections.locale.then(LocaleInfo::collect);
+        report.kernel_modules = sections.kernel_modules.then(KernelModuleInfo::collect);
+        report.services = sections
+            .services
+            .then(|| ServicesInfo::collect(&self.service_units));
+        report.usb = sections.usb.then(UsbInfo::collect);
+        report.wifi = sections.wifi.then(WifiInfo::collect);
+        report.routes = sections.routes.then(RoutesInfo::collect);
+        report.ports = sections.ports.then(PortsInfo::collect);
+        report.disk_io = sections
+            .disk_io
+            .then(|| DiskIoInfo::collect(self.sampling_interval));
+        report.net_usage = sections
+            .net_usage
+            .then(|| NetUsageInfo::collect(self.sampling_interval));
+        report.cgroup = sections.cgroup.then(CgroupInfo::collect);
+        report.redact(&self.redact);
+    }
+}
+
+impl Default for ReportBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
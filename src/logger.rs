@@ -0,0 +1,157 @@
+//! Writes a report to the system logger for `envin log`, either as one line
+//! of JSON or field by field.
+
+use {
+    crate::{flatten, format::DisplayOptions},
+    serde_json::Value,
+    std::io,
+};
+
+/// The system logger to write to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Target {
+    /// The local syslog socket, `/dev/log` on most systems
+    #[default]
+    Syslog,
+    /// The systemd journal, through its native protocol
+    Journald,
+}
+
+/// Where syslog daemons listen: Linux, macOS, then the BSDs.
+const SYSLOG_SOCKETS: [&str; 3] = ["/dev/log", "/var/run/syslog", "/var/run/log"];
+
+/// Where journald accepts native protocol datagrams.
+const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+
+/// The `user` facility at the `info` severity.
+const SYSLOG_PRIORITY: u8 = 14;
+
+/// The `info` severity.
+const JOURNAL_PRIORITY: &str = "6";
+
+/// Journal field names are at most 64 characters.
+const MAX_FIELD_NAME: usize = 64;
+
+/// Logs `report`, the JSON form of a report, under `tag`. The message is the
+/// report as one line of JSON, or with `fields` its fields as
+/// `path=value` pairs; journald then also gets each as an `ENVIN_*` field.
+pub fn write(
+    target: Target,
+    tag: &str,
+    report: &Value,
+    fields: bool,
+    options: &DisplayOptions,
+) -> io::Result<()> {
+    let leaves = if fields {
+        flatten::leaves("", report, options)
+    } else {
+        Vec::new()
+    };
+    let message = if fields {
+        logfmt(&leaves)
+    } else {
+        report.to_string()
+    };
+
+    match target {
+        Target::Syslog => send(&SYSLOG_SOCKETS, syslog_line(tag, &message).as_bytes()),
+        Target::Journald => {
+            let mut entry = Vec::new();
+            journal_field(&mut entry, "MESSAGE", &message);
+            journal_field(&mut entry, "PRIORITY", JOURNAL_PRIORITY);
+            journal_field(&mut entry, "SYSLOG_IDENTIFIER", tag);
+            journal_field(&mut entry, "SYSLOG_PID", &std::process::id().to_string());
+            for (path, value) in &leaves {
+                let name = journal_name(path);
+                if name.len() <= MAX_FIELD_NAME {
+                    journal_field(&mut entry, &name, value);
+                }
+            }
+            send(&[JOURNAL_SOCKET], &entry)
+        }
+    }
+}
+
+/// The BSD syslog format that local daemons expect, which they stamp with
+/// the host name themselves.
+fn syslog_line(tag: &str, message: &str) -> String {
+    format!(
+        "<{}>{} {}[{}]: {}",
+        SYSLOG_PRIORITY,
+        chrono::Local::now().format("%b %e %H:%M:%S"),
+        tag,
+        std::process::id(),
+        message
+    )
+}
+
+/// Joins the fields as `path=value`, quoting values with spaces or quotes.
+fn logfmt(leaves: &[(String, String)]) -> String {
+    leaves
+        .iter()
+        .map(|(path, value)| {
+            if value.is_empty() || value.contains([' ', '"', '=']) {
+                format!("{}={:?}", path, value)
+            } else {
+                format!("{}={}", path, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `system.memory_used_bytes` becomes `ENVIN_SYSTEM_MEMORY_USED_BYTES`.
+fn journal_name(path: &str) -> String {
+    let name: String = path
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("ENVIN_{}", name)
+}
+
+/// Appends `NAME=value`, or for values spanning lines the name, its length
+/// as eight little-endian bytes, then the value.
+fn journal_field(entry: &mut Vec<u8>, name: &str, value: &str) {
+    entry.extend(name.as_bytes());
+    if value.contains('\n') {
+        entry.push(b'\n');
+        entry.extend((value.len() as u64).to_le_bytes());
+    } else {
+        entry.push(b'=');
+    }
+    entry.extend(value.as_bytes());
+    entry.push(b'\n');
+}
+
+/// Sends one datagram to the first of `sockets` that exists.
+#[cfg(unix)]
+fn send(sockets: &[&str], datagram: &[u8]) -> io::Result<()> {
+    use std::{os::unix::net::UnixDatagram, path::Path};
+
+    let path = sockets
+        .iter()
+        .find(|path| Path::new(path).exists())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no logger listens on {}", sockets.join(" or ")),
+            )
+        })?;
+    let socket = UnixDatagram::unbound()?;
+    socket.send_to(datagram, path)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send(_: &[&str], _: &[u8]) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "syslog and journald are only available on Unix systems",
+    ))
+}
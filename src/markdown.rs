@@ -0,0 +1,73 @@
+//! Renders a report as Markdown, ready to paste into the "System
+//! Information" part of an issue.
+
+use {
+    crate::{
+        flatten::{self, Section},
+        format::DisplayOptions,
+    },
+    serde_json::Value,
+    std::fmt::Write,
+};
+
+/// Renders each section as a heading followed by a field table, with lists
+/// such as disks or interfaces as tables of their own. Byte counts are
+/// scaled with `--human`. `report` is the JSON form of a report.
+pub fn render(report: &Value, options: &DisplayOptions) -> String {
+    let mut out = String::from("## System Information\n");
+    let Value::Object(sections) = report else {
+        return out;
+    };
+
+    for (name, value) in sections {
+        let _ = write!(out, "\n### {}\n", flatten::title(name));
+        let section = Section::new(value, options);
+
+        if !section.fields.is_empty() {
+            out.push_str("\n| Field | Value |\n| --- | --- |\n");
+            for (field, value) in &section.fields {
+                let _ = writeln!(out, "| {} | {} |", field, cell(value));
+            }
+        }
+        for (field, entries) in section.lists {
+            write_list(&mut out, &field, entries, options);
+        }
+    }
+
+    out
+}
+
+fn write_list(out: &mut String, field: &str, entries: &[Value], options: &DisplayOptions) {
+    let _ = write!(out, "\n**{}**\n", flatten::title(field));
+    if entries.is_empty() {
+        out.push_str("\nNone\n");
+        return;
+    }
+
+    let columns = flatten::columns(entries);
+    let header: Vec<&str> = columns.iter().map(|column| column.as_str()).collect();
+    let _ = write!(
+        out,
+        "\n| {} |\n|{}\n",
+        header.join(" | "),
+        " --- |".repeat(columns.len())
+    );
+    for entry in entries {
+        let row: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                entry
+                    .get(column.as_str())
+                    .map(|value| flatten::text(column, value, options))
+                    .unwrap_or_default()
+            })
+            .map(|value| cell(&value))
+            .collect();
+        let _ = writeln!(out, "| {} |", row.join(" | "));
+    }
+}
+
+/// Escapes what would break a table cell.
+fn cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', "<br>")
+}
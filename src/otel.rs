@@ -0,0 +1,492 @@
+//! Exports a report as OpenTelemetry metrics over OTLP, named after the
+//! `system.*` semantic conventions, for `envin publish --otlp`.
+
+use {
+    crate::{util::curl_header_file, Report},
+    opentelemetry_proto::tonic::{
+        collector::metrics::v1::{
+            metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest,
+        },
+        common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
+        metrics::v1::{
+            metric::Data, number_data_point, AggregationTemporality, Gauge, Metric,
+            NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+        },
+        resource::v1::Resource,
+    },
+    prost::Message,
+    std::{
+        env::consts::{ARCH, OS},
+        io::{self, Write},
+        process::{Command, Stdio},
+        str::FromStr,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    tonic::{
+        metadata::{Ascii, MetadataKey, MetadataValue},
+        transport::{Channel, Endpoint},
+    },
+};
+
+/// How long an export may take before it is given up on.
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How the metrics are sent to the collector.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Protocol {
+    /// OTLP over gRPC, usually on port 4317
+    #[default]
+    Grpc,
+    /// OTLP over HTTP with protobuf bodies, usually on port 4318
+    Http,
+}
+
+/// Sends reports to one OTLP collector.
+pub struct Exporter {
+    endpoint: String,
+    headers: Vec<(String, String)>,
+    runtime: tokio::runtime::Runtime,
+    /// The gRPC channel, which connects on first use and again whenever the
+    /// collector drops it; `None` over HTTP.
+    channel: Option<Channel>,
+}
+
+impl Exporter {
+    /// An exporter to `endpoint`, such as `http://collector:4317`, that
+    /// adds `headers` to every export, e.g. for an API key. Over HTTP the
+    /// metrics are posted to `/v1/metrics` below it.
+    pub fn new(
+        protocol: Protocol,
+        endpoint: &str,
+        headers: Vec<(String, String)>,
+    ) -> Result<Self, String> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| err.to_string())?;
+        let channel = match protocol {
+            Protocol::Grpc => {
+                let endpoint = Endpoint::from_str(endpoint)
+                    .map_err(|err| format!("invalid endpoint `{}`: {}", endpoint, err))?
+                    .timeout(EXPORT_TIMEOUT);
+                let _runtime = runtime.enter();
+                Some(endpoint.connect_lazy())
+            }
+            Protocol::Http => None,
+        };
+        for (name, value) in &headers {
+            MetadataKey::<Ascii>::from_str(&name.to_ascii_lowercase())
+                .map_err(|_| format!("invalid header name `{}`", name))?;
+            MetadataValue::try_from(value.as_str())
+                .map_err(|_| format!("invalid value for header `{}`", name))?;
+        }
+
+        Ok(Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            headers,
+            runtime,
+            channel,
+        })
+    }
+
+    pub fn export(&self, report: &Report) -> io::Result<()> {
+        let request = request(report);
+        match &self.channel {
+            Some(channel) => self.export_grpc(channel.clone(), request),
+            None => self.export_http(&request),
+        }
+    }
+
+    fn export_grpc(
+        &self,
+        channel: Channel,
+        request: ExportMetricsServiceRequest,
+    ) -> io::Result<()> {
+        let mut request = tonic::Request::new(request);
+        for (name, value) in &self.headers {
+            // Both were checked in `new`.
+            if let (Ok(name), Ok(value)) = (
+                MetadataKey::from_str(&name.to_ascii_lowercase()),
+                MetadataValue::try_from(value.as_str()),
+            ) {
+                request.metadata_mut().insert(name, value);
+            }
+        }
+        self.runtime
+            .block_on(MetricsServiceClient::new(channel).export(request))
+            .map(|_| ())
+            .map_err(|status| io::Error::other(status.message().to_string()))
+    }
+
+    /// Posts the protobuf body with `curl`, which also handles `https://`.
+    fn export_http(&self, request: &ExportMetricsServiceRequest) -> io::Result<()> {
+        let url = format!("{}/v1/metrics", self.endpoint);
+        let timeout = EXPORT_TIMEOUT.as_secs().to_string();
+        // Kept until curl is done with it.
+        let header_file = curl_header_file(&self.headers)?;
+        let headers = header_file
+            .as_ref()
+            .map(|file| format!("@{}", file.path().display()));
+        let mut args = vec![
+            "-sS",
+            "--fail",
+            "--max-time",
+            &timeout,
+            "-H",
+            "Content-Type: application/x-protobuf",
+            "--data-binary",
+            "@-",
+            "-o",
+            if cfg!(windows) { "NUL" } else { "/dev/null" },
+        ];
+        if let Some(headers) = &headers {
+            args.extend(["-H", headers.as_str()]);
+        }
+        args.push(&url);
+
+        let mut child = Command::new("curl")
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(&request.encode_to_vec())?;
+        }
+        let output = child.wait_with_output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ))
+        }
+    }
+}
+
+/// Parses a `--otlp-header` such as `x-api-key=secret`.
+pub fn parse_header(input: &str) -> Result<(String, String), String> {
+    let (name, value) = input
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got `{}`", input))?;
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+/// The export request for every metric the report has a value for, under a
+/// resource describing the host.
+pub fn request(report: &Report) -> ExportMetricsServiceRequest {
+    let now = unix_nanos(SystemTime::now());
+    // Cumulative counters started counting when the machine booted.
+    let boot = report
+        .uptime
+        .as_ref()
+        .map_or(0, |uptime| uptime.boot_time_unix * 1_000_000_000);
+    let mut metrics = Metrics {
+        now,
+        boot,
+        metrics: Vec::new(),
+    };
+
+    if let Some(system) = &report.system {
+        metrics.gauge(
+            "system.memory.usage",
+            "By",
+            [(
+                vec![attribute("system.memory.state", "used")],
+                system.memory_used_bytes as f64,
+            )]
+            .into_iter()
+            .chain(system.memory.as_ref().map(|memory| {
+                (
+                    vec![attribute("system.memory.state", "free")],
+                    memory.free_bytes as f64,
+                )
+            })),
+        );
+        metrics.gauge(
+            "system.memory.limit",
+            "By",
+            [(Vec::new(), system.memory_total_bytes as f64)],
+        );
+        metrics.gauge(
+            "system.paging.usage",
+            "By",
+            [
+                (
+                    vec![attribute("system.paging.state", "used")],
+                    system.swap_used_bytes as f64,
+                ),
+                (
+                    vec![attribute("system.paging.state", "free")],
+                    system
+                        .swap_total_bytes
+                        .saturating_sub(system.swap_used_bytes) as f64,
+                ),
+            ],
+        );
+        metrics.gauge(
+            "system.cpu.logical.count",
+            "{cpu}",
+            [(Vec::new(), system.cpu.logical_cores as f64)],
+        );
+        if let Some(usage) = &system.cpu.usage {
+            metrics.gauge(
+                "system.cpu.utilization",
+                "1",
+                usage
+                    .per_core_percent
+                    .iter()
+                    .enumerate()
+                    .map(|(index, percent)| (logical_cpu(index), f64::from(*percent) / 100.0)),
+            );
+        }
+        metrics.gauge(
+            "system.cpu.frequency",
+            "Hz",
+            system
+                .cpu
+                .cores
+                .iter()
+                .enumerate()
+                .map(|(index, core)| (logical_cpu(index), core.frequency_mhz as f64 * 1e6)),
+        );
+    }
+    if let Some(load) = &report.load {
+        metrics.gauge(
+            "system.cpu.load_average.1m",
+            "{thread}",
+            [(Vec::new(), load.one)],
+        );
+        metrics.gauge(
+            "system.cpu.load_average.5m",
+            "{thread}",
+            [(Vec::new(), load.five)],
+        );
+        metrics.gauge(
+            "system.cpu.load_average.15m",
+            "{thread}",
+            [(Vec::new(), load.fifteen)],
+        );
+    }
+    if let Some(uptime) = &report.uptime {
+        metrics.gauge(
+            "system.uptime",
+            "s",
+            [(Vec::new(), uptime.uptime_secs as f64)],
+        );
+    }
+    if let Some(disk) = &report.disk {
+        metrics.gauge(
+            "system.filesystem.usage",
+            "By",
+            disk.disks.iter().flat_map(|entry| {
+                let attributes = |state: &str| {
+                    vec![
+                        attribute("system.device", &entry.name),
+                        attribute("system.filesystem.mountpoint", &entry.mount_point),
+                        attribute("system.filesystem.type", &entry.file_system),
+                        attribute("system.filesystem.state", state),
+                    ]
+                };
+                [
+                    (attributes("used"), entry.used_bytes as f64),
+                    (attributes("free"), entry.available_bytes as f64),
+                ]
+            }),
+        );
+    }
+    if let Some(network) = &report.network {
+        let directions = |name: &str, receive: u64, transmit: u64| {
+            [("receive", receive), ("transmit", transmit)].map(|(direction, value)| {
+                (
+                    vec![
+                        attribute("network.interface.name", name),
+                        attribute("network.io.direction", direction),
+                    ],
+                    value as f64,
+                )
+            })
+        };
+        metrics.counter(
+            "system.network.io",
+            "By",
+            network.interfaces.iter().flat_map(|interface| {
+                directions(
+                    &interface.name,
+                    interface.received_bytes,
+                    interface.transmitted_bytes,
+                )
+            }),
+        );
+        metrics.counter(
+            "system.network.packets",
+            "{packet}",
+            network.interfaces.iter().flat_map(|interface| {
+                directions(
+                    &interface.name,
+                    interface.received_packets,
+                    interface.transmitted_packets,
+                )
+            }),
+        );
+        metrics.counter(
+            "system.network.errors",
+            "{error}",
+            network.interfaces.iter().flat_map(|interface| {
+                directions(
+                    &interface.name,
+                    interface.receive_errors,
+                    interface.transmit_errors,
+                )
+            }),
+        );
+    }
+
+    ExportMetricsServiceRequest {
+        resource_metrics: vec![ResourceMetrics {
+            resource: Some(resource(report)),
+            scope_metrics: vec![ScopeMetrics {
+                scope: Some(InstrumentationScope {
+                    name: "envin".into(),
+                    version: env!("CARGO_PKG_VERSION").into(),
+                    ..Default::default()
+                }),
+                metrics: metrics.metrics,
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }],
+    }
+}
+
+/// The resource attributes, from the system and host sections where they
+/// were collected.
+fn resource(report: &Report) -> Resource {
+    let mut attributes = vec![
+        attribute("service.name", "envin"),
+        attribute("service.version", env!("CARGO_PKG_VERSION")),
+        attribute(
+            "os.type",
+            match OS {
+                "macos" => "darwin",
+                os => os,
+            },
+        ),
+        attribute(
+            "host.arch",
+            match ARCH {
+                "x86_64" => "amd64",
+                "aarch64" => "arm64",
+                "x86" => "x86",
+                "powerpc64" => "ppc64",
+                arch => arch,
+            },
+        ),
+    ];
+    if let Some(system) = &report.system {
+        attributes.extend([
+            attribute("os.description", &system.os),
+            attribute("os.name", &system.os_name),
+            attribute("os.version", &system.os_version),
+        ]);
+    }
+    if let Some(host) = &report.host {
+        attributes.push(attribute("host.name", &host.hostname));
+    }
+
+    Resource {
+        attributes,
+        dropped_attributes_count: 0,
+    }
+}
+
+fn attribute(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.into(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        }),
+    }
+}
+
+fn logical_cpu(index: usize) -> Vec<KeyValue> {
+    vec![KeyValue {
+        key: "cpu.logical_number".into(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::IntValue(index as i64)),
+        }),
+    }]
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_nanos() as u64)
+}
+
+/// Collects the metrics of one export, stamped with the same time.
+struct Metrics {
+    now: u64,
+    boot: u64,
+    metrics: Vec<Metric>,
+}
+
+impl Metrics {
+    fn gauge(
+        &mut self,
+        name: &str,
+        unit: &str,
+        points: impl IntoIterator<Item = (Vec<KeyValue>, f64)>,
+    ) {
+        let data_points = self.points(points, 0);
+        if !data_points.is_empty() {
+            self.push(name, unit, Data::Gauge(Gauge { data_points }));
+        }
+    }
+
+    /// A cumulative, monotonic sum counted since boot.
+    fn counter(
+        &mut self,
+        name: &str,
+        unit: &str,
+        points: impl IntoIterator<Item = (Vec<KeyValue>, f64)>,
+    ) {
+        let data_points = self.points(points, self.boot);
+        if !data_points.is_empty() {
+            self.push(
+                name,
+                unit,
+                Data::Sum(Sum {
+                    data_points,
+                    aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                    is_monotonic: true,
+                }),
+            );
+        }
+    }
+
+    fn points(
+        &self,
+        points: impl IntoIterator<Item = (Vec<KeyValue>, f64)>,
+        start: u64,
+    ) -> Vec<NumberDataPoint> {
+        points
+            .into_iter()
+            .map(|(attributes, value)| NumberDataPoint {
+                attributes,
+                start_time_unix_nano: start,
+                time_unix_nano: self.now,
+                value: Some(number_data_point::Value::AsDouble(value)),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn push(&mut self, name: &str, unit: &str, data: Data) {
+        self.metrics.push(Metric {
+            name: name.into(),
+            unit: unit.into(),
+            data: Some(data),
+            ..Default::default()
+        });
+    }
+}
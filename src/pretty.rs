@@ -0,0 +1,212 @@
+//! A neofetch-style summary: the distribution logo on the left and the most
+//! interesting fields of the report next to it.
+
+use crate::{
+    format::{format_bytes, DisplayOptions},
+    util::format_duration,
+    Report, Sections,
+};
+
+/// The sections the pretty layout draws from.
+pub fn sections() -> Sections {
+    Sections {
+        system: true,
+        disk: true,
+        uptime: true,
+        host: true,
+        desktop: true,
+        packages: true,
+        gpu: true,
+        battery: true,
+        rust: true,
+        ..Sections::none()
+    }
+}
+
+/// Prints the report next to the logo of the detected distribution.
+pub fn display(report: &Report, options: &DisplayOptions) {
+    let distribution = report
+        .system
+        .as_ref()
+        .map(|system| system.distribution_id.as_str())
+        .unwrap_or("");
+    let logo = logo(distribution);
+    let lines = lines(report, options);
+
+    let width = logo
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    for index in 0..logo.len().max(lines.len()) {
+        let art = logo.get(index).copied().unwrap_or("");
+        let text = lines.get(index).map(String::as_str).unwrap_or("");
+        let art = format!("{:<width$}", art, width = width);
+        println!("{}   {}", options.key(&art), text);
+    }
+}
+
+fn lines(report: &Report, options: &DisplayOptions) -> Vec<String> {
+    let mut fields: Vec<(&str, String)> = Vec::new();
+
+    if let Some(system) = &report.system {
+        fields.push(("OS", system.os.clone()));
+        fields.push(("Kernel", system.kernel.clone()));
+    }
+    if let Some(uptime) = &report.uptime {
+        fields.push(("Uptime", format_duration(uptime.uptime_secs)));
+    }
+    if let Some(packages) = &report.packages {
+        fields.push(("Packages", packages.summary()));
+    }
+    if let Some(system) = &report.system {
+        fields.push(("Shell", system.shell.clone()));
+    }
+    if let Some(desktop) = &report.desktop {
+        if let Some(environment) = &desktop.environment {
+            fields.push(("DE", environment.clone()));
+        }
+        if let Some(window_manager) = &desktop.window_manager {
+            fields.push(("WM", window_manager.clone()));
+        }
+    }
+    if let Some(system) = &report.system {
+        fields.push((
+            "CPU",
+            format!("{} ({} cores)", system.cpu.brand, system.cpu.logical_cores),
+        ));
+    }
+    if let Some(gpu) = &report.gpu {
+        for entry in &gpu.gpus {
+            fields.push(("GPU", format!("{} {}", entry.vendor, entry.model)));
+        }
+    }
+    if let Some(system) = &report.system {
+        fields.push((
+            "Memory",
+            human_usage(system.memory_used_bytes, system.memory_total_bytes, options),
+        ));
+    }
+    if let Some(disk) = &report.disk {
+        if let Some(root) = disk
+            .disks
+            .iter()
+            .find(|disk| disk.mount_point == "/" || disk.mount_point.eq_ignore_ascii_case("C:\\"))
+        {
+            fields.push((
+                "Disk",
+                format!(
+                    "{} ({})",
+                    human_usage(root.used_bytes, root.total_bytes, options),
+                    root.mount_point
+                ),
+            ));
+        }
+    }
+    if let Some(battery) = &report.battery {
+        for entry in &battery.batteries {
+            fields.push((
+                "Battery",
+                format!("{:.0}% ({})", entry.charge_percent, entry.state),
+            ));
+        }
+    }
+    if let Some(rust) = &report.rust {
+        fields.push((
+            "Rust",
+            format!("{} ({})", rust.compiler.version, rust.compiler.channel),
+        ));
+        fields.push(("Cargo", rust.cargo.version.clone()));
+    }
+
+    let mut lines = Vec::new();
+    if let Some(host) = &report.host {
+        let title = format!("{}@{}", host.username, host.hostname);
+        let underline = "-".repeat(title.chars().count());
+        lines.push(options.key(&title));
+        lines.push(underline);
+    }
+
+    let key_width = fields.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    lines.extend(fields.into_iter().map(|(key, value)| {
+        let key = format!("{:<width$}", key, width = key_width);
+        format!("{} : {}", options.key(&key), value)
+    }));
+
+    lines
+}
+
+/// The pretty layout always scales byte counts, whatever `--human` says.
+fn human_usage(used: u64, total: u64, options: &DisplayOptions) -> String {
+    let usage = format!(
+        "{} / {}",
+        format_bytes(used, options.units),
+        format_bytes(total, options.units)
+    );
+    options.warn_if(used as f64 > total as f64 * 0.9, usage)
+}
+
+fn logo(distribution: &str) -> &'static [&'static str] {
+    match distribution {
+        "debian" | "raspbian" => &[
+            r"    _____     ",
+            r"   /  __ \    ",
+            r"  |  /    |   ",
+            r"  |  \___-    ",
+            r"  -_          ",
+            r"    --_       ",
+        ],
+        "ubuntu" | "pop" | "linuxmint" => &[
+            r"          _  ",
+            r"      ---(_) ",
+            r"  _/  ---  \ ",
+            r" (_) |   |   ",
+            r"   \  --- _/ ",
+            r"      ---(_) ",
+        ],
+        "arch" | "manjaro" | "endeavouros" => &[
+            r"       /\       ",
+            r"      /  \      ",
+            r"     /\   \     ",
+            r"    /      \    ",
+            r"   /   ,,   \   ",
+            r"  /   |  |  -\  ",
+            r" /_-''    ''-_\ ",
+        ],
+        "fedora" | "rhel" | "centos" => &[
+            r"       _____    ",
+            r"      /   __)\  ",
+            r"      |  /  \ \ ",
+            r"   ___|  |__/ / ",
+            r"  / (_    _)_/   ",
+            r" / /  |  |       ",
+            r" \ \__/  |       ",
+            r"  \(_____/       ",
+        ],
+        "macos" => &[
+            r"         .:'     ",
+            r"     __ :'__     ",
+            r"  .'`  `-'  ``.  ",
+            r" :          .-'  ",
+            r" :         :     ",
+            r"  :         `-;  ",
+            r"   `.__.-.__.'   ",
+        ],
+        "windows" => &[
+            r" ______ ______ ",
+            r"|      |      |",
+            r"|______|______|",
+            r"|      |      |",
+            r"|______|______|",
+        ],
+        _ => &[
+            r"     .--.     ",
+            r"    |o_o |    ",
+            r"    |:_/ |    ",
+            r"   //   \ \   ",
+            r"  (|     | )  ",
+            r" /'\_   _/`\  ",
+            r" \___)=(___/  ",
+        ],
+    }
+}
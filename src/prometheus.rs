@@ -0,0 +1,312 @@
+//! Renders a report in the Prometheus text exposition format.
+
+use {
+    crate::{
+        component::{disk::DiskEntry, network::InterfaceInfo},
+        Report, Sections,
+    },
+    std::fmt::Write,
+};
+
+/// The `Content-Type` of the exposition format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// The sections exported when none are selected explicitly.
+pub fn sections() -> Sections {
+    Sections {
+        system: true,
+        disk: true,
+        network: true,
+        load: true,
+        uptime: true,
+        ..Sections::none()
+    }
+}
+
+type Labels = Vec<(&'static str, String)>;
+
+/// Renders every collected section as metrics whose names start with
+/// `prefix` followed by an underscore.
+pub fn render(report: &Report, prefix: &str) -> String {
+    let mut metrics = Exposition {
+        prefix,
+        out: String::new(),
+    };
+
+    if let Some(system) = &report.system {
+        metrics.gauge(
+            "system_info",
+            "Operating system and kernel, always 1.",
+            [(
+                vec![
+                    ("os", system.os.clone()),
+                    ("distribution", system.distribution_id.clone()),
+                    ("kernel", system.kernel.clone()),
+                ],
+                1.0,
+            )],
+        );
+        metrics.value(
+            "memory_used_bytes",
+            "Memory in use.",
+            system.memory_used_bytes,
+        );
+        metrics.value(
+            "memory_total_bytes",
+            "Total memory.",
+            system.memory_total_bytes,
+        );
+        metrics.value("swap_used_bytes", "Swap in use.", system.swap_used_bytes);
+        metrics.value("swap_total_bytes", "Total swap.", system.swap_total_bytes);
+        metrics.value(
+            "cpu_logical_cores",
+            "Logical CPU cores.",
+            system.cpu.logical_cores as u64,
+        );
+        metrics.gauge(
+            "cpu_core_frequency_hertz",
+            "Current clock speed of each core.",
+            system
+                .cpu
+                .cores
+                .iter()
+                .enumerate()
+                .map(|(index, core)| (core_label(index), core.frequency_mhz as f64 * 1e6)),
+        );
+        if let Some(usage) = &system.cpu.usage {
+            metrics.gauge(
+                "cpu_usage_percent",
+                "CPU usage across all cores.",
+                [(Labels::new(), f64::from(usage.global_percent))],
+            );
+            metrics.gauge(
+                "cpu_core_usage_percent",
+                "CPU usage of each core.",
+                usage
+                    .per_core_percent
+                    .iter()
+                    .enumerate()
+                    .map(|(index, percent)| (core_label(index), f64::from(*percent))),
+            );
+        }
+    }
+
+    if let Some(disk) = &report.disk {
+        let labels = |disk: &DiskEntry| {
+            vec![
+                ("device", disk.name.clone()),
+                ("mountpoint", disk.mount_point.clone()),
+                ("fstype", disk.file_system.clone()),
+            ]
+        };
+        metrics.gauge(
+            "disk_total_bytes",
+            "Size of the file system.",
+            disk.disks
+                .iter()
+                .map(|disk| (labels(disk), disk.total_bytes as f64)),
+        );
+        metrics.gauge(
+            "disk_used_bytes",
+            "Space in use on the file system.",
+            disk.disks
+                .iter()
+                .map(|disk| (labels(disk), disk.used_bytes as f64)),
+        );
+        metrics.gauge(
+            "disk_available_bytes",
+            "Space available on the file system.",
+            disk.disks
+                .iter()
+                .map(|disk| (labels(disk), disk.available_bytes as f64)),
+        );
+    }
+
+    if let Some(network) = &report.network {
+        let per_interface = |counter: fn(&InterfaceInfo) -> u64| {
+            network.interfaces.iter().map(move |interface| {
+                (
+                    vec![("interface", interface.name.clone())],
+                    counter(interface) as f64,
+                )
+            })
+        };
+        metrics.counter(
+            "network_receive_bytes_total",
+            "Bytes received.",
+            per_interface(|interface| interface.received_bytes),
+        );
+        metrics.counter(
+            "network_transmit_bytes_total",
+            "Bytes transmitted.",
+            per_interface(|interface| interface.transmitted_bytes),
+        );
+        metrics.counter(
+            "network_receive_packets_total",
+            "Packets received.",
+            per_interface(|interface| interface.received_packets),
+        );
+        metrics.counter(
+            "network_transmit_packets_total",
+            "Packets transmitted.",
+            per_interface(|interface| interface.transmitted_packets),
+        );
+        metrics.counter(
+            "network_receive_errors_total",
+            "Errors while receiving.",
+            per_interface(|interface| interface.receive_errors),
+        );
+        metrics.counter(
+            "network_transmit_errors_total",
+            "Errors while transmitting.",
+            per_interface(|interface| interface.transmit_errors),
+        );
+    }
+
+    if let Some(load) = &report.load {
+        for (name, help, value) in [
+            ("load1", "1 minute load average.", load.one),
+            ("load5", "5 minute load average.", load.five),
+            ("load15", "15 minute load average.", load.fifteen),
+        ] {
+            metrics.gauge(name, help, [(Labels::new(), value)]);
+        }
+    }
+
+    if let Some(uptime) = &report.uptime {
+        metrics.value(
+            "uptime_seconds",
+            "Seconds since the machine booted.",
+            uptime.uptime_secs,
+        );
+        metrics.value(
+            "boot_time_seconds",
+            "Unix time the machine booted at.",
+            uptime.boot_time_unix,
+        );
+    }
+
+    if let Some(processes) = &report.processes {
+        metrics.value(
+            "processes",
+            "Running processes.",
+            processes.processes.len() as u64,
+        );
+    }
+
+    if let Some(sensors) = &report.sensors {
+        metrics.gauge(
+            "sensor_temperature_celsius",
+            "Current temperature of each sensor.",
+            sensors.sensors.iter().filter_map(|sensor| {
+                let temperature = sensor.temperature?;
+                Some((
+                    vec![("sensor", sensor.label.clone())],
+                    f64::from(temperature),
+                ))
+            }),
+        );
+        metrics.gauge(
+            "fan_speed_rpm",
+            "Current speed of each fan.",
+            sensors
+                .fans
+                .iter()
+                .filter_map(|fan| Some((vec![("fan", fan.label.clone())], f64::from(fan.rpm?)))),
+        );
+    }
+
+    if let Some(battery) = &report.battery {
+        metrics.gauge(
+            "battery_charge_percent",
+            "State of charge of each battery.",
+            battery
+                .batteries
+                .iter()
+                .enumerate()
+                .map(|(index, battery)| {
+                    (
+                        vec![("battery", index.to_string())],
+                        f64::from(battery.charge_percent),
+                    )
+                }),
+        );
+        metrics.gauge(
+            "battery_health_percent",
+            "Full charge capacity of each battery relative to its design capacity.",
+            battery
+                .batteries
+                .iter()
+                .enumerate()
+                .filter_map(|(index, battery)| {
+                    Some((
+                        vec![("battery", index.to_string())],
+                        f64::from(battery.health_percent?),
+                    ))
+                }),
+        );
+    }
+
+    metrics.out
+}
+
+fn core_label(index: usize) -> Labels {
+    vec![("core", index.to_string())]
+}
+
+/// Metric families written so far.
+struct Exposition<'a> {
+    prefix: &'a str,
+    out: String,
+}
+
+impl Exposition<'_> {
+    /// Writes a gauge with a single unlabeled sample.
+    fn value(&mut self, name: &str, help: &str, value: u64) {
+        self.gauge(name, help, [(Labels::new(), value as f64)]);
+    }
+
+    fn gauge(&mut self, name: &str, help: &str, samples: impl IntoIterator<Item = (Labels, f64)>) {
+        self.family(name, "gauge", help, samples);
+    }
+
+    fn counter(
+        &mut self,
+        name: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = (Labels, f64)>,
+    ) {
+        self.family(name, "counter", help, samples);
+    }
+
+    fn family(
+        &mut self,
+        name: &str,
+        kind: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = (Labels, f64)>,
+    ) {
+        let name = format!("{}_{}", self.prefix, name);
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+        for (labels, value) in samples {
+            let _ = write!(self.out, "{}", name);
+            if !labels.is_empty() {
+                let labels: Vec<String> = labels
+                    .iter()
+                    .map(|(key, value)| format!("{}=\"{}\"", key, escape(value)))
+                    .collect();
+                let _ = write!(self.out, "{{{}}}", labels.join(","));
+            }
+            let _ = writeln!(self.out, " {}", value);
+        }
+    }
+}
+
+/// Escapes a label value as the exposition format requires.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,147 @@
+//! Posts reports to a webhook for `envin push`, retrying with exponential
+//! backoff when the receiver is down or overloaded.
+
+use {
+    crate::util::curl_header_file,
+    std::{
+        io::{self, Write},
+        process::{Command, Stdio},
+        thread,
+        time::Duration,
+    },
+};
+
+/// How long one attempt may take, connecting included.
+const POST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The wait before the first retry, doubled for each one after.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Backoff never grows past this.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A webhook and the headers every post carries.
+#[derive(Clone, Debug)]
+pub struct Webhook {
+    url: String,
+    headers: Vec<(String, String)>,
+    retries: u32,
+}
+
+impl Webhook {
+    /// Takes `http://` and `https://` URLs. A failed post is retried up to
+    /// `retries` times.
+    pub fn new(url: &str, headers: Vec<(String, String)>, retries: u32) -> Result<Self, String> {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(format!("`{}` is not an http:// or https:// URL", url));
+        }
+        Ok(Self {
+            url: url.to_string(),
+            headers,
+            retries,
+        })
+    }
+
+    /// Posts `body` as JSON. Connection errors, timeouts, 5xx answers, 408
+    /// and 429 are retried after 1s, 2s, 4s and so on up to a minute; other
+    /// errors are not, since the same post would fail the same way.
+    pub fn post(&self, body: &[u8]) -> io::Result<()> {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            match self.attempt(body) {
+                Ok(()) => return Ok(()),
+                Err(Failure::Final(err)) => return Err(err),
+                Err(Failure::Transient(err)) if attempt >= self.retries => return Err(err),
+                Err(Failure::Transient(err)) => {
+                    eprintln!(
+                        "Failed to push to {}: {}, retrying in {}s",
+                        self.url,
+                        err,
+                        backoff.as_secs()
+                    );
+                }
+            }
+            thread::sleep(backoff);
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            attempt += 1;
+        }
+    }
+
+    /// Posts with `curl`, which also handles `https://`, writing the status
+    /// code to stdout.
+    fn attempt(&self, body: &[u8]) -> Result<(), Failure> {
+        let timeout = POST_TIMEOUT.as_secs().to_string();
+        let user_agent = format!("envin/{}", env!("CARGO_PKG_VERSION"));
+        // Kept until curl is done with it.
+        let header_file = curl_header_file(&self.headers).map_err(Failure::Final)?;
+        let headers = header_file
+            .as_ref()
+            .map(|file| format!("@{}", file.path().display()));
+        let mut args = vec![
+            "-sS",
+            "--max-time",
+            &timeout,
+            "-A",
+            &user_agent,
+            "-H",
+            "Content-Type: application/json",
+            "--data-binary",
+            "@-",
+            "-o",
+            if cfg!(windows) { "NUL" } else { "/dev/null" },
+            "-w",
+            "%{http_code}",
+        ];
+        if let Some(headers) = &headers {
+            args.extend(["-H", headers.as_str()]);
+        }
+        args.push(&self.url);
+
+        let mut child = Command::new("curl")
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(Failure::Final)?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(body).map_err(Failure::Transient)?;
+        }
+        let output = child.wait_with_output().map_err(Failure::Transient)?;
+        if !output.status.success() {
+            return Err(Failure::Transient(io::Error::other(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            )));
+        }
+
+        let status: u16 = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .unwrap_or(0);
+        let error = || io::Error::other(format!("the webhook answered {}", status));
+        match status {
+            200..=299 => Ok(()),
+            408 | 429 | 500.. => Err(Failure::Transient(error())),
+            _ => Err(Failure::Final(error())),
+        }
+    }
+}
+
+enum Failure {
+    /// Worth trying again after a while.
+    Transient(io::Error),
+    Final(io::Error),
+}
+
+/// Parses a `--header` such as `Authorization: Bearer secret`.
+pub fn parse_header(input: &str) -> Result<(String, String), String> {
+    let (name, value) = input
+        .split_once(':')
+        .ok_or_else(|| format!("expected `Name: value`, got `{}`", input))?;
+    let name = name.trim();
+    if name.is_empty() || !name.bytes().all(|byte| byte.is_ascii_graphic()) {
+        return Err(format!("invalid header name `{}`", name));
+    }
+    Ok((name.to_string(), value.trim().to_string()))
+}
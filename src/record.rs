@@ -0,0 +1,147 @@
+//! Appending timestamped reports to a history file for `envin record`,
+//! rotating it once it grows too large.
+
+use {
+    crate::{csv, flatten, format::DisplayOptions},
+    chrono::{DateTime, Local, SecondsFormat},
+    serde_json::{Map, Value},
+    std::{
+        fs::{self, OpenOptions},
+        io::Write,
+        path::{Path, PathBuf},
+    },
+};
+
+/// How each report is written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum RecordFormat {
+    /// One JSON object a line, with a `timestamp` before the sections
+    #[default]
+    Jsonl,
+    /// `timestamp,path,value` rows, one for every field
+    Csv,
+}
+
+impl RecordFormat {
+    /// CSV for `.csv` files, JSON lines for anything else.
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("csv") => Self::Csv,
+            _ => Self::Jsonl,
+        }
+    }
+}
+
+/// Appends reports to one file.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    path: PathBuf,
+    format: RecordFormat,
+    /// Rotate before the file would grow past this many bytes.
+    max_bytes: Option<u64>,
+    /// How many rotated files, `history.jsonl.1` being the newest, to keep.
+    keep: usize,
+}
+
+impl Recorder {
+    pub fn new(path: PathBuf, format: RecordFormat) -> Self {
+        Self {
+            path,
+            format,
+            max_bytes: None,
+            keep: 5,
+        }
+    }
+
+    /// Rotates the file once it would grow past `max_bytes`, keeping `keep`
+    /// earlier ones; with `keep` at 0 the file just starts over.
+    pub fn rotate(mut self, max_bytes: u64, keep: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self.keep = keep;
+        self
+    }
+
+    /// Appends `report`, the JSON form of a report, as taken at `time`.
+    pub fn append(
+        &self,
+        report: &Value,
+        time: DateTime<Local>,
+        options: &DisplayOptions,
+    ) -> Result<(), String> {
+        let timestamp = time.to_rfc3339_opts(SecondsFormat::Secs, false);
+        let mut entry = match self.format {
+            RecordFormat::Jsonl => timestamped(report, time).to_string() + "\n",
+            RecordFormat::Csv => flatten::leaves("", report, options)
+                .into_iter()
+                .map(|(path, value)| {
+                    format!(
+                        "{},{},{}\n",
+                        timestamp,
+                        csv::field(&path, ','),
+                        csv::field(&value, ',')
+                    )
+                })
+                .collect(),
+        };
+
+        let size = fs::metadata(&self.path).map_or(0, |metadata| metadata.len());
+        let size = match self.max_bytes {
+            Some(max_bytes) if size > 0 && size + entry.len() as u64 > max_bytes => {
+                self.rotate_now()?;
+                0
+            }
+            _ => size,
+        };
+        if size == 0 && self.format == RecordFormat::Csv {
+            entry.insert_str(0, "timestamp,path,value\n");
+        }
+
+        let error = |err: std::io::Error| format!("{}: {}", self.path.display(), err);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(error)?;
+        file.write_all(entry.as_bytes()).map_err(error)
+    }
+
+    /// Shifts `path.1` to `path.2` and so on, dropping the oldest, then
+    /// moves the file itself to `path.1`.
+    fn rotate_now(&self) -> Result<(), String> {
+        let rotated = |index: usize| {
+            let mut name = self.path.clone().into_os_string();
+            name.push(format!(".{}", index));
+            PathBuf::from(name)
+        };
+        let error = |path: &Path, err: std::io::Error| format!("{}: {}", path.display(), err);
+
+        if self.keep == 0 {
+            return fs::remove_file(&self.path).map_err(|err| error(&self.path, err));
+        }
+        let oldest = rotated(self.keep);
+        if oldest.exists() {
+            fs::remove_file(&oldest).map_err(|err| error(&oldest, err))?;
+        }
+        for index in (1..self.keep).rev() {
+            let from = rotated(index);
+            if from.exists() {
+                fs::rename(&from, rotated(index + 1)).map_err(|err| error(&from, err))?;
+            }
+        }
+        fs::rename(&self.path, rotated(1)).map_err(|err| error(&self.path, err))
+    }
+}
+
+/// `report`, the JSON form of a report, with the time it was taken at as a
+/// `timestamp` before the sections.
+pub fn timestamped(report: &Value, time: DateTime<Local>) -> Value {
+    let mut object = Map::new();
+    object.insert(
+        "timestamp".into(),
+        time.to_rfc3339_opts(SecondsFormat::Secs, false).into(),
+    );
+    if let Value::Object(sections) = report {
+        object.extend(sections.clone());
+    }
+    Value::Object(object)
+}
@@ -0,0 +1,38 @@
+//! Blanking out fields of a report before it is shown.
+
+use serde_json::Value;
+
+/// What redacted text fields are replaced with.
+pub const REDACTED: &str = "[redacted]";
+
+/// Replaces the text at the dotted `path` of `value` with [`REDACTED`].
+/// Lists along the way are walked element by element, so
+/// `network.interfaces.mac_address` covers every interface. Fields that are
+/// not text are left alone.
+pub fn redact(value: &mut Value, path: &str) {
+    let segments: Vec<&str> = path
+        .split('.')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    redact_segments(value, &segments);
+}
+
+fn redact_segments(value: &mut Value, segments: &[&str]) {
+    match value {
+        Value::Array(values) => {
+            for value in values {
+                redact_segments(value, segments);
+            }
+        }
+        Value::Object(fields) => {
+            let Some((first, rest)) = segments.split_first() else {
+                return;
+            };
+            if let Some(field) = fields.get_mut(*first) {
+                redact_segments(field, rest);
+            }
+        }
+        Value::String(text) if segments.is_empty() => *text = REDACTED.into(),
+        _ => {}
+    }
+}
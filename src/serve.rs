@@ -0,0 +1,356 @@
+//! A small HTTP server answering requests from the latest collected report.
+
+use {
+    crate::{Report, ReportBuilder},
+    std::{
+        io::{self, BufRead, BufReader, Read, Write},
+        net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, RwLock, RwLockReadGuard,
+        },
+        thread,
+        time::Duration,
+    },
+};
+
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The longest request line or header accepted, in bytes.
+const MAX_LINE: usize = 8 * 1024;
+
+/// The most headers a request may carry.
+const MAX_HEADERS: usize = 100;
+
+/// Connections served at once across all listeners; more are turned away
+/// with 503 rather than each holding a thread.
+const MAX_CONNECTIONS: usize = 64;
+
+/// The request line and headers of an HTTP request.
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub method: String,
+    /// The path without its query string.
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Looks up a header by its case-insensitive name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the request carries `Authorization: Bearer <token>`. The
+    /// token is compared in constant time.
+    pub fn has_bearer_token(&self, token: &str) -> bool {
+        is_bearer_token(self.header("Authorization"), token)
+    }
+}
+
+/// Whether an `Authorization` header value is `Bearer <token>`, compared in
+/// constant time.
+pub(crate) fn is_bearer_token(authorization: Option<&str>, token: &str) -> bool {
+    let Some(given) = authorization.and_then(|value| value.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// Answers a request from the latest report.
+pub type Handler = Box<dyn Fn(&Request, &Report) -> Response + Send + Sync>;
+
+/// A complete HTTP response.
+#[derive(Clone, Debug)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    /// Headers besides `Content-Type` and `Content-Length`.
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+impl Response {
+    pub fn ok(content_type: &'static str, body: String) -> Self {
+        Self {
+            status: 200,
+            content_type,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    /// Asks the client for a bearer token.
+    pub fn unauthorized() -> Self {
+        let mut response = Self::text(401, "Unauthorized");
+        response
+            .headers
+            .push(("WWW-Authenticate", "Bearer realm=\"envin\"".into()));
+        response
+    }
+
+    pub fn not_found() -> Self {
+        Self::text(404, "Not Found")
+    }
+
+    pub fn method_not_allowed() -> Self {
+        Self::text(405, "Method Not Allowed")
+    }
+
+    /// A plain text response whose body is `reason`.
+    pub fn text(status: u16, reason: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            headers: Vec::new(),
+            body: format!("{}\n", reason),
+        }
+    }
+}
+
+/// Parses a listen address such as `127.0.0.1:9101`, `localhost:9101` or
+/// `:9101`, which listens on every interface.
+pub fn parse_listen_address(input: &str) -> Result<SocketAddr, String> {
+    let input = input.trim();
+    let address = match input.strip_prefix(':') {
+        Some(port) => format!("0.0.0.0:{}", port),
+        None => input.to_string(),
+    };
+    address
+        .to_socket_addrs()
+        .map_err(|err| format!("invalid address `{}`: {}", input, err))?
+        .next()
+        .ok_or_else(|| format!("`{}` did not resolve to an address", input))
+}
+
+/// Serves requests on `address` until the process is stopped.
+///
+/// A background thread collects a fresh report every `interval` and
+/// `handler` answers each request from the latest one, so slow sections
+/// never hold up a scrape.
+pub fn serve<H>(
+    address: SocketAddr,
+    builder: ReportBuilder,
+    interval: Duration,
+    handler: H,
+) -> io::Result<()>
+where
+    H: Fn(&Request, &Report) -> Response + Send + Sync + 'static,
+{
+    serve_all(vec![(address, Box::new(handler))], builder, interval)
+}
+
+/// Like [`serve`], but listens on several addresses at once, each answered
+/// by its own handler from the same reports.
+pub fn serve_all(
+    listeners: Vec<(SocketAddr, Handler)>,
+    builder: ReportBuilder,
+    interval: Duration,
+) -> io::Result<()> {
+    // Bind everything first so a taken port fails before any serving.
+    let listeners = bind(listeners)?;
+    listeners.accept(&Latest::start(builder, interval));
+    Ok(())
+}
+
+/// The latest collected report, which a background thread replaces with a
+/// fresh one every interval. Clones share the same report.
+#[derive(Clone, Debug)]
+pub struct Latest {
+    report: Arc<RwLock<Report>>,
+    interval: Duration,
+}
+
+impl Latest {
+    /// Collects the first report, then starts refreshing it every
+    /// `interval`.
+    pub fn start(builder: ReportBuilder, interval: Duration) -> Self {
+        let report = Arc::new(RwLock::new(builder.clone().build()));
+
+        let latest = Arc::clone(&report);
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            let mut next = latest
+                .read()
+                .map(|report| report.clone())
+                .unwrap_or_default();
+            builder.refresh(&mut next);
+            if let Ok(mut report) = latest.write() {
+                *report = next;
+            }
+        });
+
+        Self { report, interval }
+    }
+
+    /// The latest report, or `None` if a refresh panicked while replacing
+    /// it.
+    pub fn read(&self) -> Option<RwLockReadGuard<'_, Report>> {
+        self.report.read().ok()
+    }
+
+    /// How often the report is refreshed.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+/// Listeners bound by [`bind`], not yet accepting connections.
+pub struct Bound(Vec<(TcpListener, Arc<Handler>)>);
+
+/// Binds every address, failing on the first one that is taken.
+pub fn bind(listeners: Vec<(SocketAddr, Handler)>) -> io::Result<Bound> {
+    listeners
+        .into_iter()
+        .map(|(address, handler)| Ok((TcpListener::bind(address)?, Arc::new(handler))))
+        .collect::<io::Result<Vec<_>>>()
+        .map(Bound)
+}
+
+impl Bound {
+    /// Answers requests from `latest` until the process is stopped, or
+    /// returns right away when nothing was bound.
+    pub fn accept(self, latest: &Latest) {
+        let active = Arc::new(AtomicUsize::new(0));
+        let accepting: Vec<_> = self
+            .0
+            .into_iter()
+            .map(|(listener, handler)| {
+                let latest = latest.clone();
+                let active = Arc::clone(&active);
+                thread::spawn(move || accept(listener, latest, handler, active))
+            })
+            .collect();
+        for thread in accepting {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn accept(listener: TcpListener, latest: Latest, handler: Arc<Handler>, active: Arc<AtomicUsize>) {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            active.fetch_sub(1, Ordering::SeqCst);
+            let _ = stream.set_write_timeout(Some(READ_TIMEOUT));
+            let _ = write_response(&stream, &Response::text(503, "Service Unavailable"));
+            continue;
+        }
+        let latest = latest.clone();
+        let handler = Arc::clone(&handler);
+        let active = Arc::clone(&active);
+        thread::spawn(move || {
+            // A client hanging up early is not worth reporting.
+            let _ = handle(stream, &latest, handler.as_ref());
+            active.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+fn handle<H>(stream: TcpStream, latest: &Latest, handler: &H) -> io::Result<()>
+where
+    H: Fn(&Request, &Report) -> Response,
+{
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let response = match read_request(&stream)? {
+        Ok(request) => match latest.read() {
+            Some(report) => handler(&request, &report),
+            None => Response::text(500, "Internal Server Error"),
+        },
+        Err(status) => Response::text(status, reason(status)),
+    };
+    write_response(&stream, &response)
+}
+
+/// Reads the request line and headers, or the status to refuse them with:
+/// 400 for a malformed or overlong request line, 431 for too many or too
+/// long headers.
+fn read_request(stream: &TcpStream) -> io::Result<Result<Request, u16>> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    if !read_line(&mut reader, &mut line)? {
+        return Ok(Err(400));
+    }
+
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(Err(400));
+    };
+    let path = target.split('?').next().unwrap_or(target);
+    let mut request = Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        headers: Vec::new(),
+    };
+
+    loop {
+        if !read_line(&mut reader, &mut line)? {
+            return Ok(Err(431));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if request.headers.len() == MAX_HEADERS {
+            return Ok(Err(431));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            request
+                .headers
+                .push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    Ok(Ok(request))
+}
+
+/// Reads one line into `line`, returning `false` if it runs past
+/// [`MAX_LINE`]. At the end of the stream `line` is left empty.
+fn read_line(reader: &mut impl BufRead, line: &mut String) -> io::Result<bool> {
+    line.clear();
+    reader.by_ref().take(MAX_LINE as u64 + 1).read_line(line)?;
+    Ok(line.len() <= MAX_LINE)
+}
+
+fn write_response(mut stream: &TcpStream, response: &Response) -> io::Result<()> {
+    let headers: String = response
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\r\n", name, value))
+        .collect();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        response.content_type,
+        response.body.len(),
+        headers,
+        response.body
+    )?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
@@ -0,0 +1,170 @@
+//! Saving reports to disk and comparing two of them.
+//!
+//! A snapshot is the JSON form of a [`Report`], so the output of
+//! `envin --json` can be loaded as one too.
+
+use {
+    crate::{format::DisplayOptions, Report},
+    serde_json::Value,
+    std::{fs, path::Path},
+};
+
+/// Writes `report` to `path` as pretty-printed JSON.
+pub fn save(report: &Report, path: &Path) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(report).map_err(|err| err.to_string())?;
+    fs::write(path, json + "\n").map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+/// Reads a report saved with [`save`].
+pub fn load(path: &Path) -> Result<Report, String> {
+    let json = fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    serde_json::from_str(&json).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+/// A single difference between two reports.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Changed {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+impl Change {
+    pub fn display(&self, options: &DisplayOptions) {
+        let line = match self {
+            Change::Added { path, value } => format!("+ {} = {}", path, value),
+            Change::Removed { path, value } => format!("- {} = {}", path, value),
+            Change::Changed { path, old, new } => format!("~ {} : {} -> {}", path, old, new),
+        };
+        println!(
+            "{}",
+            options.warn_if(!matches!(self, Change::Added { .. }), line)
+        );
+    }
+}
+
+/// Lists what differs between `old` and `new`, field by field.
+///
+/// Lists of disks, interfaces and other entries are matched up by their
+/// name rather than their position, so a new disk shows up as one addition
+/// instead of every later disk changing.
+pub fn diff(old: &Report, new: &Report) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let (Ok(old), Ok(new)) = (serde_json::to_value(old), serde_json::to_value(new)) else {
+        return changes;
+    };
+    diff_values("", &old, &new, &mut changes);
+    changes
+}
+
+/// Prints `changes`, or a note that there are none.
+pub fn display(changes: &[Change], options: &DisplayOptions) {
+    if changes.is_empty() {
+        println!("No differences");
+    }
+    for change in changes {
+        change.display(options);
+    }
+}
+
+fn diff_values(path: &str, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let child = join(path, key);
+                match new.get(key) {
+                    Some(new_value) => diff_values(&child, old_value, new_value, changes),
+                    None => changes.push(Change::Removed {
+                        path: child,
+                        value: old_value.clone(),
+                    }),
+                }
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    changes.push(Change::Added {
+                        path: join(path, key),
+                        value: new_value.clone(),
+                    });
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            let old = keyed(old);
+            let new = keyed(new);
+            for (key, old_value) in &old {
+                let child = format!("{}[{}]", path, key);
+                match new.iter().find(|(new_key, _)| new_key == key) {
+                    Some((_, new_value)) => diff_values(&child, old_value, new_value, changes),
+                    None => changes.push(Change::Removed {
+                        path: child,
+                        value: (*old_value).clone(),
+                    }),
+                }
+            }
+            for (key, new_value) in &new {
+                if !old.iter().any(|(old_key, _)| old_key == key) {
+                    changes.push(Change::Added {
+                        path: format!("{}[{}]", path, key),
+                        value: (*new_value).clone(),
+                    });
+                }
+            }
+        }
+        _ if old != new => changes.push(Change::Changed {
+            path: path.to_string(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// Pairs each entry with the field identifying it, or plain values with
+/// themselves, falling back to its position. Repeated names get a `#n`
+/// suffix.
+fn keyed(values: &[Value]) -> Vec<(String, &Value)> {
+    let mut keyed: Vec<(String, &Value)> = Vec::new();
+    for (index, value) in values.iter().enumerate() {
+        let name = match value {
+            Value::String(value) => Some(value.clone()),
+            Value::Number(value) => Some(value.to_string()),
+            _ => ["name", "label", "pid"]
+                .iter()
+                .find_map(|field| match value.get(field)? {
+                    Value::String(key) => Some(key.clone()),
+                    Value::Number(key) => Some(key.to_string()),
+                    _ => None,
+                }),
+        }
+        .unwrap_or_else(|| index.to_string());
+        let repeats = keyed
+            .iter()
+            .filter(|(key, _)| key == &name || key.starts_with(&format!("{}#", name)))
+            .count();
+        let key = match repeats {
+            0 => name,
+            repeats => format!("{}#{}", name, repeats + 1),
+        };
+        keyed.push((key, value));
+    }
+    keyed
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
@@ -0,0 +1,150 @@
+//! Renders a report as StatsD gauges, named in the dotted hierarchy that
+//! Graphite stores them under.
+
+use {
+    crate::Report,
+    std::{
+        io,
+        net::{SocketAddr, ToSocketAddrs, UdpSocket},
+    },
+};
+
+/// The port StatsD listens on.
+pub const DEFAULT_PORT: u16 = 8125;
+
+/// Datagrams are kept below the usual Ethernet MTU so they are never
+/// fragmented.
+const MAX_DATAGRAM: usize = 1432;
+
+/// Parses a StatsD address such as `localhost:8125`, defaulting to port
+/// 8125.
+pub fn parse_address(input: &str) -> Result<SocketAddr, String> {
+    let input = input.trim();
+    let resolved = match input.to_socket_addrs() {
+        Ok(addresses) => addresses,
+        Err(_) => (input, DEFAULT_PORT)
+            .to_socket_addrs()
+            .map_err(|err| format!("invalid address `{}`: {}", input, err))?,
+    };
+    resolved
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("`{}` did not resolve to an address", input))
+}
+
+/// Renders the memory, CPU, load, uptime, disk and network figures of the
+/// report as `prefix.name:value|g` lines.
+pub fn render(report: &Report, prefix: &str) -> Vec<String> {
+    let mut gauges = Vec::new();
+    let mut gauge = |name: String, value: f64| {
+        gauges.push(format!("{}.{}:{}|g", prefix, name, value));
+    };
+    // Widening an `f32` would print digits it never had.
+    let percent = |value: f32| (f64::from(value) * 100.0).round() / 100.0;
+
+    if let Some(system) = &report.system {
+        gauge("memory.used_bytes".into(), system.memory_used_bytes as f64);
+        gauge(
+            "memory.total_bytes".into(),
+            system.memory_total_bytes as f64,
+        );
+        if let Some(memory) = &system.memory {
+            gauge(
+                "memory.available_bytes".into(),
+                memory.available_bytes as f64,
+            );
+        }
+        gauge("swap.used_bytes".into(), system.swap_used_bytes as f64);
+        gauge("swap.total_bytes".into(), system.swap_total_bytes as f64);
+        if let Some(usage) = &system.cpu.usage {
+            gauge("cpu.usage_percent".into(), percent(usage.global_percent));
+            for (index, core) in usage.per_core_percent.iter().enumerate() {
+                gauge(format!("cpu.core.{}.usage_percent", index), percent(*core));
+            }
+        }
+    }
+    if let Some(load) = &report.load {
+        gauge("load.one".into(), load.one);
+        gauge("load.five".into(), load.five);
+        gauge("load.fifteen".into(), load.fifteen);
+    }
+    if let Some(uptime) = &report.uptime {
+        gauge("uptime.seconds".into(), uptime.uptime_secs as f64);
+    }
+    if let Some(disk) = &report.disk {
+        for entry in &disk.disks {
+            let mount = component(&entry.mount_point);
+            gauge(
+                format!("disk.{}.used_bytes", mount),
+                entry.used_bytes as f64,
+            );
+            gauge(
+                format!("disk.{}.total_bytes", mount),
+                entry.total_bytes as f64,
+            );
+            gauge(
+                format!("disk.{}.available_bytes", mount),
+                entry.available_bytes as f64,
+            );
+        }
+    }
+    if let Some(network) = &report.network {
+        for interface in &network.interfaces {
+            let name = component(&interface.name);
+            gauge(
+                format!("network.{}.received_bytes", name),
+                interface.received_bytes as f64,
+            );
+            gauge(
+                format!("network.{}.transmitted_bytes", name),
+                interface.transmitted_bytes as f64,
+            );
+        }
+    }
+
+    gauges
+}
+
+/// Sends `lines` to `address`, as many to a datagram as fit.
+pub fn send(address: SocketAddr, lines: &[String]) -> io::Result<()> {
+    let local: SocketAddr = if address.is_ipv4() {
+        ([0, 0, 0, 0], 0).into()
+    } else {
+        ([0; 8], 0).into()
+    };
+    let socket = UdpSocket::bind(local)?;
+    let mut datagram = String::new();
+    for line in lines {
+        if !datagram.is_empty() && datagram.len() + 1 + line.len() > MAX_DATAGRAM {
+            socket.send_to(datagram.as_bytes(), address)?;
+            datagram.clear();
+        }
+        if !datagram.is_empty() {
+            datagram.push('\n');
+        }
+        datagram.push_str(line);
+    }
+    if !datagram.is_empty() {
+        socket.send_to(datagram.as_bytes(), address)?;
+    }
+    Ok(())
+}
+
+/// Turns a mount point or interface name into one component of a metric
+/// name: `/` becomes `root`, `/var/lib` `var_lib`, and anything StatsD or
+/// Graphite would split on becomes `_`.
+fn component(name: &str) -> String {
+    let name = name.trim_matches(|c| c == '/' || c == '\\');
+    if name.is_empty() {
+        return "root".into();
+    }
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,221 @@
+//! One-line templates such as `{os} | {mem_used_bytes|human}` for status
+//! bars and prompts.
+//!
+//! A placeholder is either a short alias from [`ALIASES`] or a dotted path
+//! into the JSON form of the report, like `system.cpu.brand` or
+//! `disk.disks.0.mount_point`. Appending `|human` scales a byte count. Use
+//! `{{` and `}}` for literal braces.
+
+use {
+    crate::{
+        format::{format_bytes, DisplayOptions},
+        Report, Sections,
+    },
+    serde_json::Value,
+};
+
+/// Short names for the most common fields and the paths they stand for.
+pub const ALIASES: &[(&str, &str)] = &[
+    ("os", "system.os"),
+    ("os_name", "system.os_name"),
+    ("os_version", "system.os_version"),
+    ("distro", "system.distribution_id"),
+    ("kernel", "system.kernel"),
+    ("shell", "system.shell"),
+    ("cpu", "system.cpu.brand"),
+    ("cores", "system.cpu.logical_cores"),
+    ("cpu_usage", "system.cpu.usage.global_percent"),
+    ("mem_used_bytes", "system.memory_used_bytes"),
+    ("mem_total_bytes", "system.memory_total_bytes"),
+    ("swap_used_bytes", "system.swap_used_bytes"),
+    ("swap_total_bytes", "system.swap_total_bytes"),
+    ("load1", "load.one"),
+    ("load5", "load.five"),
+    ("load15", "load.fifteen"),
+    ("uptime_secs", "uptime.uptime_secs"),
+    ("hostname", "host.hostname"),
+    ("user", "host.username"),
+    ("rustc", "rust.compiler.version"),
+    ("channel", "rust.compiler.channel"),
+    ("cargo", "rust.cargo.version"),
+    ("project", "project.name"),
+    ("project_version", "project.version"),
+];
+
+#[derive(Clone, Debug)]
+enum Part {
+    Text(String),
+    Field { path: Vec<String>, human: bool },
+}
+
+/// A parsed template.
+#[derive(Clone, Debug)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => return Err("unclosed `{` in template".into()),
+                        }
+                    }
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(parse_placeholder(&placeholder)?);
+                }
+                '}' => return Err("unmatched `}` in template".into()),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+
+        Ok(Self { parts })
+    }
+
+    /// The sections the placeholders refer to.
+    pub fn sections(&self) -> Sections {
+        let names: Vec<&str> = self
+            .fields()
+            .filter_map(|path| path.first().map(String::as_str))
+            .collect();
+        // Placeholders were checked against the section names when parsing.
+        Sections::from_names(&names).unwrap_or_else(|_| Sections::none())
+    }
+
+    /// Whether any placeholder needs CPU usage to be sampled.
+    pub fn uses_cpu_usage(&self) -> bool {
+        self.fields()
+            .any(|path| path.len() >= 3 && path[0] == "system" && path[2] == "usage")
+    }
+
+    /// Fills in the placeholders from `report`, leaving unknown values empty.
+    pub fn render(&self, report: &Report, options: &DisplayOptions) -> String {
+        let report = serde_json::to_value(report).unwrap_or(Value::Null);
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => text.clone(),
+                Part::Field { path, human } => {
+                    let value = path.iter().try_fold(&report, |value, segment| {
+                        match segment.parse::<usize>() {
+                            Ok(index) if value.is_array() => value.get(index),
+                            _ => value.get(segment),
+                        }
+                    });
+                    match value {
+                        Some(Value::Number(number)) if *human => number
+                            .as_u64()
+                            .map(|bytes| format_bytes(bytes, options.units))
+                            .unwrap_or_else(|| number.to_string()),
+                        Some(value) => render_value(value),
+                        None => String::new(),
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn fields(&self) -> impl Iterator<Item = &Vec<String>> {
+        self.parts.iter().filter_map(|part| match part {
+            Part::Field { path, .. } => Some(path),
+            Part::Text(_) => None,
+        })
+    }
+}
+
+fn parse_placeholder(placeholder: &str) -> Result<Part, String> {
+    let (name, filter) = match placeholder.split_once('|') {
+        Some((name, filter)) => (name.trim(), Some(filter.trim())),
+        None => (placeholder.trim(), None),
+    };
+    let human = match filter {
+        None => false,
+        Some("human") => true,
+        Some(filter) => return Err(format!("unknown template filter `{}`", filter)),
+    };
+
+    let path = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map_or(name, |(_, path)| path);
+    let path: Vec<String> = path.split('.').map(str::to_string).collect();
+    if Sections::from_names(&path[..1]).is_err() {
+        return Err(format!("unknown template field `{}`", name));
+    }
+
+    Ok(Part::Field { path, human })
+}
+
+fn render_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => match number.as_f64() {
+            Some(float) if number.is_f64() => format!("{:.2}", float),
+            _ => number.to_string(),
+        },
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(template: &Template) -> Vec<String> {
+        template.fields().map(|path| path.join(".")).collect()
+    }
+
+    #[test]
+    fn resolves_aliases_and_paths() {
+        let template =
+            Template::parse("{hostname}: {mem_used_bytes|human} {{ {disk.disks.0.name} }}")
+                .unwrap();
+        assert_eq!(
+            paths(&template),
+            [
+                "host.hostname",
+                "system.memory_used_bytes",
+                "disk.disks.0.name"
+            ]
+        );
+        assert!(matches!(
+            &template.parts[2],
+            Part::Field { human: true, .. }
+        ));
+        let sections = template.sections();
+        assert!(sections.host && sections.system && sections.disk);
+        assert!(!sections.network);
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_bad_braces() {
+        assert!(Template::parse("{mem_used}").is_err());
+        assert!(Template::parse("{os|loud}").is_err());
+        assert!(Template::parse("{os").is_err());
+        assert!(Template::parse("os}").is_err());
+    }
+}
@@ -0,0 +1,383 @@
+//! An htop-style dashboard with tabs for the CPU, memory, disks, network and
+//! processes, redrawn as new reports come in.
+
+use {
+    crate::{
+        component::{
+            network::NetworkInfo,
+            process::{ProcessEntry, ProcessSort},
+        },
+        format::{format_bytes, DisplayOptions},
+        Report,
+    },
+    ratatui::{
+        crossterm::event::{self, Event, KeyCode, KeyEventKind},
+        layout::{Constraint, Layout, Rect},
+        style::{Color, Modifier, Style},
+        text::Line,
+        widgets::{Block, Cell, Gauge, Paragraph, Row, Table, TableState, Tabs},
+        DefaultTerminal, Frame,
+    },
+    std::{
+        io,
+        sync::mpsc::{self, Receiver},
+        thread,
+        time::{Duration, Instant},
+    },
+};
+
+/// How long CPU usage is sampled for on each refresh.
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long to wait for a key press before checking for a new report.
+const INPUT_TIMEOUT: Duration = Duration::from_millis(100);
+
+const TABS: [&str; 5] = ["CPU", "Memory", "Disks", "Network", "Processes"];
+
+/// Runs the dashboard until the user quits, collecting a new report every
+/// `interval` on a background thread.
+pub fn run(interval: Duration, options: DisplayOptions) -> io::Result<()> {
+    let reports = spawn_collector(interval);
+    ratatui::run(|terminal| App::new(options).run(terminal, reports))
+}
+
+fn spawn_collector(interval: Duration) -> Receiver<Report> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let builder = Report::builder()
+            .cpu_usage(SAMPLE_INTERVAL)
+            .disk()
+            .network()
+            .processes();
+        let mut report = builder.clone().build();
+        // The receiver is gone once the dashboard quits.
+        while sender.send(report.clone()).is_ok() {
+            thread::sleep(interval);
+            builder.refresh(&mut report);
+        }
+    });
+    receiver
+}
+
+/// Receive and transmit rates of one interface in bytes per second.
+struct Throughput {
+    name: String,
+    received: f64,
+    transmitted: f64,
+}
+
+struct App {
+    options: DisplayOptions,
+    tab: usize,
+    report: Option<Report>,
+    received_at: Instant,
+    throughput: Vec<Throughput>,
+    sort: ProcessSort,
+    processes: TableState,
+}
+
+impl App {
+    fn new(options: DisplayOptions) -> Self {
+        Self {
+            options,
+            tab: 0,
+            report: None,
+            received_at: Instant::now(),
+            throughput: Vec::new(),
+            sort: ProcessSort::Cpu,
+            processes: TableState::default().with_selected(0),
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal, reports: Receiver<Report>) -> io::Result<()> {
+        loop {
+            while let Ok(report) = reports.try_recv() {
+                self.update(report);
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(INPUT_TIMEOUT)? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => {
+                    self.tab = (self.tab + 1) % TABS.len()
+                }
+                KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+                    self.tab = (self.tab + TABS.len() - 1) % TABS.len()
+                }
+                KeyCode::Char(digit @ '1'..='5') => self.tab = digit as usize - '1' as usize,
+                KeyCode::Down | KeyCode::Char('j') => self.processes.select_next(),
+                KeyCode::Up | KeyCode::Char('k') => self.processes.select_previous(),
+                KeyCode::Char('s') => self.sort = self.sort.next(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Takes a new report, deriving network rates from the previous one.
+    fn update(&mut self, report: Report) {
+        let elapsed = self.received_at.elapsed().as_secs_f64();
+        if let (Some(previous), Some(current)) = (
+            self.report
+                .as_ref()
+                .and_then(|report| report.network.as_ref()),
+            report.network.as_ref(),
+        ) {
+            self.throughput = throughput(previous, current, elapsed);
+        }
+        self.report = Some(report);
+        self.received_at = Instant::now();
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tabs, body, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(
+            Tabs::new(TABS)
+                .select(self.tab)
+                .block(Block::bordered().title(" envin "))
+                .highlight_style(Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            tabs,
+        );
+        frame.render_widget(
+            Line::from(" q quit  tab/←/→ switch tabs  ↑/↓ scroll  s sort processes")
+                .style(Style::new().add_modifier(Modifier::DIM)),
+            help,
+        );
+
+        let Some(report) = self.report.take() else {
+            frame.render_widget(Paragraph::new("Collecting..."), body);
+            return;
+        };
+        match self.tab {
+            0 => self.draw_cpu(frame, body, &report),
+            1 => self.draw_memory(frame, body, &report),
+            2 => self.draw_disks(frame, body, &report),
+            3 => self.draw_network(frame, body, &report),
+            _ => self.draw_processes(frame, body, &report),
+        }
+        self.report = Some(report);
+    }
+
+    fn draw_cpu(&self, frame: &mut Frame, area: Rect, report: &Report) {
+        let Some(system) = &report.system else {
+            return;
+        };
+        let cpu = &system.cpu;
+        let usage = cpu.usage.as_ref();
+        let cores = usage.map_or(&[][..], |usage| &usage.per_core_percent[..]);
+
+        let mut constraints = vec![Constraint::Length(3)];
+        constraints.extend(cores.iter().map(|_| Constraint::Length(1)));
+        let areas = Layout::vertical(constraints).split(area);
+
+        let global = usage.map_or(0.0, |usage| usage.global_percent);
+        frame.render_widget(
+            percent_gauge(global).block(Block::bordered().title(format!(" {} ", cpu.brand.trim()))),
+            areas[0],
+        );
+        for (index, percent) in cores.iter().enumerate() {
+            let area = areas[index + 1];
+            let [label, gauge] =
+                Layout::horizontal([Constraint::Length(10), Constraint::Min(0)]).areas(area);
+            frame.render_widget(Line::from(format!(" Core {:<3}", index)), label);
+            frame.render_widget(percent_gauge(*percent), gauge);
+        }
+    }
+
+    fn draw_memory(&self, frame: &mut Frame, area: Rect, report: &Report) {
+        let Some(system) = &report.system else {
+            return;
+        };
+        let [memory, swap] =
+            Layout::vertical([Constraint::Length(3), Constraint::Length(3)]).areas(area);
+        frame.render_widget(
+            self.usage_gauge(system.memory_used_bytes, system.memory_total_bytes)
+                .block(Block::bordered().title(" Memory ")),
+            memory,
+        );
+        frame.render_widget(
+            self.usage_gauge(system.swap_used_bytes, system.swap_total_bytes)
+                .block(Block::bordered().title(" Swap ")),
+            swap,
+        );
+    }
+
+    fn draw_disks(&self, frame: &mut Frame, area: Rect, report: &Report) {
+        let Some(disk) = &report.disk else {
+            return;
+        };
+        let areas = Layout::vertical(disk.disks.iter().map(|_| Constraint::Length(3))).split(area);
+        for (disk, area) in disk.disks.iter().zip(areas.iter()) {
+            frame.render_widget(
+                self.usage_gauge(disk.used_bytes, disk.total_bytes)
+                    .block(Block::bordered().title(format!(
+                        " {} on {} ({}) ",
+                        disk.name, disk.mount_point, disk.file_system
+                    ))),
+                *area,
+            );
+        }
+    }
+
+    fn draw_network(&self, frame: &mut Frame, area: Rect, report: &Report) {
+        let Some(network) = &report.network else {
+            return;
+        };
+        let rows = network.interfaces.iter().map(|interface| {
+            let rate = self
+                .throughput
+                .iter()
+                .find(|throughput| throughput.name == interface.name);
+            Row::new([
+                interface.name.clone(),
+                self.rate(rate.map(|rate| rate.received)),
+                self.rate(rate.map(|rate| rate.transmitted)),
+                format_bytes(interface.received_bytes, self.options.units),
+                format_bytes(interface.transmitted_bytes, self.options.units),
+                interface.ip_addresses.join(", "),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Length(14),
+                Constraint::Length(14),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Min(0),
+            ],
+        )
+        .header(header([
+            "Interface",
+            "Receive",
+            "Transmit",
+            "Received",
+            "Sent",
+            "Addresses",
+        ]))
+        .block(Block::bordered());
+        frame.render_widget(table, area);
+    }
+
+    fn draw_processes(&mut self, frame: &mut Frame, area: Rect, report: &Report) {
+        let Some(processes) = &report.processes else {
+            return;
+        };
+        let mut processes: Vec<&ProcessEntry> = processes.processes.iter().collect();
+        processes.sort_by(|a, b| self.sort.compare(a, b));
+
+        let rows = processes.iter().map(|process| {
+            Row::new([
+                Cell::from(process.pid.to_string()),
+                Cell::from(format!("{:.1}", process.cpu_usage)),
+                Cell::from(format_bytes(process.memory_bytes, self.options.units)),
+                Cell::from(process.status.clone()),
+                Cell::from(process.name.clone()),
+            ])
+        });
+        let sorted_by = match self.sort {
+            ProcessSort::Cpu => "CPU",
+            ProcessSort::Memory => "memory",
+        };
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(7),
+                Constraint::Length(12),
+                Constraint::Length(10),
+                Constraint::Min(0),
+            ],
+        )
+        .header(header(["PID", "CPU %", "Memory", "Status", "Name"]))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .block(Block::bordered().title(format!(
+            " {} processes, sorted by {} ",
+            processes.len(),
+            sorted_by
+        )));
+        frame.render_stateful_widget(table, area, &mut self.processes);
+    }
+
+    fn usage_gauge(&self, used: u64, total: u64) -> Gauge<'static> {
+        let ratio = if total == 0 {
+            0.0
+        } else {
+            used as f64 / total as f64
+        };
+        gauge(ratio).label(format!(
+            "{} / {} ({:.1}%)",
+            format_bytes(used, self.options.units),
+            format_bytes(total, self.options.units),
+            ratio * 100.0
+        ))
+    }
+
+    fn rate(&self, bytes_per_second: Option<f64>) -> String {
+        bytes_per_second
+            .map(|rate| format!("{}/s", format_bytes(rate as u64, self.options.units)))
+            .unwrap_or_else(|| "-".into())
+    }
+}
+
+fn header<const N: usize>(titles: [&'static str; N]) -> Row<'static> {
+    Row::new(titles).style(Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+}
+
+fn percent_gauge(percent: f32) -> Gauge<'static> {
+    gauge(f64::from(percent) / 100.0).label(format!("{:.1}%", percent))
+}
+
+/// A gauge that turns yellow past 70% and red past 90%.
+fn gauge(ratio: f64) -> Gauge<'static> {
+    let ratio = ratio.clamp(0.0, 1.0);
+    let color = if ratio > 0.9 {
+        Color::Red
+    } else if ratio > 0.7 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    Gauge::default()
+        .gauge_style(Style::new().fg(color))
+        .ratio(ratio)
+}
+
+fn throughput(previous: &NetworkInfo, current: &NetworkInfo, elapsed: f64) -> Vec<Throughput> {
+    current
+        .interfaces
+        .iter()
+        .filter_map(|interface| {
+            let before = previous
+                .interfaces
+                .iter()
+                .find(|before| before.name == interface.name)?;
+            Some(Throughput {
+                name: interface.name.clone(),
+                received: interface
+                    .received_bytes
+                    .saturating_sub(before.received_bytes) as f64
+                    / elapsed,
+                transmitted: interface
+                    .transmitted_bytes
+                    .saturating_sub(before.transmitted_bytes) as f64
+                    / elapsed,
+            })
+        })
+        .collect()
+}
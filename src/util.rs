@@ -0,0 +1,278 @@
+use {
+    std::{
+        env, fs,
+        io::{self, Read, Write},
+        path::{Path, PathBuf},
+        process::{self, Command, Stdio},
+        sync::atomic::{AtomicU64, Ordering},
+        thread,
+        time::{Duration, Instant},
+    },
+    which::which,
+};
+
+/// How long [`command_output`] lets a command run, so a probe stuck on a
+/// daemon that never answers cannot hang the whole report.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs a command and returns its trimmed stdout if it exited successfully
+/// within ten seconds.
+pub(crate) fn command_output(command: &str, args: &[&str]) -> Option<String> {
+    let (stdout, _) = run(command, args, COMMAND_TIMEOUT)?;
+    Some(stdout.trim().to_string())
+}
+
+/// Reads a kernel variable with `sysctl -n`, as on macOS and the BSDs.
+pub(crate) fn sysctl(name: &str) -> Option<String> {
+    command_output("sysctl", &["-n", name]).filter(|value| !value.is_empty())
+}
+
+/// Whether `envin` was built for one of the BSDs.
+pub(crate) fn is_bsd() -> bool {
+    cfg!(any(
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly"
+    ))
+}
+
+/// Like [`command_output`], but gives up on commands still running after
+/// `timeout`. Falls back to stderr for tools such as older Pythons that
+/// print their version there.
+pub(crate) fn command_output_timeout(
+    command: &str,
+    args: &[&str],
+    timeout: Duration,
+) -> Option<String> {
+    let (stdout, stderr) = run(command, args, timeout)?;
+    let output = if stdout.trim().is_empty() {
+        stderr
+    } else {
+        stdout
+    };
+    Some(output.trim().to_string())
+}
+
+/// Runs a command, killing it after `timeout`, and returns its stdout and
+/// stderr if it exited successfully.
+fn run(command: &str, args: &[&str], timeout: Duration) -> Option<(String, String)> {
+    which(command).ok()?;
+    let mut child = Command::new(command)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    // Read both pipes while waiting so a chatty command cannot fill them up
+    // and block.
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    // Most commands finish within milliseconds, so polling starts fast.
+    let mut poll = Duration::from_millis(1);
+    let status = loop {
+        match child.try_wait().ok()? {
+            Some(status) => break status,
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            None => {
+                thread::sleep(poll);
+                poll = (poll * 2).min(Duration::from_millis(10));
+            }
+        }
+    };
+    if !status.success() {
+        return None;
+    }
+
+    Some((
+        stdout.join().unwrap_or_default(),
+        stderr.join().unwrap_or_default(),
+    ))
+}
+
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+/// Picks the first word that is a version, as in `GNU bash, version
+/// 5.2.15(1)-release`, `v20.11.0` or `go1.22.1`, and keeps its leading
+/// dotted number.
+pub(crate) fn parse_version(output: &str) -> Option<String> {
+    let line = output.lines().next()?;
+    line.split_whitespace()
+        .map(|word| {
+            word.trim_start_matches('"')
+                .trim_start_matches("go")
+                .trim_start_matches('v')
+        })
+        .filter(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|word| {
+            let version: String = word
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == '.')
+                .collect();
+            version.trim_end_matches('.').to_string()
+        })
+        .find(|version| !version.is_empty())
+}
+
+pub(crate) fn read_trimmed<P: AsRef<Path>>(path: P) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty())
+}
+
+pub(crate) fn read_hex_id(path: &Path) -> Option<u16> {
+    let value = read_trimmed(path)?;
+    u16::from_str_radix(value.trim_start_matches("0x"), 16).ok()
+}
+
+/// `ConvertTo-Json` emits a bare object for a single result and an array
+/// otherwise.
+pub(crate) fn json_objects(output: &str) -> Vec<serde_json::Value> {
+    match output.parse::<serde_json::Value>() {
+        Ok(serde_json::Value::Array(values)) => values,
+        Ok(value @ serde_json::Value::Object(_)) => vec![value],
+        _ => Vec::new(),
+    }
+}
+
+pub(crate) fn json_objects_in<'a>(
+    value: &'a serde_json::Value,
+    key: &str,
+) -> Vec<&'a serde_json::Value> {
+    value
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|values| values.iter().collect())
+        .unwrap_or_default()
+}
+
+pub(crate) fn json_string(value: &serde_json::Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Parses sizes such as `1536 MB` or `8 GB` as reported by `system_profiler`.
+pub(crate) fn parse_size(size: &str) -> Option<u64> {
+    let (value, unit) = size.trim().split_once(' ')?;
+    let value: u64 = value.parse().ok()?;
+    match unit {
+        "KB" => Some(value << 10),
+        "MB" => Some(value << 20),
+        "GB" => Some(value << 30),
+        "TB" => Some(value << 40),
+        _ => None,
+    }
+}
+
+/// Parses the CPU lists of sysfs, such as `0-3,8-11` or `5`.
+pub(crate) fn parse_cpu_list(list: &str) -> Vec<usize> {
+    list.trim()
+        .split(',')
+        .filter_map(|range| match range.split_once('-') {
+            Some((first, last)) => Some(first.parse().ok()?..=last.parse().ok()?),
+            None => {
+                let cpu = range.parse().ok()?;
+                Some(cpu..=cpu)
+            }
+        })
+        .flatten()
+        .collect()
+}
+
+pub(crate) fn format_duration(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m {}s", minutes, secs % 60)
+    }
+}
+
+/// Matches `text` against `pattern`, where `*` stands for any run of
+/// characters.
+pub(crate) fn glob(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|&start| text.is_char_boundary(start))
+                .any(|start| glob(rest, &text[start..]))
+        }
+    }
+}
+
+/// A temporary file only the current user can read, removed when dropped.
+/// Secrets such as auth headers are handed to `curl` through one, as
+/// arguments would show up in the process list.
+pub(crate) struct PrivateFile {
+    path: PathBuf,
+}
+
+impl PrivateFile {
+    pub(crate) fn create(contents: &str) -> io::Result<Self> {
+        static COUNT: AtomicU64 = AtomicU64::new(0);
+        let path = env::temp_dir().join(format!(
+            "envin-{}-{}",
+            process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&path)?;
+        // Wrapped before writing so a failed write still removes the file.
+        let private = Self { path };
+        file.write_all(contents.as_bytes())?;
+        Ok(private)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for PrivateFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Writes `headers` to a [`PrivateFile`] for `curl -H @file`, or nothing
+/// when there are none.
+pub(crate) fn curl_header_file(headers: &[(String, String)]) -> io::Result<Option<PrivateFile>> {
+    if headers.is_empty() {
+        return Ok(None);
+    }
+    let contents: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\n", name, value))
+        .collect();
+    PrivateFile::create(&contents).map(Some)
+}